@@ -0,0 +1,125 @@
+//! `cr_program_settings_derive` provides `#[derive(ProgramSettings)]` and `settings_path!` for `cr_program_settings`.
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, DeriveInput, LitStr, Token};
+
+/// Derives the `ProgramSettings` trait for a struct or enum.
+///
+/// Syntax:
+///     #[settings(folder = "my_app")] // file name defaults to the type name, e.g. `UiSettings.ser`
+///     #[settings(folder = "my_app", file = "ui.toml")]
+///
+/// The folder must always be given, as defaulting to a crate name picks up the name of whichever crate the
+/// type is defined in, which is not always the application.
+#[proc_macro_derive(ProgramSettings, attributes(settings))]
+pub fn derive_program_settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let mut folder: Option<LitStr> = None;
+    let mut file: Option<LitStr> = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("settings"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("folder") {
+                folder = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("file") {
+                file = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown settings attribute, expected `folder` or `file`"))
+            }
+        });
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+    }
+
+    let name = &input.ident;
+
+    let folder = match folder {
+        Some(folder) => folder,
+        None => {
+            return syn::Error::new_spanned(
+                name,
+                "ProgramSettings requires a folder, e.g. #[settings(folder = \"my_app\")]",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    let file = file.unwrap_or_else(|| LitStr::new(&format!("{}.ser", name), name.span()));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::cr_program_settings::program_settings::ProgramSettings for #name #ty_generics #where_clause {
+            const FOLDER: &'static str = #folder;
+            const FILE_NAME: &'static str = #file;
+        }
+    };
+
+    expanded.into()
+}
+
+/// Creates a `location::SettingsLocation` from string literals that are checked at compile time.
+///
+/// Syntax:
+///     settings_path!("config.toml") // the folder defaults to the crate name, or the folder set with `set_default_folder()`
+///     settings_path!("my_app", "config.toml")
+///
+/// Each name must be a non-empty string literal without path separators, and must not be `.` or `..`,
+/// so a typo such as `settings_path!("my_app/config.toml")` fails to compile.
+#[proc_macro]
+pub fn settings_path(input: TokenStream) -> TokenStream {
+    let names = parse_macro_input!(input with Punctuated::<LitStr, Token![,]>::parse_terminated);
+
+    let names: Vec<&LitStr> = names.iter().collect();
+    if let Err(err) = names.iter().try_for_each(|name| check_path_name(name)) {
+        return err.to_compile_error().into();
+    }
+
+    let expanded = match names.as_slice() {
+        [file] => quote! {
+            ::cr_program_settings::location::SettingsLocation::new(
+                ::cr_program_settings::default_folder(env!("CARGO_CRATE_NAME")),
+                #file,
+            )
+        },
+        [folder, file] => quote! {
+            ::cr_program_settings::location::SettingsLocation::new(#folder, #file)
+        },
+        _ => {
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "settings_path! expects a file name, or a folder and a file name, e.g. settings_path!(\"my_app\", \"config.toml\")",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    expanded.into()
+}
+
+/// Returns an error spanning `name` if it is not a single, non-empty path component
+fn check_path_name(name: &LitStr) -> syn::Result<()> {
+    let value = name.value();
+    let message = if value.is_empty() {
+        "settings path names must not be empty"
+    } else if value.contains(['/', '\\']) {
+        "settings path names must not contain path separators, pass the folder and file name as separate arguments"
+    } else if value == "." || value == ".." {
+        "settings path names must not be `.` or `..`"
+    } else {
+        return Ok(());
+    };
+    Err(syn::Error::new_spanned(name, message))
+}
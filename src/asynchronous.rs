@@ -0,0 +1,95 @@
+//! Async settings source file, for use inside a tokio runtime
+//!
+//! Serialization and deserialization happen on the calling task, while the blocking file IO is moved onto
+//! tokio's blocking thread pool using `spawn_blocking`. The IO goes through the same code as the sync functions,
+//! so the active backend, errors, and `SETTINGS_PATHS` behave identically.
+//!
+//! `testing::with_temp_settings_dir` is per thread, so it does not apply to the blocking thread pool.
+#![warn(missing_docs)]
+
+use crate::{
+    deserialize_settings, read_settings_bytes, serialize_settings, write_settings_bytes,
+    LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// Async version of `save_settings_with_filename()`, saves to `USER_HOME/crate_name/file_name`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// runtime.block_on(async {
+///     let settings = Settings{ setting1: 12 };
+///
+///     save_settings_async!(settings, "async_settings.ser").await.expect("Unable to save settings");
+///
+///     let loaded_settings = load_settings_async!(Settings, "async_settings.ser").await.expect("Unable to load settings");
+///     assert_eq!(settings,loaded_settings);
+/// });
+/// ```
+pub async fn save_settings_with_filename_async<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let serialized_data = serialize_settings(settings)?;
+    let crate_name = PathBuf::from(crate_name);
+    let file_name = file_name.to_string();
+    match tokio::task::spawn_blocking(move || {
+        write_settings_bytes(&crate_name, &file_name, serialized_data.as_bytes())
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => Err(SaveSettingsError::IOError(io::Error::other(err))),
+    }
+}
+
+/// Async version of `save_settings()`, saves to `USER_HOME/crate_name/crate_name.ser`
+pub async fn save_settings_async<T>(crate_name: &str, settings: &T) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_with_filename_async(crate_name, &format!("{}.ser", crate_name), settings).await
+}
+
+/// Async version of `load_settings_with_filename()`, loads from `USER_HOME/crate_name/file_name`
+///
+/// For example usage, see `save_settings_with_filename_async()` documentation.
+pub async fn load_settings_with_filename_async<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let crate_name = PathBuf::from(crate_name);
+    let file_name = file_name.to_string();
+    match tokio::task::spawn_blocking(move || read_settings_bytes(&crate_name, &file_name)).await {
+        Ok(Ok((settings_file_path, file_data))) => {
+            deserialize_settings(settings_file_path, file_data)
+        }
+        Ok(Err(err)) => Err(err),
+        Err(err) => Err(LoadSettingsError::IOError(io::Error::other(err))),
+    }
+}
+
+/// Async version of `load_settings()`, loads from `USER_HOME/crate_name/crate_name.ser`
+pub async fn load_settings_async<T>(crate_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_with_filename_async(crate_name, &format!("{}.ser", crate_name)).await
+}
@@ -0,0 +1,197 @@
+//! Debounced background autosave source file
+#![warn(missing_docs)]
+
+use crate::settings_container::SettingsContainer;
+use crate::{get_home_override, set_home_override, SaveSettingsError};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Callback invoked with every error encountered by a background save
+type ErrorCallback = Box<dyn Fn(&SaveSettingsError) + Send + Sync>;
+
+/// State shared between an `AutoSaver` and its worker thread
+struct Shared<T> {
+    container: Mutex<SettingsContainer<T>>,
+    state: Mutex<WorkerState>,
+    wake: Condvar,
+    last_error: Mutex<Option<SaveSettingsError>>,
+    on_error: Mutex<Option<ErrorCallback>>,
+}
+
+/// Flags the worker thread waits on
+#[derive(Default)]
+struct WorkerState {
+    dirty: bool,
+    shutdown: bool,
+}
+
+impl<T> Shared<T>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    /// Saves the container, recording any error, and clears the dirty flag
+    fn save(&self) -> Result<(), ()> {
+        self.state.lock().unwrap().dirty = false;
+        match self.container.lock().unwrap().save() {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let mut last_error = self.last_error.lock().unwrap();
+                if let Some(on_error) = self.on_error.lock().unwrap().as_ref() {
+                    on_error(&err);
+                }
+                *last_error = Some(err);
+                Err(())
+            }
+        }
+    }
+}
+
+/// Wraps a `SettingsContainer` and saves it on a background thread at most once every `debounce` window,
+/// coalescing any number of modifications made within the window into a single write.
+/// Dropping the `AutoSaver` stops the thread and performs a final save if there are unsaved changes.
+///
+/// The background thread uses the same users home as the thread that created the `AutoSaver`,
+/// including a `testing::with_temp_settings_dir` override.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::autosave::AutoSaver;
+/// use cr_program_settings::settings_container::SettingsContainer;
+///
+/// #[derive(Serialize,Deserialize,PartialEq,Debug,Clone)]
+/// struct AudioSettings {
+/// volume: u32,
+/// }
+///
+/// let container = SettingsContainer::new(AudioSettings{ volume: 0 },env!("CARGO_CRATE_NAME"),"doctest_autosave.ser");
+/// let auto_saver = AutoSaver::new(container, Duration::from_millis(100));
+///
+/// // the user drags a slider, only the final value needs to reach the disk
+/// for volume in 0..=100 {
+///     auto_saver.modify(|settings| settings.volume = volume);
+/// }
+///
+/// auto_saver.flush().expect("Unable to save settings");
+/// drop(auto_saver);
+///
+/// let loaded = SettingsContainer::<AudioSettings>::load(env!("CARGO_CRATE_NAME"),"doctest_autosave.ser").unwrap();
+/// assert_eq!(loaded.get_settings(), &Some(AudioSettings{ volume: 100 }));
+/// ```
+pub struct AutoSaver<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Send + 'static,
+{
+    shared: Arc<Shared<T>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<T> AutoSaver<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Send + 'static,
+{
+    /// Creates a new `AutoSaver` and starts its background thread
+    pub fn new(container: SettingsContainer<T>, debounce: Duration) -> Self {
+        let shared = Arc::new(Shared {
+            container: Mutex::new(container),
+            state: Mutex::new(WorkerState::default()),
+            wake: Condvar::new(),
+            last_error: Mutex::new(None),
+            on_error: Mutex::new(None),
+        });
+
+        let worker_shared = shared.clone();
+        let home_override = get_home_override();
+        let worker = std::thread::spawn(move || {
+            set_home_override(home_override);
+            let shared = worker_shared;
+            loop {
+                let state = shared.state.lock().unwrap();
+                let state = shared
+                    .wake
+                    .wait_while(state, |state| !state.dirty && !state.shutdown)
+                    .unwrap();
+                if state.shutdown {
+                    break;
+                }
+                // wait out the debounce window so every change made within it is saved together
+                let (state, _) = shared
+                    .wake
+                    .wait_timeout_while(state, debounce, |state| !state.shutdown)
+                    .unwrap();
+                let shutdown = state.shutdown;
+                drop(state);
+                if shutdown {
+                    break;
+                }
+                let _ = shared.save();
+            }
+        });
+
+        Self {
+            shared,
+            worker: Some(worker),
+        }
+    }
+
+    /// Sets a callback that is invoked with every error encountered by a save, in addition to recording it.
+    /// The callback runs on the background thread and must not call `take_last_error()`.
+    pub fn on_error(self, callback: impl Fn(&SaveSettingsError) + Send + Sync + 'static) -> Self {
+        *self.shared.on_error.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Modifies the inner settings if present and schedules a save
+    pub fn modify(&self, f: impl FnOnce(&mut T)) {
+        if let Some(settings) = self.shared.container.lock().unwrap().get_mut_settings() {
+            f(settings);
+        }
+        self.mark_dirty();
+    }
+
+    /// Schedules a save without modifying the settings
+    pub fn mark_dirty(&self) {
+        self.shared.state.lock().unwrap().dirty = true;
+        self.shared.wake.notify_all();
+    }
+
+    /// Returns true if there are changes that have not been saved yet
+    pub fn is_dirty(&self) -> bool {
+        self.shared.state.lock().unwrap().dirty
+    }
+
+    /// Locks and returns the inner container for reading, changes made through it are not saved automatically,
+    /// call `mark_dirty()` afterwards if needed
+    pub fn container(&self) -> MutexGuard<'_, SettingsContainer<T>> {
+        self.shared.container.lock().unwrap()
+    }
+
+    /// Saves immediately, regardless of the debounce window
+    pub fn flush(&self) -> Result<(), SaveSettingsError> {
+        self.shared.state.lock().unwrap().dirty = false;
+        self.shared.container.lock().unwrap().save()
+    }
+
+    /// Takes the most recent error encountered by a background save, if any
+    pub fn take_last_error(&self) -> Option<SaveSettingsError> {
+        self.shared.last_error.lock().unwrap().take()
+    }
+}
+
+impl<T> Drop for AutoSaver<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Send + 'static,
+{
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.wake.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        if self.shared.state.lock().unwrap().dirty {
+            let _ = self.shared.save();
+        }
+    }
+}
@@ -0,0 +1,648 @@
+//! Storage backend source file
+#![warn(missing_docs)]
+
+use crate::permissions::{create_new_settings_file, create_settings_dir, create_settings_file};
+use crate::{get_settings_dir, get_settings_file_path, LoadSettingsError, SaveSettingsError};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+use std::{fs, io};
+
+/// Global storage backend, `None` means the default backend is used.
+static SETTINGS_BACKEND: RwLock<Option<Arc<dyn SettingsBackend + Send + Sync>>> = RwLock::new(None);
+
+/// Sets the storage backend that every save, load, and delete function in the library routes through.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::backend::{set_backend, MemoryBackend};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// set_backend(Box::new(MemoryBackend::new()));
+///
+/// let settings = Settings{ setting1: 64 };
+/// save_settings!(settings, "memory_only.ser").expect("Unable to save settings in memory");
+///
+/// let loaded_settings = load_settings!(Settings, "memory_only.ser").expect("Unable to load settings from memory");
+/// assert_eq!(settings,loaded_settings);
+///
+/// // nothing was written to the users home directory
+/// assert!(!get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("memory_only.ser").exists());
+/// ```
+pub fn set_backend(backend: Box<dyn SettingsBackend + Send + Sync>) {
+    *SETTINGS_BACKEND.write().unwrap() = Some(Arc::from(backend));
+}
+
+/// Restores the default backend, `LocalStorageBackend` on `wasm32` with the `wasm` feature, otherwise `FileSystemBackend`
+pub fn reset_backend() {
+    *SETTINGS_BACKEND.write().unwrap() = None;
+}
+
+/// Whether the `FileSystemBackend` waits for writes to reach the disk before returning
+static SYNC_WRITES: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether the `FileSystemBackend` flushes and syncs settings files to disk before a save returns, enabled by default.
+///
+/// When enabled, a successful save means the settings survive a power loss right after it, and renamed files,
+/// e.g. by `save_settings_batch()`, also sync their folder so the rename itself is durable.
+/// Disabling it makes saves faster, e.g. for settings that are saved very often and are cheap to lose.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::backend::{set_sync_writes, sync_writes};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// assert!(sync_writes());
+/// set_sync_writes(false);
+/// save_settings!(Settings{ setting1: 1 }, "unsynced.ser").unwrap();
+/// set_sync_writes(true);
+/// ```
+pub fn set_sync_writes(enabled: bool) {
+    SYNC_WRITES.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the `FileSystemBackend` syncs settings files to disk before a save returns
+pub fn sync_writes() -> bool {
+    SYNC_WRITES.load(Ordering::Relaxed)
+}
+
+/// Returns the currently active storage backend
+pub fn get_backend() -> Arc<dyn SettingsBackend + Send + Sync> {
+    match SETTINGS_BACKEND.read().unwrap().as_ref() {
+        None => default_backend(),
+        Some(backend) => backend.clone(),
+    }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+/// Returns the backend used when none has been set
+fn default_backend() -> Arc<dyn SettingsBackend + Send + Sync> {
+    Arc::new(FileSystemBackend)
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+/// Returns the backend used when none has been set
+fn default_backend() -> Arc<dyn SettingsBackend + Send + Sync> {
+    Arc::new(crate::local_storage::LocalStorageBackend)
+}
+
+#[derive(Debug)]
+/// Enum state representing the errors a storage backend can report
+pub enum BackendError {
+    /// The backend was unable to find the users home directory
+    FailedToGetUserHome,
+    /// In portable mode, the backend was unable to find the directory of the executable
+    FailedToGetExecutableDir(io::Error),
+    /// The settings file path exists, but is a directory rather than a file
+    PathIsDirectory(PathBuf),
+    /// The settings file path, or one of its components, is longer than the platform allows
+    PathTooLong(PathBuf),
+    /// The backend encountered an io error, `NotFound` is used for missing files
+    IOError(io::Error),
+    /// The storage, e.g. the browsers `localStorage`, is not available
+    StorageUnavailable,
+    /// The storage is full and could not hold the settings
+    QuotaExceeded,
+    /// The settings file already exists and was not replaced, returned by `SettingsBackend::write_new()`
+    AlreadyExists(PathBuf),
+}
+
+impl From<BackendError> for SaveSettingsError {
+    fn from(err: BackendError) -> Self {
+        match err {
+            BackendError::FailedToGetUserHome => SaveSettingsError::FailedToGetUserHome,
+            BackendError::FailedToGetExecutableDir(err) => {
+                SaveSettingsError::FailedToGetExecutableDir(err)
+            }
+            BackendError::PathIsDirectory(path) => SaveSettingsError::PathIsDirectory(path),
+            BackendError::PathTooLong(path) => SaveSettingsError::PathTooLong(path),
+            BackendError::IOError(err) => SaveSettingsError::IOError(err),
+            BackendError::StorageUnavailable => SaveSettingsError::StorageUnavailable,
+            BackendError::QuotaExceeded => SaveSettingsError::QuotaExceeded,
+            BackendError::AlreadyExists(path) => SaveSettingsError::AlreadyExists(path),
+        }
+    }
+}
+
+impl From<BackendError> for LoadSettingsError {
+    fn from(err: BackendError) -> Self {
+        match err {
+            BackendError::FailedToGetUserHome => LoadSettingsError::FailedToGetUserHome,
+            BackendError::FailedToGetExecutableDir(err) => {
+                LoadSettingsError::FailedToGetExecutableDir(err)
+            }
+            BackendError::PathIsDirectory(path) => LoadSettingsError::PathIsDirectory(path),
+            BackendError::PathTooLong(path) => LoadSettingsError::PathTooLong(path),
+            BackendError::IOError(err) => LoadSettingsError::IOError(err),
+            BackendError::StorageUnavailable => LoadSettingsError::StorageUnavailable,
+            BackendError::QuotaExceeded => LoadSettingsError::StorageUnavailable,
+            BackendError::AlreadyExists(path) => {
+                LoadSettingsError::IOError(BackendError::AlreadyExists(path).into())
+            }
+        }
+    }
+}
+
+impl From<BackendError> for io::Error {
+    fn from(err: BackendError) -> Self {
+        match err {
+            BackendError::FailedToGetUserHome => io::Error::new(
+                io::ErrorKind::NotFound,
+                "unable to find the users home directory",
+            ),
+            BackendError::FailedToGetExecutableDir(err) => err,
+            BackendError::PathIsDirectory(path) => io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("{} is a directory", path.display()),
+            ),
+            BackendError::PathTooLong(path) => io::Error::new(
+                io::ErrorKind::InvalidFilename,
+                format!("{} is too long", path.display()),
+            ),
+            BackendError::IOError(err) => err,
+            BackendError::StorageUnavailable => io::Error::new(
+                io::ErrorKind::Unsupported,
+                "settings storage is unavailable",
+            ),
+            BackendError::QuotaExceeded => io::Error::new(
+                io::ErrorKind::StorageFull,
+                "settings storage quota exceeded",
+            ),
+            BackendError::AlreadyExists(path) => io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ),
+        }
+    }
+}
+
+/// Trait for a place settings can be stored, `folder` is relative, e.g. the crate name, and `file` is the file name.
+pub trait SettingsBackend {
+    /// Reads the bytes of a stored settings file
+    fn read(&self, folder: &Path, file: &str) -> Result<Vec<u8>, BackendError>;
+    /// Writes the bytes of a settings file, replacing it if it exists
+    fn write(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError>;
+    /// Deletes a settings file
+    fn delete(&self, folder: &Path, file: &str) -> Result<(), BackendError>;
+    /// Deletes a folder and every settings file inside it.
+    /// If the folder is a symlink, only the link is removed and the folder it points to is left untouched
+    fn delete_folder(&self, folder: &Path) -> Result<(), BackendError>;
+    /// Deletes a folder like `delete_folder()`, but if the folder is a symlink, also deletes the folder it points to
+    /// along with every file inside it. The default implementation calls `delete_folder()`, for backends without links.
+    fn delete_folder_following_symlinks(&self, folder: &Path) -> Result<(), BackendError> {
+        self.delete_folder(folder)
+    }
+    /// Returns true if the settings file exists
+    fn exists(&self, folder: &Path, file: &str) -> bool;
+    /// Lists the names of every settings file directly inside the folder, sorted by name
+    fn list(&self, folder: &Path) -> Result<Vec<String>, BackendError>;
+    /// Lists the names of every folder directly inside the folder, e.g. `profiles`, sorted by name.
+    /// The default implementation lists none, for backends that store every file of a folder side by side.
+    fn list_folders(&self, _folder: &Path) -> Result<Vec<String>, BackendError> {
+        Ok(vec![])
+    }
+    /// Returns the location of the folder, used for the paths recorded in `SETTINGS_PATHS`
+    fn resolve(&self, folder: &Path) -> Option<PathBuf>;
+    /// Moves the settings file `from` to `to` within the folder, replacing `to` if it exists.
+    /// The default implementation copies the file and deletes the original, backends that can should replace it atomically.
+    fn rename(&self, folder: &Path, from: &str, to: &str) -> Result<(), BackendError> {
+        let bytes = self.read(folder, from)?;
+        self.write(folder, to, &bytes)?;
+        self.delete(folder, from)
+    }
+    /// Returns the size of a stored settings file in bytes.
+    /// The default implementation reads the whole file with `read()`, backends that can should look the size up instead.
+    fn file_size(&self, folder: &Path, file: &str) -> Result<u64, BackendError> {
+        Ok(self.read(folder, file)?.len() as u64)
+    }
+    /// Opens a stored settings file for reading, so formats that can parse from a stream do not need the whole file in memory.
+    /// The default implementation reads the whole file with `read()`, backends that store files on disk should stream them.
+    fn reader(&self, folder: &Path, file: &str) -> Result<Box<dyn BufRead>, BackendError> {
+        Ok(Box::new(Cursor::new(self.read(folder, file)?)))
+    }
+    /// Returns when a stored settings file was last modified.
+    /// The default implementation returns `NotFound` for a missing file and `Unsupported` otherwise, for backends that do not record it.
+    fn modified(&self, folder: &Path, file: &str) -> Result<SystemTime, BackendError> {
+        if !self.exists(folder, file) {
+            return Err(not_found(&folder.join(file)));
+        }
+        Err(BackendError::IOError(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the settings backend does not record modification times",
+        )))
+    }
+    /// Writes the bytes of a settings file only if it does not exist yet, otherwise returns `AlreadyExists`.
+    /// The default implementation checks `exists()` before writing, backends that can should check and create the file atomically.
+    fn write_new(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        if self.exists(folder, file) {
+            return Err(BackendError::AlreadyExists(folder.join(file)));
+        }
+        self.write(folder, file, bytes)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+/// The default backend, stores settings files in `USER_HOME/folder/file`
+pub struct FileSystemBackend;
+
+impl SettingsBackend for FileSystemBackend {
+    fn read(&self, folder: &Path, file: &str) -> Result<Vec<u8>, BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        if settings_file_path.is_dir() {
+            return Err(BackendError::PathIsDirectory(settings_file_path));
+        }
+        match File::open(&settings_file_path) {
+            Ok(mut file) => {
+                // preallocate the whole file when its size is known, so large files are not reallocated while reading
+                let capacity = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+                let mut file_data = Vec::with_capacity(usize::try_from(capacity).unwrap_or(0));
+                match file.read_to_end(&mut file_data) {
+                    Ok(_) => Ok(file_data),
+                    Err(err) => Err(BackendError::IOError(err)),
+                }
+            }
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+
+    fn file_size(&self, folder: &Path, file: &str) -> Result<u64, BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        match fs::metadata(&settings_file_path) {
+            Ok(metadata) if metadata.is_dir() => {
+                Err(BackendError::PathIsDirectory(settings_file_path))
+            }
+            Ok(metadata) => Ok(metadata.len()),
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+
+    fn modified(&self, folder: &Path, file: &str) -> Result<SystemTime, BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        match fs::metadata(&settings_file_path) {
+            Ok(metadata) if metadata.is_dir() => {
+                Err(BackendError::PathIsDirectory(settings_file_path))
+            }
+            Ok(metadata) => metadata.modified().map_err(BackendError::IOError),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(not_found(&settings_file_path))
+            }
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+
+    fn reader(&self, folder: &Path, file: &str) -> Result<Box<dyn BufRead>, BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        if settings_file_path.is_dir() {
+            return Err(BackendError::PathIsDirectory(settings_file_path));
+        }
+        match File::open(&settings_file_path) {
+            Ok(file) => Ok(Box::new(BufReader::new(file))),
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+
+    fn write(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        if settings_file_path.is_dir() {
+            return Err(BackendError::PathIsDirectory(settings_file_path));
+        }
+        if let Some(settings_path) = settings_file_path.parent() {
+            create_settings_dir(settings_path).map_err(|err| path_error(settings_path, err))?;
+        }
+        match create_settings_file(&settings_file_path) {
+            Ok(file) => write_file(file, bytes).map_err(BackendError::IOError),
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+
+    fn delete(&self, folder: &Path, file: &str) -> Result<(), BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        fs::remove_file(&settings_file_path).map_err(|err| path_error(&settings_file_path, err))
+    }
+
+    fn delete_folder(&self, folder: &Path) -> Result<(), BackendError> {
+        let settings_path = self.settings_dir(folder)?;
+        if is_symlink(&settings_path) {
+            return remove_symlink(&settings_path).map_err(|err| path_error(&settings_path, err));
+        }
+        fs::remove_dir_all(&settings_path).map_err(|err| path_error(&settings_path, err))
+    }
+
+    fn delete_folder_following_symlinks(&self, folder: &Path) -> Result<(), BackendError> {
+        let settings_path = self.settings_dir(folder)?;
+        if is_symlink(&settings_path) {
+            let target =
+                fs::canonicalize(&settings_path).map_err(|err| path_error(&settings_path, err))?;
+            fs::remove_dir_all(&target).map_err(|err| path_error(&target, err))?;
+            return remove_symlink(&settings_path).map_err(|err| path_error(&settings_path, err));
+        }
+        fs::remove_dir_all(&settings_path).map_err(|err| path_error(&settings_path, err))
+    }
+
+    fn exists(&self, folder: &Path, file: &str) -> bool {
+        match self.file_path(folder, file) {
+            Ok(settings_file_path) => settings_file_path.is_file(),
+            Err(_) => false,
+        }
+    }
+
+    fn list(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let settings_path = self.settings_dir(folder)?;
+        let mut files = vec![];
+        for entry in fs::read_dir(&settings_path).map_err(|err| path_error(&settings_path, err))? {
+            let path = entry.map_err(BackendError::IOError)?.path();
+            if path.is_file() {
+                if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
+                    files.push(file_name.to_string());
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn list_folders(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let settings_path = self.settings_dir(folder)?;
+        let mut folders = vec![];
+        for entry in fs::read_dir(&settings_path).map_err(|err| path_error(&settings_path, err))? {
+            let path = entry.map_err(BackendError::IOError)?.path();
+            if path.is_dir() {
+                if let Some(folder_name) = path.file_name().and_then(|name| name.to_str()) {
+                    folders.push(folder_name.to_string());
+                }
+            }
+        }
+        folders.sort();
+        Ok(folders)
+    }
+
+    fn resolve(&self, folder: &Path) -> Option<PathBuf> {
+        get_settings_dir(folder).ok()
+    }
+
+    fn rename(&self, folder: &Path, from: &str, to: &str) -> Result<(), BackendError> {
+        let from_path = self.file_path(folder, from)?;
+        let to_path = self.file_path(folder, to)?;
+        if to_path.is_dir() {
+            return Err(BackendError::PathIsDirectory(to_path));
+        }
+        fs::rename(&from_path, &to_path).map_err(|err| path_error(&to_path, err))?;
+        if sync_writes() {
+            if let Some(settings_path) = to_path.parent() {
+                sync_dir(settings_path).map_err(BackendError::IOError)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_new(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        let settings_file_path = self.file_path(folder, file)?;
+        if let Some(settings_path) = settings_file_path.parent() {
+            create_settings_dir(settings_path).map_err(|err| path_error(settings_path, err))?;
+        }
+        match create_new_settings_file(&settings_file_path) {
+            Ok(file) => write_file(file, bytes).map_err(BackendError::IOError),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                Err(BackendError::AlreadyExists(settings_file_path))
+            }
+            Err(err) => Err(path_error(&settings_file_path, err)),
+        }
+    }
+}
+
+impl FileSystemBackend {
+    /// Returns the full path of a settings folder
+    fn settings_dir(&self, folder: &Path) -> Result<PathBuf, BackendError> {
+        get_settings_dir(folder).map(long_path)
+    }
+
+    /// Returns the full path of a settings file
+    fn file_path(&self, folder: &Path, file: &str) -> Result<PathBuf, BackendError> {
+        get_settings_file_path(folder, file).map(long_path)
+    }
+}
+
+/// Writes the bytes to a settings file, then flushes and syncs it to disk if `sync_writes()` is enabled
+fn write_file(mut file: File, bytes: &[u8]) -> io::Result<()> {
+    file.write_all(bytes)?;
+    if sync_writes() {
+        file.flush()?;
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+/// Syncs a folder to disk, so renames and newly created files inside it survive a power loss
+fn sync_dir(path: &Path) -> io::Result<()> {
+    File::open(path)?.sync_all()
+}
+
+#[cfg(not(unix))]
+/// Folders can not be opened for syncing on this platform, renames are made durable by the OS
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Returns true if the path itself is a symlink, without following it
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+/// Removes a symlink without touching what it points to, links to folders are removed like folders on Windows
+fn remove_symlink(path: &Path) -> io::Result<()> {
+    fs::remove_dir(path).or_else(|_| fs::remove_file(path))
+}
+
+#[cfg(not(windows))]
+/// Removes a symlink without touching what it points to
+fn remove_symlink(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)
+}
+
+/// Maps an io error to `PathTooLong` if the OS rejected the path for its length, e.g. `ENAMETOOLONG` on unix
+/// or `ERROR_FILENAME_EXCED_RANGE` on Windows
+fn path_error(path: &Path, err: io::Error) -> BackendError {
+    if err.kind() == io::ErrorKind::InvalidFilename {
+        BackendError::PathTooLong(path.to_path_buf())
+    } else {
+        BackendError::IOError(err)
+    }
+}
+
+#[cfg(windows)]
+/// Windows limits paths to `MAX_PATH` characters unless they use the `\\?\` prefix
+const MAX_PATH: usize = 260;
+
+#[cfg(windows)]
+/// Adds the `\\?\` prefix to absolute drive paths longer than `MAX_PATH`, so deep settings folders can be used on Windows
+fn long_path(path: PathBuf) -> PathBuf {
+    use std::path::{Component, Prefix};
+    if path.as_os_str().len() < MAX_PATH {
+        return path;
+    }
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) if matches!(prefix.kind(), Prefix::Disk(_)) => {
+            // verbatim paths are not normalized by Windows, so rebuild the path with `\` separators first
+            let normalized: PathBuf = path.components().collect();
+            let mut verbatim = std::ffi::OsString::from(r"\\?\");
+            verbatim.push(normalized.as_os_str());
+            PathBuf::from(verbatim)
+        }
+        _ => path,
+    }
+}
+
+#[cfg(not(windows))]
+/// Paths are only length limited by the OS on this platform, so they are returned unchanged
+fn long_path(path: PathBuf) -> PathBuf {
+    path
+}
+
+#[derive(Debug, Default)]
+/// A backend that keeps settings files in memory, useful for unit tests and platforms without a filesystem.
+/// Paths recorded in `SETTINGS_PATHS` are relative, e.g. `crate_name/file_name`.
+pub struct MemoryBackend {
+    files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+}
+
+impl MemoryBackend {
+    /// Creates a new empty `MemoryBackend`
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Returns a `NotFound` io error for a missing in-memory path
+fn not_found(path: &Path) -> BackendError {
+    BackendError::IOError(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} does not exist", path.display()),
+    ))
+}
+
+impl SettingsBackend for MemoryBackend {
+    fn read(&self, folder: &Path, file: &str) -> Result<Vec<u8>, BackendError> {
+        let path = folder.join(file);
+        match self.files.lock().unwrap().get(&path) {
+            None => Err(not_found(&path)),
+            Some(bytes) => Ok(bytes.clone()),
+        }
+    }
+
+    fn write(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(folder.join(file), bytes.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, folder: &Path, file: &str) -> Result<(), BackendError> {
+        let path = folder.join(file);
+        match self.files.lock().unwrap().remove(&path) {
+            None => Err(not_found(&path)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn delete_folder(&self, folder: &Path) -> Result<(), BackendError> {
+        let mut files = self.files.lock().unwrap();
+        let count = files.len();
+        files.retain(|path, _| !path.starts_with(folder));
+        if files.len() == count {
+            Err(not_found(folder))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn exists(&self, folder: &Path, file: &str) -> bool {
+        self.files.lock().unwrap().contains_key(&folder.join(file))
+    }
+
+    fn file_size(&self, folder: &Path, file: &str) -> Result<u64, BackendError> {
+        let path = folder.join(file);
+        match self.files.lock().unwrap().get(&path) {
+            None => Err(not_found(&path)),
+            Some(bytes) => Ok(bytes.len() as u64),
+        }
+    }
+
+    fn list(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let files = self.files.lock().unwrap();
+        let mut names: Vec<String> = files
+            .keys()
+            .filter(|path| path.parent() == Some(folder))
+            .filter_map(|path| path.file_name().and_then(|name| name.to_str()))
+            .map(|name| name.to_string())
+            .collect();
+        if names.is_empty() && !files.keys().any(|path| path.starts_with(folder)) {
+            return Err(not_found(folder));
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn list_folders(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let files = self.files.lock().unwrap();
+        let mut names: Vec<String> = files
+            .keys()
+            .filter(|path| path.parent() != Some(folder))
+            .filter_map(|path| path.strip_prefix(folder).ok()?.iter().next())
+            .filter_map(|name| name.to_str())
+            .map(|name| name.to_string())
+            .collect();
+        if names.is_empty() && !files.keys().any(|path| path.starts_with(folder)) {
+            return Err(not_found(folder));
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn resolve(&self, folder: &Path) -> Option<PathBuf> {
+        Some(folder.to_path_buf())
+    }
+
+    fn rename(&self, folder: &Path, from: &str, to: &str) -> Result<(), BackendError> {
+        let mut files = self.files.lock().unwrap();
+        let from_path = folder.join(from);
+        match files.remove(&from_path) {
+            None => Err(not_found(&from_path)),
+            Some(bytes) => {
+                files.insert(folder.join(to), bytes);
+                Ok(())
+            }
+        }
+    }
+
+    fn write_new(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        let path = folder.join(file);
+        match self.files.lock().unwrap().entry(path) {
+            Entry::Occupied(entry) => Err(BackendError::AlreadyExists(entry.key().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(bytes.to_vec());
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Batch save source file, saves several settings files with all-or-nothing semantics
+#![warn(missing_docs)]
+
+use crate::backend::{get_backend, BackendError, SettingsBackend};
+use crate::{is_read_only, serialize_settings, track_settings_path, SaveSettingsError};
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+/// A settings file to save with `save_settings_batch()`, the settings are serialized when the item is created
+pub struct BatchItem {
+    crate_name: String,
+    file_name: String,
+    serialized_data: Result<String, SaveSettingsError>,
+}
+
+impl BatchItem {
+    /// Creates an item that saves `settings` to `USER_HOME/crate_name/file_name`
+    pub fn new<T>(crate_name: &str, file_name: &str, settings: &T) -> Self
+    where
+        T: Serialize,
+    {
+        Self {
+            crate_name: crate_name.to_string(),
+            file_name: file_name.to_string(),
+            serialized_data: serialize_settings(settings),
+        }
+    }
+
+    /// Returns the name of the folder the item is saved in
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Returns the name of the file the item is saved to
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+#[derive(Debug)]
+/// Error returned by `save_settings_batch()`, describing the first item that failed
+pub struct BatchSaveError {
+    /// The index of the item that failed
+    pub index: usize,
+    /// The folder of the item that failed
+    pub crate_name: String,
+    /// The file name of the item that failed
+    pub file_name: String,
+    /// Why the item failed
+    pub error: SaveSettingsError,
+}
+
+/// Returns the name of the temporary file an item is written to before being moved into place
+fn temp_file_name(file_name: &str) -> String {
+    format!(".{}.batch.tmp", file_name)
+}
+
+/// Saves every item, or none of them.
+///
+/// Every item is first serialized and written to a temporary file next to its destination, then, only once all of them
+/// succeeded, moved into place. If anything fails, the temporary files are removed, any files already moved into place are
+/// restored to their previous contents, and the error reports which item failed.
+/// If an existing destination file can not be read, so its contents could not be restored, nothing is moved into place.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::batch::{save_settings_batch, BatchItem};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Display{
+/// width: u32,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Audio{
+/// volume: u32,
+/// }
+///
+/// save_settings_batch(vec![
+///     BatchItem::new(env!("CARGO_CRATE_NAME"), "display.ser", &Display{ width: 1920 }),
+///     BatchItem::new(env!("CARGO_CRATE_NAME"), "audio.ser", &Audio{ volume: 80 }),
+/// ]).expect("Unable to apply settings");
+///
+/// // `5` can not be saved as a settings file, so neither file is changed
+/// let failed = save_settings_batch(vec![
+///     BatchItem::new(env!("CARGO_CRATE_NAME"), "display.ser", &Display{ width: 1280 }),
+///     BatchItem::new(env!("CARGO_CRATE_NAME"), "audio.ser", &5),
+/// ]).unwrap_err();
+/// assert_eq!(failed.index, 1);
+/// assert_eq!(load_settings_with_filename::<Display>(env!("CARGO_CRATE_NAME"), "display.ser").unwrap(), Display{ width: 1920 });
+/// ```
+pub fn save_settings_batch(
+    items: impl IntoIterator<Item = BatchItem>,
+) -> Result<(), BatchSaveError> {
+    let backend = get_backend();
+
+    let mut prepared: Vec<(String, String)> = vec![];
+    for (index, item) in items.into_iter().enumerate() {
+        let result = if is_read_only() {
+            Err(SaveSettingsError::ReadOnlyMode)
+        } else {
+            item.serialized_data
+        };
+        let result = result.and_then(|serialized_data| {
+            backend
+                .write(
+                    Path::new(&item.crate_name),
+                    &temp_file_name(&item.file_name),
+                    serialized_data.as_bytes(),
+                )
+                .map_err(SaveSettingsError::from)
+        });
+        match result {
+            Ok(_) => prepared.push((item.crate_name, item.file_name)),
+            Err(error) => {
+                remove_temp_files(backend.as_ref(), &prepared);
+                return Err(BatchSaveError {
+                    index,
+                    crate_name: item.crate_name,
+                    file_name: item.file_name,
+                    error,
+                });
+            }
+        }
+    }
+
+    // keep the previous contents so files already moved into place can be restored if a later move fails,
+    // `None` only for files that do not exist yet, and nothing is moved if an existing file can not be read
+    let mut previous: Vec<Option<Vec<u8>>> = Vec::with_capacity(prepared.len());
+    for (index, (crate_name, file_name)) in prepared.iter().enumerate() {
+        match backend.read(Path::new(crate_name), file_name) {
+            Ok(bytes) => previous.push(Some(bytes)),
+            Err(BackendError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+                previous.push(None)
+            }
+            Err(err) => {
+                remove_temp_files(backend.as_ref(), &prepared);
+                return Err(BatchSaveError {
+                    index,
+                    crate_name: crate_name.clone(),
+                    file_name: file_name.clone(),
+                    error: err.into(),
+                });
+            }
+        }
+    }
+
+    for (index, (crate_name, file_name)) in prepared.iter().enumerate() {
+        let folder = Path::new(crate_name);
+        if let Err(err) = backend.rename(folder, &temp_file_name(file_name), file_name) {
+            for ((crate_name, file_name), previous) in prepared[..index].iter().zip(&previous) {
+                let _ = match previous {
+                    Some(bytes) => backend.write(Path::new(crate_name), file_name, bytes),
+                    None => backend.delete(Path::new(crate_name), file_name),
+                };
+            }
+            remove_temp_files(backend.as_ref(), &prepared[index..]);
+            return Err(BatchSaveError {
+                index,
+                crate_name: crate_name.clone(),
+                file_name: file_name.clone(),
+                error: err.into(),
+            });
+        }
+    }
+
+    for (crate_name, file_name) in prepared {
+        if let Some(settings_path) = backend.resolve(Path::new(&crate_name)) {
+            track_settings_path(settings_path.join(file_name));
+        }
+    }
+    Ok(())
+}
+
+/// Removes the temporary files of the given items, ignoring errors
+fn remove_temp_files(backend: &(dyn SettingsBackend + Send + Sync), items: &[(String, String)]) {
+    for (crate_name, file_name) in items {
+        let _ = backend.delete(Path::new(crate_name), &temp_file_name(file_name));
+    }
+}
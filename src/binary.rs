@@ -0,0 +1,47 @@
+//! Binary settings format source file, uses `bincode` for compact and fast encoding
+#![warn(missing_docs)]
+
+use crate::format::{load_settings_with_format, save_settings_with_format, Format};
+use crate::{LoadSettingsError, SaveSettingsError};
+use serde::{Deserialize, Serialize};
+
+/// Saves a serializable settings object using `bincode` to `USER_HOME/crate_name/file_name`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct FrameState{
+/// positions: Vec<(f32, f32)>,
+/// frame: u64,
+/// }
+///
+/// let state = FrameState{ positions: vec![(1.0, 2.5), (-4.0, 0.25)], frame: 1024 };
+///
+/// save_settings_bin(env!("CARGO_CRATE_NAME"), "frame_state.bin", &state).expect("Unable to save binary settings");
+///
+/// let loaded_state = load_settings_bin::<FrameState>(env!("CARGO_CRATE_NAME"), "frame_state.bin").expect("Unable to load binary settings");
+///
+/// assert_eq!(state,loaded_state);
+/// ```
+pub fn save_settings_bin<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_with_format(crate_name, file_name, settings, Format::Bincode)
+}
+
+/// Loads a settings file saved with `save_settings_bin()` from `USER_HOME/crate_name/file_name`
+///
+/// For example usage, see `save_settings_bin()` documentation.
+pub fn load_settings_bin<T>(crate_name: &str, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_with_format(crate_name, file_name, Format::Bincode)
+}
@@ -0,0 +1,185 @@
+//! Settings diff source file, shows what saving new settings would change in a settings file
+#![warn(missing_docs)]
+
+use crate::{load_settings_raw, LoadSettingsError, SaveSettingsError, SettingsError};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::io;
+use toml::value::Table;
+use toml::Value;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The keys that differ between two settings documents, returned by `diff_settings()`.
+///
+/// Keys are named by their path, e.g. `window.width`, elements of arrays of tables by their index, e.g. `servers[0].host`,
+/// and every other array is compared as a single value.
+pub struct SettingsDiff {
+    added: Vec<(String, Value)>,
+    removed: Vec<(String, Value)>,
+    changed: Vec<(String, Value, Value)>,
+}
+
+impl SettingsDiff {
+    /// Compares two settings documents, listing the keys of `new` that are added, removed, or changed compared to `old`
+    pub fn between(old: &Value, new: &Value) -> Self {
+        let mut diff = Self::default();
+        diff.compare("", old, new);
+        diff
+    }
+
+    /// Returns true if the documents are the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns the path and value of each key that only exists in the new document
+    pub fn added(&self) -> &[(String, Value)] {
+        &self.added
+    }
+
+    /// Returns the path and value of each key that only exists in the old document
+    pub fn removed(&self) -> &[(String, Value)] {
+        &self.removed
+    }
+
+    /// Returns the path, old value, and new value of each key whose value changed
+    pub fn changed(&self) -> &[(String, Value, Value)] {
+        &self.changed
+    }
+
+    /// Returns one line per difference, e.g. for listing them in a "review changes before saving" dialog:
+    /// `+ path = value` for added keys, `- path = value` for removed keys, and `~ path: old -> new` for changed keys.
+    /// Returns no lines if the documents are the same.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::diff::SettingsDiff;
+    ///
+    /// let old: toml::Value = toml::from_str("volume = 50\ntheme = \"dark\"").unwrap();
+    /// let new: toml::Value = toml::from_str("volume = 80\ntheme = \"dark\"\nmuted = true").unwrap();
+    /// let diff = SettingsDiff::between(&old, &new);
+    /// assert_eq!(diff.to_lines(), vec!["+ muted = true", "~ volume: 50 -> 80"]);
+    /// ```
+    pub fn to_lines(&self) -> Vec<String> {
+        let added = self
+            .added
+            .iter()
+            .map(|(path, value)| format!("+ {} = {}", path, value));
+        let removed = self
+            .removed
+            .iter()
+            .map(|(path, value)| format!("- {} = {}", path, value));
+        let changed = self
+            .changed
+            .iter()
+            .map(|(path, old, new)| format!("~ {}: {} -> {}", path, old, new));
+        added.chain(removed).chain(changed).collect()
+    }
+
+    /// Records the differences between `old` and `new`, which are both found at `path`
+    fn compare(&mut self, path: &str, old: &Value, new: &Value) {
+        match (old, new) {
+            (Value::Table(old), Value::Table(new)) => {
+                for (key, old_value) in old {
+                    let key_path = join_key(path, key);
+                    match new.get(key) {
+                        None => self.removed.push((key_path, old_value.clone())),
+                        Some(new_value) => self.compare(&key_path, old_value, new_value),
+                    }
+                }
+                for (key, new_value) in new {
+                    if !old.contains_key(key) {
+                        self.added.push((join_key(path, key), new_value.clone()));
+                    }
+                }
+            }
+            (Value::Array(old), Value::Array(new))
+                if is_array_of_tables(old) && is_array_of_tables(new) =>
+            {
+                for (index, old_value) in old.iter().enumerate() {
+                    let element_path = format!("{}[{}]", path, index);
+                    match new.get(index) {
+                        None => self.removed.push((element_path, old_value.clone())),
+                        Some(new_value) => self.compare(&element_path, old_value, new_value),
+                    }
+                }
+                for (index, new_value) in new.iter().enumerate().skip(old.len()) {
+                    self.added
+                        .push((format!("{}[{}]", path, index), new_value.clone()));
+                }
+            }
+            (old, new) if old != new => {
+                self.changed
+                    .push((path.to_string(), old.clone(), new.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Display for SettingsDiff {
+    /// Lists one difference per line, `+` for added keys, `-` for removed keys, and `~` for changed keys
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for line in self.to_lines() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the settings file `USER_HOME/crate_name/file_name` with `new_value`, listing what saving `new_value` would change.
+/// A missing settings file is treated as empty, so every key of `new_value` is listed as added.
+/// Use `SettingsDiff::to_lines()` for a list of the changed key paths with their old and new values.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::diff::diff_settings;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// volume: u32,
+/// theme: String,
+/// }
+///
+/// save_settings!(Settings{ volume: 50, theme: "dark".to_string() }, "diff.ser").unwrap();
+///
+/// let diff = diff_settings(env!("CARGO_CRATE_NAME"), "diff.ser", &Settings{ volume: 80, theme: "dark".to_string() }).unwrap();
+/// assert!(!diff.is_empty());
+/// assert_eq!(diff.to_string(), "~ volume: 50 -> 80\n");
+/// assert_eq!(diff.to_lines(), vec!["~ volume: 50 -> 80"]);
+/// ```
+pub fn diff_settings<T>(
+    crate_name: &str,
+    file_name: &str,
+    new_value: &T,
+) -> Result<SettingsDiff, SettingsError>
+where
+    T: Serialize,
+{
+    let old = match load_settings_raw(crate_name, file_name) {
+        Ok(old) => old,
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+            Value::Table(Table::new())
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let new = Value::try_from(new_value).map_err(SaveSettingsError::SerializationError)?;
+    Ok(SettingsDiff::between(&old, &new))
+}
+
+/// Appends `key` to a key path
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Returns true if every element of the array is a table, empty arrays included
+fn is_array_of_tables(array: &[Value]) -> bool {
+    array.iter().all(Value::is_table)
+}
@@ -0,0 +1,136 @@
+//! Encrypted settings source file, uses XChaCha20-Poly1305 to encrypt the serialized TOML
+//!
+//! Encrypted files start with the `ENCRYPTED_MAGIC` bytes, followed by the random 24 byte nonce used for that save,
+//! followed by the encrypted settings. Every save uses a new nonce, so the same key can be reused indefinitely.
+#![warn(missing_docs)]
+
+use crate::{
+    deserialize_settings, read_settings_bytes, serialize_settings, write_settings_bytes,
+    LoadSettingsError, SaveSettingsError,
+};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+
+/// Bytes every encrypted settings file starts with, used to tell encrypted files apart from plaintext ones
+pub const ENCRYPTED_MAGIC: &[u8; 8] = b"CRPSENC1";
+
+/// Length of the XChaCha20-Poly1305 nonce stored after `ENCRYPTED_MAGIC`
+const NONCE_LENGTH: usize = 24;
+
+#[derive(Clone, PartialEq, Eq)]
+/// A 256 bit key used to encrypt and decrypt settings files
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    /// Creates a key from raw bytes, e.g. a key stored in the OS keyring
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a new random key using the operating systems random number generator
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Derives a key from a password using argon2 with its default parameters.
+    /// The salt must be at least 8 bytes, and the same password and salt always derive the same key,
+    /// so use a salt unique to the program or install, e.g. a random value saved alongside the settings.
+    pub fn from_password(password: &str, salt: &[u8]) -> Result<Self, argon2::Error> {
+        let mut key = [0u8; 32];
+        argon2::Argon2::default().hash_password_into(password.as_bytes(), salt, &mut key)?;
+        Ok(Self(key))
+    }
+
+    /// Returns the raw bytes of the key
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // never print the key itself
+        f.write_str("SecretKey(..)")
+    }
+}
+
+/// Returns true if the data starts with `ENCRYPTED_MAGIC`
+pub fn is_encrypted(file_data: &[u8]) -> bool {
+    file_data.starts_with(ENCRYPTED_MAGIC)
+}
+
+/// Encrypts and saves a serializable settings object to `USER_HOME/crate_name/file_name`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::encryption::SecretKey;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Credentials{
+/// api_token: String,
+/// }
+///
+/// let key = SecretKey::from_password("correct horse battery staple", b"doctest salt").unwrap();
+/// let credentials = Credentials{ api_token: "hunter2".to_string() };
+///
+/// save_settings_encrypted(env!("CARGO_CRATE_NAME"), "credentials.enc", &credentials, &key).expect("Unable to save encrypted settings");
+///
+/// let loaded = load_settings_encrypted::<Credentials>(env!("CARGO_CRATE_NAME"), "credentials.enc", &key).expect("Unable to load encrypted settings");
+/// assert_eq!(credentials, loaded);
+///
+/// // the wrong key is reported as a decryption failure
+/// let wrong_key = SecretKey::generate();
+/// assert!(matches!(load_settings_encrypted::<Credentials>(env!("CARGO_CRATE_NAME"), "credentials.enc", &wrong_key), Err(LoadSettingsError::DecryptionFailed)));
+/// ```
+pub fn save_settings_encrypted<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+    key: &SecretKey,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let serialized_data = serialize_settings(settings)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let encrypted_data = cipher
+        .encrypt(&nonce, serialized_data.as_bytes())
+        .map_err(|_| SaveSettingsError::EncryptionFailed)?;
+
+    let mut file_data =
+        Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LENGTH + encrypted_data.len());
+    file_data.extend_from_slice(ENCRYPTED_MAGIC);
+    file_data.extend_from_slice(&nonce);
+    file_data.extend_from_slice(&encrypted_data);
+    write_settings_bytes(Path::new(crate_name), file_name, &file_data)
+}
+
+/// Loads and decrypts a settings file saved with `save_settings_encrypted()` from `USER_HOME/crate_name/file_name`.
+/// Returns `NotEncrypted` if the file is not an encrypted settings file, and `DecryptionFailed` if the key is wrong
+/// or the file was tampered with.
+///
+/// For example usage, see `save_settings_encrypted()` documentation.
+pub fn load_settings_encrypted<T>(
+    crate_name: &str,
+    file_name: &str,
+    key: &SecretKey,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    if !is_encrypted(&file_data) || file_data.len() < ENCRYPTED_MAGIC.len() + NONCE_LENGTH {
+        return Err(LoadSettingsError::NotEncrypted);
+    }
+    let (nonce, encrypted_data) = file_data[ENCRYPTED_MAGIC.len()..].split_at(NONCE_LENGTH);
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    let decrypted_data = cipher
+        .decrypt(XNonce::from_slice(nonce), encrypted_data)
+        .map_err(|_| LoadSettingsError::DecryptionFailed)?;
+    deserialize_settings(settings_file_path, decrypted_data)
+}
@@ -0,0 +1,198 @@
+//! Environment variable override source file
+#![warn(missing_docs)]
+
+use crate::options::UnknownEnvVar;
+use crate::{deserialize_settings, read_settings_bytes, LoadSettingsError};
+use serde::Deserialize;
+use std::path::Path;
+use toml::value::Table;
+use toml::Value;
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, then overrides fields with environment variables
+/// starting with `prefix` before deserializing into `T`.
+///
+/// The rest of the variable name after the prefix is the field name, matched case-insensitively, with `__`
+/// separating nested tables, e.g. `MYAPP_UI__DARK_MODE=true` overrides `dark_mode` in the `ui` table.
+/// Values are parsed as TOML values when possible, e.g. `42`, `true`, or `[1, 2]`, and used as strings otherwise.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::env_overrides::load_settings_with_env_overrides;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Ui{
+/// dark_mode: bool,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// setting2: String,
+/// ui: Ui,
+/// }
+///
+/// let settings = Settings{ setting1: 1, setting2: "from file".to_string(), ui: Ui{ dark_mode: false } };
+/// save_settings!(settings, "env_overrides.ser").unwrap();
+///
+/// std::env::set_var("DOCTEST_APP_SETTING1", "42");
+/// std::env::set_var("DOCTEST_APP_UI__DARK_MODE", "true");
+///
+/// let loaded = load_settings_with_env_overrides::<Settings>(env!("CARGO_CRATE_NAME"), "env_overrides.ser", "DOCTEST_APP_").unwrap();
+/// assert_eq!(loaded, Settings{ setting1: 42, setting2: "from file".to_string(), ui: Ui{ dark_mode: true } });
+/// ```
+pub fn load_settings_with_env_overrides<T>(
+    crate_name: &str,
+    file_name: &str,
+    prefix: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let mut value = deserialize_settings::<Value>(settings_file_path, file_data)?;
+    apply_env_overrides(&mut value, prefix);
+    value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
+
+/// Applies every environment variable starting with `prefix` on top of `value`
+pub(crate) fn apply_env_overrides(value: &mut Value, prefix: &str) {
+    let mut overrides: Vec<(String, String)> = std::env::vars_os()
+        .filter_map(|(key, val)| Some((key.into_string().ok()?, val.into_string().ok()?)))
+        .filter_map(|(key, val)| Some((key.strip_prefix(prefix)?.to_string(), val)))
+        .filter(|(key, _)| !key.is_empty())
+        .collect();
+    // sort so overrides are applied in the same order every time
+    overrides.sort();
+
+    if !value.is_table() {
+        *value = Value::Table(Table::new());
+    }
+    for (key, raw_value) in overrides {
+        let keys: Vec<&str> = key.split("__").collect();
+        if let Value::Table(table) = value {
+            set_nested(table, &keys, parse_env_value(&raw_value));
+        }
+    }
+}
+
+/// Sets the value at the nested key path, creating or replacing tables along the way
+fn set_nested(table: &mut Table, keys: &[&str], new_value: Value) {
+    let (first, rest) = match keys.split_first() {
+        None => return,
+        Some(split) => split,
+    };
+    let key = matching_key(table, first);
+    if rest.is_empty() {
+        table.insert(key, new_value);
+        return;
+    }
+    let entry = table
+        .entry(key)
+        .or_insert_with(|| Value::Table(Table::new()));
+    if !entry.is_table() {
+        *entry = Value::Table(Table::new());
+    }
+    if let Value::Table(inner) = entry {
+        set_nested(inner, rest, new_value);
+    }
+}
+
+/// Returns the existing key that matches `key` case-insensitively, or `key` lowercased if there is none
+fn matching_key(table: &Table, key: &str) -> String {
+    table
+        .keys()
+        .find(|existing| existing.eq_ignore_ascii_case(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_lowercase())
+}
+
+/// Parses an environment variable as a TOML value, falling back to a string
+fn parse_env_value(raw_value: &str) -> Value {
+    match toml::from_str::<Table>(&format!("value = {}", raw_value)) {
+        Ok(mut table) => table
+            .remove("value")
+            .unwrap_or_else(|| Value::String(raw_value.to_string())),
+        Err(_) => Value::String(raw_value.to_string()),
+    }
+}
+
+/// Expands references to environment variables in every string value of `value`, including those in nested tables and arrays.
+/// Keys are never expanded. See `expand_env_str()` for the syntax
+pub(crate) fn expand_env_vars(value: &mut Value, unknown: UnknownEnvVar) -> Result<(), String> {
+    match value {
+        Value::String(text) => *text = expand_env_str(text, unknown)?,
+        Value::Array(items) => {
+            for item in items {
+                expand_env_vars(item, unknown)?;
+            }
+        }
+        Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                expand_env_vars(item, unknown)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces `${VAR}` and `$VAR`, and `%VAR%` on Windows, with the value of the environment variable `VAR`.
+/// `$$` is a literal `$`, and `%%` a literal `%` on Windows. A `$` or `%` that does not start a reference is kept as written.
+/// A variable that is not set is kept as written, or its name is returned as the error if `unknown` is `UnknownEnvVar::Error`
+pub(crate) fn expand_env_str(text: &str, unknown: UnknownEnvVar) -> Result<String, String> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(|c| c == '$' || (cfg!(windows) && c == '%')) {
+        expanded.push_str(&rest[..index]);
+        let marker = &rest[index..index + 1];
+        let after = &rest[index + 1..];
+        // the name of the variable and the length of the whole reference
+        let reference = if after.starts_with(marker) {
+            expanded.push_str(marker);
+            rest = &after[1..];
+            continue;
+        } else if marker == "%" {
+            after
+                .find('%')
+                .map(|end| (&after[..end], end + 2))
+                .filter(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+        } else if let Some(braced) = after.strip_prefix('{') {
+            braced
+                .find('}')
+                .map(|end| (&braced[..end], end + 3))
+                .filter(|(name, _)| is_env_name(name))
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            Some((&after[..end], end + 1)).filter(|(name, _)| is_env_name(name))
+        };
+        let (name, reference_len) = match reference {
+            Some(reference) => reference,
+            None => {
+                expanded.push_str(marker);
+                rest = after;
+                continue;
+            }
+        };
+        match std::env::var_os(name) {
+            Some(env_value) => expanded.push_str(&env_value.to_string_lossy()),
+            None if unknown == UnknownEnvVar::Error => return Err(name.to_string()),
+            None => expanded.push_str(&rest[index..index + reference_len]),
+        }
+        rest = &rest[index + reference_len..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Returns true if `name` is a valid environment variable name for a `$` reference: a letter or `_`,
+/// followed by letters, digits, and `_`
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
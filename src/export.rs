@@ -0,0 +1,128 @@
+//! Settings export source file, copies settings files to and from paths outside the settings folder, e.g. for backups
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::format::Format;
+use crate::{
+    is_read_only, read_only_error, track_settings_path, write_settings_bytes, LoadSettingsError,
+    SettingsError,
+};
+use serde::Deserialize;
+use std::path::Path;
+use std::{fs, io};
+
+/// Copies the settings file `USER_HOME/crate_name/file_name` to `dest`, e.g. a path the user picked in a file dialog.
+/// Missing parent folders of `dest` are created and an existing file at `dest` is replaced. The file is copied as it is,
+/// so it works for any format and keeps comments.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::export::{export_settings, import_settings};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 21 }, "exported.ser").unwrap();
+///
+/// let backup = std::env::temp_dir().join("cr_program_settings_doctest_backup").join("settings.toml");
+/// export_settings(env!("CARGO_CRATE_NAME"), "exported.ser", &backup).expect("Unable to export settings");
+///
+/// save_settings!(Settings{ setting1: 0 }, "exported.ser").unwrap();
+/// import_settings(&backup, env!("CARGO_CRATE_NAME"), "exported.ser").expect("Unable to import settings");
+/// assert_eq!(load_settings!(Settings, "exported.ser").unwrap(), Settings{ setting1: 21 });
+///
+/// std::fs::remove_dir_all(backup.parent().unwrap()).unwrap();
+/// ```
+pub fn export_settings(crate_name: &str, file_name: &str, dest: &Path) -> io::Result<()> {
+    let file_data = get_backend().read(Path::new(crate_name), file_name)?;
+    if let Some(parent) = dest
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(dest, file_data)?;
+    log_info!(
+        "exported settings {}/{} to {}",
+        crate_name,
+        file_name,
+        dest.display()
+    );
+    Ok(())
+}
+
+/// Copies the file at `src` into `USER_HOME/crate_name/file_name`, replacing the current settings file.
+/// The file is not checked, see `import_settings_validated()` to only import files that load as the settings type.
+///
+/// For example usage, see `export_settings()` documentation.
+pub fn import_settings(src: &Path, crate_name: &str, file_name: &str) -> io::Result<()> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let file_data = fs::read(src)?;
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    if let Err(err) = backend.write(folder, file_name, &file_data) {
+        log_warn!(
+            "failed to import {} into settings {}/{}: {:?}",
+            src.display(),
+            crate_name,
+            file_name,
+            err
+        );
+        return Err(err.into());
+    }
+    log_info!(
+        "imported {} into settings {}/{}",
+        src.display(),
+        crate_name,
+        file_name
+    );
+    if let Some(settings_path) = backend.resolve(folder) {
+        track_settings_path(settings_path.join(file_name));
+    }
+    Ok(())
+}
+
+/// Copies the file at `src` into `USER_HOME/crate_name/file_name` like `import_settings()`, but only if it loads as `T`
+/// in the format picked from `file_name` by `Format::from_file_name()`, so a broken file never replaces working settings.
+/// Returns the imported settings.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::export::import_settings_validated;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 5 }, "validated_import.ser").unwrap();
+///
+/// let not_settings = std::env::temp_dir().join("cr_program_settings_doctest_not_settings.toml");
+/// std::fs::write(&not_settings, "something_else = true\n").unwrap();
+///
+/// assert!(import_settings_validated::<Settings>(&not_settings, env!("CARGO_CRATE_NAME"), "validated_import.ser").is_err());
+/// assert_eq!(load_settings!(Settings, "validated_import.ser").unwrap(), Settings{ setting1: 5 });
+///
+/// std::fs::remove_file(not_settings).unwrap();
+/// ```
+pub fn import_settings_validated<T>(
+    src: &Path,
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, SettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let file_data = fs::read(src)?;
+    let format = Format::from_file_name(file_name)
+        .ok_or_else(|| LoadSettingsError::FormatNotEnabled(file_name.to_string()))?;
+    let settings = format.deserialize::<T>(&file_data)?;
+    write_settings_bytes(Path::new(crate_name), file_name, &file_data)?;
+    Ok(settings)
+}
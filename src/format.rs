@@ -0,0 +1,406 @@
+//! Settings file format source file
+#![warn(missing_docs)]
+
+use crate::options::{
+    load_settings_with_options, save_settings_with_options, LoadOptions, SaveOptions,
+};
+use crate::{
+    decode_settings_text, delete_setting_file, load_settings_borrowed, open_settings_reader,
+    read_settings_bytes, serialize_settings, track_settings_path, write_settings_bytes,
+    LoadSettingsError, ParseError, SaveSettingsError, SettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The serialization formats settings can be saved in, formats other than TOML are behind features
+pub enum Format {
+    /// Pretty printed TOML, the format used by the rest of the library
+    Toml,
+    #[cfg(feature = "bincode")]
+    /// Compact binary encoding using `bincode`
+    Bincode,
+    #[cfg(feature = "json")]
+    /// Pretty printed JSON using `serde_json`
+    Json,
+    #[cfg(feature = "yaml")]
+    /// YAML using `serde_yaml`, anchors, aliases, and `<<` merge keys are resolved when loading
+    Yaml,
+}
+
+impl Format {
+    /// Returns the file extension used for this format, without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Toml => "toml",
+            #[cfg(feature = "bincode")]
+            Format::Bincode => "bin",
+            #[cfg(feature = "json")]
+            Format::Json => "json",
+            #[cfg(feature = "yaml")]
+            Format::Yaml => "yaml",
+        }
+    }
+
+    /// Picks the format from the extension of `file_name`: `json` is JSON, `bin` is bincode, `yaml` or `yml` is YAML,
+    /// and `toml`, `ser`, or any other extension is TOML. Returns `None` if the extension names a format that is not compiled in,
+    /// e.g. `json` without the `json` feature, or a format the library does not support, e.g. `ron`.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::format::Format;
+    ///
+    /// assert_eq!(Format::from_file_name("settings.ser"), Some(Format::Toml));
+    /// assert_eq!(Format::from_file_name("settings"), Some(Format::Toml));
+    /// assert_eq!(Format::from_file_name("settings.ron"), None);
+    /// ```
+    pub fn from_file_name(file_name: &str) -> Option<Format> {
+        let extension = Path::new(file_name)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+        match extension.as_deref() {
+            #[cfg(feature = "json")]
+            Some("json") => Some(Format::Json),
+            #[cfg(not(feature = "json"))]
+            Some("json") => None,
+            #[cfg(feature = "bincode")]
+            Some("bin") => Some(Format::Bincode),
+            #[cfg(not(feature = "bincode"))]
+            Some("bin") => None,
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => Some(Format::Yaml),
+            #[cfg(not(feature = "yaml"))]
+            Some("yaml" | "yml") => None,
+            Some("ron") => None,
+            _ => Some(Format::Toml),
+        }
+    }
+
+    /// Serializes settings into the bytes of a file in this format
+    pub fn serialize<T>(&self, settings: &T) -> Result<Vec<u8>, SaveSettingsError>
+    where
+        T: Serialize,
+    {
+        match self {
+            Format::Toml => serialize_settings(settings).map(String::into_bytes),
+            #[cfg(feature = "bincode")]
+            Format::Bincode => {
+                bincode::serialize(settings).map_err(SaveSettingsError::BincodeEncodeError)
+            }
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::to_vec_pretty(settings)
+                .map_err(SaveSettingsError::JsonSerializationError),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::to_string(settings)
+                .map(String::into_bytes)
+                .map_err(SaveSettingsError::YamlSerializationError),
+        }
+    }
+
+    /// Deserializes settings from the bytes of a file in this format
+    pub fn deserialize<T>(&self, file_data: &[u8]) -> Result<T, LoadSettingsError>
+    where
+        for<'a> T: Deserialize<'a>,
+    {
+        self.deserialize_borrowed(file_data)
+    }
+
+    /// Deserializes settings read from `reader`. JSON and bincode parse while reading, so large files are never held
+    /// in memory as a whole, TOML and YAML need the whole document and read it to the end first.
+    pub fn deserialize_from<T>(&self, mut reader: impl Read) -> Result<T, LoadSettingsError>
+    where
+        for<'a> T: Deserialize<'a>,
+    {
+        match self {
+            Format::Toml => {
+                let mut file_data = vec![];
+                reader
+                    .read_to_end(&mut file_data)
+                    .map_err(LoadSettingsError::IOError)?;
+                self.deserialize(&file_data)
+            }
+            #[cfg(feature = "bincode")]
+            Format::Bincode => bincode::deserialize_from::<_, T>(reader)
+                .map_err(LoadSettingsError::BincodeDecodeError),
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::from_reader::<_, T>(reader)
+                .map_err(LoadSettingsError::JsonDeserializationError),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::from_reader::<_, serde_yaml::Value>(reader)
+                .and_then(from_yaml_value)
+                .map_err(LoadSettingsError::YamlDeserializationError),
+        }
+    }
+
+    /// Deserializes settings that may borrow from `file_data`, which the caller keeps alive.
+    /// JSON and bincode hand out slices of the buffer, so `&'a str` fields are loaded without copying,
+    /// while TOML and YAML always copy strings, see `load_settings_borrowed()`.
+    /// TOML that is not valid UTF-8 returns `LoadSettingsError::InvalidEncoding` with an empty path.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use serde::Deserialize;
+    /// use cr_program_settings::format::Format;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Settings<'a>{
+    /// #[serde(borrow)]
+    /// motd: std::borrow::Cow<'a, str>,
+    /// }
+    ///
+    /// let file_data = b"motd = \"hello\"".to_vec();
+    /// let settings = Format::Toml.deserialize_borrowed::<Settings>(&file_data).unwrap();
+    /// assert_eq!(settings.motd, "hello");
+    /// ```
+    pub fn deserialize_borrowed<'a, T>(&self, file_data: &'a [u8]) -> Result<T, LoadSettingsError>
+    where
+        T: Deserialize<'a>,
+    {
+        match self {
+            // the bytes do not come from a known file, so an encoding error has an empty path
+            Format::Toml => {
+                decode_settings_text(Path::new(""), file_data).and_then(load_settings_borrowed)
+            }
+            #[cfg(feature = "bincode")]
+            Format::Bincode => {
+                bincode::deserialize::<T>(file_data).map_err(LoadSettingsError::BincodeDecodeError)
+            }
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::from_slice::<T>(file_data)
+                .map_err(LoadSettingsError::JsonDeserializationError),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::from_slice::<serde_yaml::Value>(file_data)
+                .and_then(from_yaml_value)
+                .map_err(LoadSettingsError::YamlDeserializationError),
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+/// Deserializes settings from a parsed YAML document, after resolving its `<<` merge keys,
+/// which `serde_yaml` leaves as regular keys when deserializing straight into a struct
+fn from_yaml_value<'a, T>(mut value: serde_yaml::Value) -> Result<T, serde_yaml::Error>
+where
+    T: Deserialize<'a>,
+{
+    value.apply_merge()?;
+    T::deserialize(value)
+}
+
+/// Saves a serializable settings object in the given format to `USER_HOME/crate_name/file_name`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::format::{load_settings_with_format, save_settings_with_format, Format};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let settings = Settings{ setting1: 8 };
+/// save_settings_with_format(env!("CARGO_CRATE_NAME"), "formatted.toml", &settings, Format::Toml).unwrap();
+///
+/// let loaded_settings = load_settings_with_format::<Settings>(env!("CARGO_CRATE_NAME"), "formatted.toml", Format::Toml).unwrap();
+/// assert_eq!(settings,loaded_settings);
+/// ```
+pub fn save_settings_with_format<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+    format: Format,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    if format == Format::Toml {
+        return save_settings_with_options(
+            crate_name,
+            file_name,
+            settings,
+            &SaveOptions::default(),
+        );
+    }
+    let serialized_data = format.serialize(settings)?;
+    write_settings_bytes(Path::new(crate_name), file_name, &serialized_data)
+}
+
+/// Loads a settings file saved in the given format from `USER_HOME/crate_name/file_name`
+///
+/// For example usage, see `save_settings_with_format()` documentation.
+pub fn load_settings_with_format<T>(
+    crate_name: &str,
+    file_name: &str,
+    format: Format,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    // the backend reads TOML files into a buffer sized to the file, which is all the parser needs,
+    // other formats are parsed while streaming the file
+    if format == Format::Toml {
+        return load_settings_with_options(crate_name, file_name, &LoadOptions::default());
+    }
+    let (settings_file_path, reader) = open_settings_reader(Path::new(crate_name), file_name)?;
+    let settings = format.deserialize_from::<T>(reader)?;
+    track_settings_path(settings_file_path);
+    Ok(settings)
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/file_name`, in the format picked by `Format::from_file_name()`.
+/// Returns `SaveSettingsError::FormatNotEnabled` if the format of the extension is not compiled in.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::format::{load_settings_auto, save_settings_auto};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let settings = Settings{ setting1: 8 };
+/// save_settings_auto(env!("CARGO_CRATE_NAME"), "auto.toml", &settings).unwrap();
+/// assert_eq!(load_settings_auto::<Settings>(env!("CARGO_CRATE_NAME"), "auto.toml").unwrap(), settings);
+///
+/// #[cfg(feature = "json")]
+/// {
+///     save_settings_auto(env!("CARGO_CRATE_NAME"), "auto.json", &settings).unwrap();
+///     assert_eq!(load_settings_auto::<Settings>(env!("CARGO_CRATE_NAME"), "auto.json").unwrap(), settings);
+/// }
+/// ```
+pub fn save_settings_auto<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    match Format::from_file_name(file_name) {
+        None => Err(SaveSettingsError::FormatNotEnabled(file_name.to_string())),
+        Some(format) => save_settings_with_format(crate_name, file_name, settings, format),
+    }
+}
+
+/// Loads a settings file from `USER_HOME/crate_name/file_name`, in the format picked by `Format::from_file_name()`.
+/// Returns `LoadSettingsError::FormatNotEnabled` if the format of the extension is not compiled in.
+///
+/// For example usage, see `save_settings_auto()` documentation.
+pub fn load_settings_auto<T>(crate_name: &str, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match Format::from_file_name(file_name) {
+        None => Err(LoadSettingsError::FormatNotEnabled(file_name.to_string())),
+        Some(format) => load_settings_with_format(crate_name, file_name, format),
+    }
+}
+
+/// Loads a settings file from `USER_HOME/crate_name/file_name` whose format is not known, e.g. one written by another config library.
+/// The format picked by `Format::from_file_name()` is tried first, then TOML, JSON, and YAML, whichever are compiled in.
+/// Bincode is only tried when the extension names it, since almost any bytes decode as some bincode value.
+/// Returns the settings along with the format that parsed them, so the file can be saved again in the canonical format.
+/// If no format parses the file, the error of the first format tried is returned, as a `ParseError` for TOML.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::format::{load_settings_any, save_settings_with_format, Format};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let settings = Settings{ setting1: 8 };
+/// #[cfg(feature = "json")]
+/// {
+///     // a legacy JSON file saved under the name the program now uses for TOML
+///     save_settings_with_format(env!("CARGO_CRATE_NAME"), "legacy.ser", &settings, Format::Json).unwrap();
+///     let (loaded, format) = load_settings_any::<Settings>(env!("CARGO_CRATE_NAME"), "legacy.ser").unwrap();
+///     assert_eq!(loaded, settings);
+///     assert_eq!(format, Format::Json);
+///
+///     // saving it again migrates the file to TOML
+///     save_settings_with_filename(env!("CARGO_CRATE_NAME"), "legacy.ser", &loaded).unwrap();
+///     assert_eq!(load_settings_any::<Settings>(env!("CARGO_CRATE_NAME"), "legacy.ser").unwrap().1, Format::Toml);
+/// }
+/// ```
+pub fn load_settings_any<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<(T, Format), LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let mut formats = vec![];
+    if let Some(format) = Format::from_file_name(file_name) {
+        formats.push(format);
+    }
+    for format in [
+        Format::Toml,
+        #[cfg(feature = "json")]
+        Format::Json,
+        #[cfg(feature = "yaml")]
+        Format::Yaml,
+    ] {
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    let mut first_error = None;
+    for format in formats {
+        match format.deserialize::<T>(&file_data) {
+            Ok(settings) => {
+                track_settings_path(settings_file_path);
+                return Ok((settings, format));
+            }
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+    // TOML is always tried, so there is at least one error
+    match first_error.unwrap() {
+        LoadSettingsError::DeserializationError(err) => {
+            let text = decode_settings_text(&settings_file_path, &file_data)?;
+            Err(LoadSettingsError::ParseError(Box::new(ParseError::new(
+                settings_file_path,
+                text,
+                err,
+            ))))
+        }
+        err => Err(err),
+    }
+}
+
+/// Converts the settings file `USER_HOME/crate_name/file_name` from one format to another.
+/// The converted file is saved next to the original, with its extension replaced by the extension of `to`,
+/// and the original is deleted if `delete_original` is true and the names differ.
+/// Returns the file name of the converted file.
+///
+/// The settings are loaded as `T`, so any fields not present in `T` are not carried over.
+pub fn convert_settings<T>(
+    crate_name: &str,
+    file_name: &str,
+    from: Format,
+    to: Format,
+    delete_original: bool,
+) -> Result<String, SettingsError>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    let settings = load_settings_with_format::<T>(crate_name, file_name, from)?;
+    let converted_file_name = Path::new(file_name)
+        .with_extension(to.extension())
+        .to_string_lossy()
+        .to_string();
+    save_settings_with_format(crate_name, &converted_file_name, &settings, to)?;
+    if delete_original && converted_file_name != file_name {
+        delete_setting_file(crate_name, file_name)?;
+    }
+    Ok(converted_file_name)
+}
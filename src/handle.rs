@@ -0,0 +1,147 @@
+//! Settings handle source file, a shared snapshot of settings that is cheap to read on hot paths
+#![warn(missing_docs)]
+
+use crate::{
+    load_settings_with_filename, save_settings_with_filename, LoadSettingsError, SaveSettingsError,
+};
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+/// Settings loaded once from `USER_HOME/crate_name/file_name` and kept in memory, for code that reads settings in a
+/// tight loop, e.g. once per frame. `get()` returns a snapshot of the settings without locking or touching the disk,
+/// so readers on any number of threads always see a complete, consistent version of the settings.
+///
+/// `store()` and `modify()` write the new settings to the file first, and only replace the snapshot once the save
+/// succeeded, so the snapshot always matches what is on disk. `reload()` picks up edits made outside the program.
+/// Snapshots taken before an update keep the old settings until they are dropped.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::handle::SettingsHandle;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Clone)]
+/// struct Settings{
+/// volume: u32,
+/// }
+///
+/// let handle = SettingsHandle::new(Settings{ volume: 0 }, env!("CARGO_CRATE_NAME"), "handle.ser");
+/// handle.store(Settings{ volume: 10 }).expect("Unable to save settings");
+///
+/// // the hot path only clones an Arc
+/// let snapshot = handle.get();
+/// assert_eq!(snapshot.volume, 10);
+///
+/// handle.modify(|settings| settings.volume = 20).expect("Unable to save settings");
+/// assert_eq!(handle.get().volume, 20);
+/// assert_eq!(snapshot.volume, 10);
+/// assert_eq!(load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "handle.ser").unwrap(), Settings{ volume: 20 });
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "handle.ser").unwrap();
+/// ```
+pub struct SettingsHandle<T> {
+    /// The current snapshot of the settings
+    current: ArcSwap<T>,
+    /// The name of the parent folder of the settings file
+    crate_name: String,
+    /// The name of the settings file
+    file_name: String,
+    /// Held while writing or reloading, so the snapshot is replaced in the same order the file is written
+    write_lock: Mutex<()>,
+}
+
+impl<T> SettingsHandle<T> {
+    /// Creates a handle holding `settings`, without reading or writing the settings file
+    pub fn new(settings: T, crate_name: impl AsRef<str>, file_name: impl AsRef<str>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(settings),
+            crate_name: crate_name.as_ref().to_string(),
+            file_name: file_name.as_ref().to_string(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns a snapshot of the current settings, without locking or reading the settings file
+    pub fn get(&self) -> Arc<T> {
+        self.current.load_full()
+    }
+
+    /// Returns the name of the parent folder of the settings file
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Returns the name of the settings file
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+impl<T> SettingsHandle<T>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    /// Loads the settings from `USER_HOME/crate_name/file_name` into a new handle
+    pub fn load(
+        crate_name: impl AsRef<str>,
+        file_name: impl AsRef<str>,
+    ) -> Result<Self, LoadSettingsError> {
+        let settings = load_settings_with_filename(crate_name.as_ref(), file_name.as_ref())?;
+        Ok(Self::new(settings, crate_name, file_name))
+    }
+
+    /// Loads the settings into a new handle, or uses `T::default()` if the file is missing or fails to load
+    pub fn load_or_default(crate_name: impl AsRef<str>, file_name: impl AsRef<str>) -> Self
+    where
+        T: Default,
+    {
+        let settings = load_settings_with_filename(crate_name.as_ref(), file_name.as_ref())
+            .unwrap_or_default();
+        Self::new(settings, crate_name, file_name)
+    }
+
+    /// Loads the settings file again and replaces the snapshot with it, e.g. after the file was edited outside the program.
+    /// The snapshot is left unchanged if the file fails to load
+    pub fn reload(&self) -> Result<(), LoadSettingsError> {
+        let _write_guard = self.write_lock.lock().unwrap();
+        let settings = load_settings_with_filename(&self.crate_name, &self.file_name)?;
+        self.current.store(Arc::new(settings));
+        Ok(())
+    }
+
+    /// Saves `settings` to the settings file, then replaces the snapshot with them.
+    /// The snapshot is left unchanged if the save fails
+    pub fn store(&self, settings: T) -> Result<(), SaveSettingsError> {
+        let _write_guard = self.write_lock.lock().unwrap();
+        save_settings_with_filename(&self.crate_name, &self.file_name, &settings)?;
+        self.current.store(Arc::new(settings));
+        Ok(())
+    }
+
+    /// Applies `modification` to a copy of the current settings, then saves and stores the copy like `store()`.
+    /// Modifications from several threads are applied one after the other, so none of them is lost
+    pub fn modify<F>(&self, modification: F) -> Result<(), SaveSettingsError>
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        let _write_guard = self.write_lock.lock().unwrap();
+        let mut settings = T::clone(&self.current.load());
+        modification(&mut settings);
+        save_settings_with_filename(&self.crate_name, &self.file_name, &settings)?;
+        self.current.store(Arc::new(settings));
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for SettingsHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsHandle")
+            .field("settings", &self.current.load())
+            .field("crate_name", &self.crate_name)
+            .field("file_name", &self.file_name)
+            .finish()
+    }
+}
@@ -0,0 +1,119 @@
+//! Settings includes source file, lets a settings file pull in other settings files from the same folder
+//!
+//! A settings file loaded with `LoadOptions::includes()` can list other files in a top-level `include` array,
+//! e.g. `include = ["keybindings.toml", "theme.toml"]`. Each included file is merged over the one before it,
+//! and the including file is merged over all of them, so its own values always win.
+#![warn(missing_docs)]
+
+use crate::layered::merge_values;
+use crate::{deserialize_settings, read_settings_bytes_limited, LoadSettingsError};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use toml::value::Table;
+use toml::Value;
+
+/// The top-level key listing the files a settings file includes
+pub const INCLUDE_KEY: &str = "include";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// What loading with includes does with an included file that does not exist, see `LoadOptions::missing_include()`
+pub enum MissingInclude {
+    #[default]
+    /// The load fails with the `NotFound` io error of the included file
+    Error,
+    /// The included file is skipped, as if it was not listed
+    Skip,
+}
+
+/// Replaces the `include` key of `table`, read from `settings_file_path` in `folder`, with the merged contents of the files it lists,
+/// merging `table` itself over them
+pub(crate) fn resolve_includes(
+    folder: &Path,
+    settings_file_path: &Path,
+    mut table: Table,
+    missing: MissingInclude,
+    max_file_size: u64,
+) -> Result<Value, LoadSettingsError> {
+    let mut chain = vec![settings_file_path.to_path_buf()];
+    let includes = take_includes(&mut table)?;
+    let mut merged = merge_includes(folder, includes, missing, max_file_size, &mut chain)?;
+    merge_values(&mut merged, Value::Table(table));
+    Ok(merged)
+}
+
+/// Loads and merges each included file in order, along with the files they include.
+/// `chain` holds the files currently being included, so a file including one of them is reported as a cycle
+fn merge_includes(
+    folder: &Path,
+    includes: Vec<String>,
+    missing: MissingInclude,
+    max_file_size: u64,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Value, LoadSettingsError> {
+    let mut merged = Value::Table(Table::new());
+    for file_name in includes {
+        let (include_path, file_data) =
+            match read_settings_bytes_limited(folder, &file_name, max_file_size) {
+                Ok(read) => read,
+                Err(LoadSettingsError::IOError(err))
+                    if err.kind() == io::ErrorKind::NotFound && missing == MissingInclude::Skip =>
+                {
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+        if chain.contains(&include_path) {
+            let mut cycle = chain.clone();
+            cycle.push(include_path);
+            return Err(LoadSettingsError::IncludeCycle(cycle));
+        }
+        let mut included = deserialize_settings::<Table>(include_path.clone(), file_data)?;
+        let nested_includes = take_includes(&mut included)?;
+        chain.push(include_path);
+        let mut included_merged =
+            merge_includes(folder, nested_includes, missing, max_file_size, chain)?;
+        chain.pop();
+        merge_values(&mut included_merged, Value::Table(included));
+        merge_values(&mut merged, included_merged);
+    }
+    Ok(merged)
+}
+
+/// Removes the `include` key from `table`, returning the file names it lists
+fn take_includes(table: &mut Table) -> Result<Vec<String>, LoadSettingsError> {
+    let includes = match table.remove(INCLUDE_KEY) {
+        None => return Ok(vec![]),
+        Some(Value::Array(includes)) => includes,
+        Some(other) => {
+            return Err(LoadSettingsError::InvalidInclude(format!(
+                "`{}` must be an array of file names, found a {}",
+                INCLUDE_KEY,
+                other.type_str()
+            )))
+        }
+    };
+    includes
+        .into_iter()
+        .map(|include| match include {
+            Value::String(file_name) if is_plain_file_name(&file_name) => Ok(file_name),
+            Value::String(file_name) => Err(LoadSettingsError::InvalidInclude(format!(
+                "`{}` is not a file name in the settings folder",
+                file_name
+            ))),
+            other => Err(LoadSettingsError::InvalidInclude(format!(
+                "`{}` must only contain strings, found a {}",
+                INCLUDE_KEY,
+                other.type_str()
+            ))),
+        })
+        .collect()
+}
+
+/// Returns true if `file_name` names a file directly inside the settings folder, e.g. not `../other.toml`
+fn is_plain_file_name(file_name: &str) -> bool {
+    let mut components = Path::new(file_name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
@@ -0,0 +1,67 @@
+//! Tracing instrumentation source file, emits a `tracing` event for every save, load, and delete with the `tracing` feature
+//!
+//! Without the feature every function here is a no-op, and no time is measured.
+
+use std::fmt::Debug;
+use std::path::Path;
+
+/// A settings operation being timed, finished with `succeeded()` or `failed()`
+pub(crate) struct SettingsOperation {
+    /// What is being done, e.g. `save`
+    #[cfg(feature = "tracing")]
+    operation: &'static str,
+    /// When the operation started
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl SettingsOperation {
+    /// Starts timing an operation
+    #[cfg(feature = "tracing")]
+    pub(crate) fn start(operation: &'static str) -> Self {
+        Self {
+            operation,
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Starts timing an operation
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn start(_operation: &'static str) -> Self {
+        Self {}
+    }
+
+    /// Emits a debug event for an operation on `path` that succeeded, `size` is the number of bytes written or read
+    #[cfg(feature = "tracing")]
+    pub(crate) fn succeeded(self, path: &Path, size: Option<usize>) {
+        tracing::debug!(
+            operation = self.operation,
+            path = %path.display(),
+            size,
+            elapsed_us = self.start.elapsed().as_micros() as u64,
+            "settings {} succeeded",
+            self.operation
+        );
+    }
+
+    /// Emits a debug event for an operation on `path` that succeeded, `size` is the number of bytes written or read
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn succeeded(self, _path: &Path, _size: Option<usize>) {}
+
+    /// Emits a warn event for an operation on `path` that failed
+    #[cfg(feature = "tracing")]
+    pub(crate) fn failed(self, path: &Path, error: &dyn Debug) {
+        tracing::warn!(
+            operation = self.operation,
+            path = %path.display(),
+            elapsed_us = self.start.elapsed().as_micros() as u64,
+            error = ?error,
+            "settings {} failed",
+            self.operation
+        );
+    }
+
+    /// Emits a warn event for an operation on `path` that failed
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn failed(self, _path: &Path, _error: &dyn Debug) {}
+}
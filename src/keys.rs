@@ -0,0 +1,173 @@
+//! Settings key source file, reads and writes single keys of a settings file by their dotted path, e.g. `ui.dark_mode`
+#![warn(missing_docs)]
+
+use crate::{
+    load_settings_raw, save_settings_raw, LoadSettingsError, SaveSettingsError, SettingsError,
+};
+use std::io;
+use toml::value::Table;
+use toml::Value;
+
+/// Returns the value at the dotted `key_path` of the settings file `USER_HOME/crate_name/file_name`, without deserializing
+/// the file into a settings struct. Array elements are selected with their index, e.g. `servers.0.host`.
+///
+/// Returns `Ok(None)` if the key, or an array index, does not exist, and `InvalidKeyPath` if the path is empty,
+/// or passes through a value that is not a table or array, e.g. `ui.dark_mode.x` where `dark_mode` is a boolean.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::keys::{get_settings_value, set_settings_value};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Ui{
+/// dark_mode: bool,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// ui: Ui,
+/// }
+///
+/// save_settings!(Settings{ ui: Ui{ dark_mode: false } }, "keys.ser").unwrap();
+///
+/// // e.g. from a helper binary that does not know the `Settings` struct
+/// set_settings_value(env!("CARGO_CRATE_NAME"), "keys.ser", "ui.dark_mode", toml::Value::Boolean(true)).unwrap();
+/// assert_eq!(get_settings_value(env!("CARGO_CRATE_NAME"), "keys.ser", "ui.dark_mode").unwrap(), Some(toml::Value::Boolean(true)));
+///
+/// assert_eq!(load_settings!(Settings, "keys.ser").unwrap(), Settings{ ui: Ui{ dark_mode: true } });
+/// ```
+pub fn get_settings_value(
+    crate_name: &str,
+    file_name: &str,
+    key_path: &str,
+) -> Result<Option<Value>, LoadSettingsError> {
+    let keys = split_key_path(key_path).map_err(LoadSettingsError::InvalidKeyPath)?;
+    let mut value = &load_settings_raw(crate_name, file_name)?;
+    for (depth, key) in keys.iter().enumerate() {
+        let next = match value {
+            Value::Table(table) => table.get(*key),
+            Value::Array(array) => match key.parse::<usize>() {
+                Ok(index) => array.get(index),
+                Err(_) => {
+                    return Err(LoadSettingsError::InvalidKeyPath(mismatch(
+                        &keys, depth, value,
+                    )))
+                }
+            },
+            _ => {
+                return Err(LoadSettingsError::InvalidKeyPath(mismatch(
+                    &keys, depth, value,
+                )))
+            }
+        };
+        match next {
+            None => return Ok(None),
+            Some(next) => value = next,
+        }
+    }
+    Ok(Some(value.clone()))
+}
+
+/// Sets the value at the dotted `key_path` of the settings file `USER_HOME/crate_name/file_name` and saves the file,
+/// without deserializing the file into a settings struct. Missing tables along the path are created,
+/// and the file is created if it does not exist. A file that can not be loaded is left untouched. Array elements are selected with their index, e.g. `servers.0.host`,
+/// and must already exist.
+///
+/// Returns `InvalidKeyPath` if the path is empty, selects an array element that does not exist, or passes through a value
+/// that is not a table or array. The file is saved in the format of the rest of the library, so comments are not kept.
+///
+/// For example usage, see `get_settings_value()` documentation.
+pub fn set_settings_value(
+    crate_name: &str,
+    file_name: &str,
+    key_path: &str,
+    new_value: Value,
+) -> Result<(), SettingsError> {
+    let keys = split_key_path(key_path).map_err(invalid_key_path)?;
+    let mut root = match load_settings_raw(crate_name, file_name) {
+        Ok(root) => root,
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+            Value::Table(Table::new())
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let (last_key, parent_keys) = keys.split_last().expect("key paths are never empty");
+    let mut value = &mut root;
+    for (depth, key) in parent_keys.iter().enumerate() {
+        value = match value {
+            Value::Table(table) => table
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Table(Table::new())),
+            Value::Array(array) => match key
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < array.len())
+            {
+                Some(index) => &mut array[index],
+                None => return Err(invalid_key_path(missing_index(&keys, depth))),
+            },
+            _ => return Err(invalid_key_path(mismatch(&keys, depth, value))),
+        };
+    }
+    let depth = parent_keys.len();
+    match value {
+        Value::Table(table) => {
+            table.insert(last_key.to_string(), new_value);
+        }
+        Value::Array(array) => match last_key
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < array.len())
+        {
+            Some(index) => array[index] = new_value,
+            None => return Err(invalid_key_path(missing_index(&keys, depth))),
+        },
+        _ => return Err(invalid_key_path(mismatch(&keys, depth, value))),
+    }
+    save_settings_raw(crate_name, file_name, &root)?;
+    Ok(())
+}
+
+/// Wraps a key path message in the error returned by `set_settings_value()`
+fn invalid_key_path(message: String) -> SettingsError {
+    SettingsError::Save(SaveSettingsError::InvalidKeyPath(message))
+}
+
+/// Splits a dotted key path into its keys, returning a message if the path or one of its keys is empty
+fn split_key_path(key_path: &str) -> Result<Vec<&str>, String> {
+    let keys: Vec<&str> = key_path.split('.').collect();
+    if keys.iter().any(|key| key.is_empty()) {
+        return Err(format!("{:?} is not a valid key path", key_path));
+    }
+    Ok(keys)
+}
+
+/// Describes a key path that reaches a value which can not contain the key at `depth`
+fn mismatch(keys: &[&str], depth: usize, value: &Value) -> String {
+    if depth == 0 {
+        return format!("the settings file is a {}, not a table", value.type_str());
+    }
+    match value {
+        Value::Array(_) => format!(
+            "{} is an array, {:?} is not an index",
+            keys[..depth].join("."),
+            keys[depth]
+        ),
+        _ => format!(
+            "{} is a {}, not a table",
+            keys[..depth].join("."),
+            value.type_str()
+        ),
+    }
+}
+
+/// Describes a key path that selects an array element that does not exist
+fn missing_index(keys: &[&str], depth: usize) -> String {
+    format!(
+        "{} has no element {:?}",
+        keys[..depth].join("."),
+        keys[depth]
+    )
+}
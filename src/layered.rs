@@ -0,0 +1,179 @@
+//! Layered settings source file, combines defaults, the settings file, and environment variables
+#![warn(missing_docs)]
+
+use crate::env_overrides::apply_env_overrides;
+use crate::{
+    deserialize_settings, read_settings_bytes, save_settings_with_filename, LoadSettingsError,
+    SettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use toml::value::Table;
+use toml::Value;
+
+/// Options for `load_settings_layered()`, every layer is optional
+pub struct LayerOptions<'a, T> {
+    /// Environment variables starting with this prefix override the file and defaults,
+    /// see `load_settings_with_env_overrides()` for how variables are named and parsed
+    pub env_prefix: Option<&'a str>,
+    /// Settings the file and environment variables are merged on top of
+    pub defaults: Option<T>,
+}
+
+impl<T> Default for LayerOptions<'_, T> {
+    fn default() -> Self {
+        Self {
+            env_prefix: None,
+            defaults: None,
+        }
+    }
+}
+
+/// Loads settings by merging three layers, each overriding the previous one: the `defaults`, the settings file at
+/// `USER_HOME/crate_name/file_name`, and environment variables starting with `env_prefix`.
+///
+/// Layers are merged as TOML values: tables are merged key by key, recursively, while every other value,
+/// including arrays, is replaced as a whole by the layer above it. A missing settings file is skipped,
+/// so the defaults and environment variables alone are enough to load the settings.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::layered::{load_settings_layered, LayerOptions};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Clone)]
+/// struct Database{
+/// host: String,
+/// port: u16,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Clone)]
+/// struct Settings{
+/// database: Database,
+/// features: Vec<String>,
+/// }
+///
+/// let defaults = Settings{ database: Database{ host: "localhost".to_string(), port: 5432 }, features: vec!["a".to_string(), "b".to_string()] };
+///
+/// // the file only overrides some of the defaults
+/// std::fs::create_dir_all(get_user_home().unwrap().join(env!("CARGO_CRATE_NAME"))).unwrap();
+/// std::fs::write(get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("layered.ser"), "features = [\"c\"]\n[database]\nport = 6543\n").unwrap();
+/// std::env::set_var("DOCTEST_LAYERED_DATABASE__HOST", "db.example.com");
+///
+/// let loaded = load_settings_layered(env!("CARGO_CRATE_NAME"), "layered.ser", LayerOptions{ env_prefix: Some("DOCTEST_LAYERED_"), defaults: Some(defaults) }).unwrap();
+/// assert_eq!(loaded, Settings{ database: Database{ host: "db.example.com".to_string(), port: 6543 }, features: vec!["c".to_string()] });
+/// ```
+pub fn load_settings_layered<T>(
+    crate_name: &str,
+    file_name: &str,
+    options: LayerOptions<T>,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    let mut value = match &options.defaults {
+        None => Value::Table(Table::new()),
+        Some(defaults) => {
+            Value::try_from(defaults).map_err(LoadSettingsError::DefaultsNotSerializable)?
+        }
+    };
+
+    match read_settings_bytes(Path::new(crate_name), file_name) {
+        Ok((settings_file_path, file_data)) => {
+            let file_value = deserialize_settings::<Value>(settings_file_path, file_data)?;
+            merge_values(&mut value, file_value);
+        }
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    if let Some(env_prefix) = options.env_prefix {
+        apply_env_overrides(&mut value, env_prefix);
+    }
+
+    value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, filling in any fields missing from the file with the
+/// values from `T::default()`, so adding a field to a settings struct does not break files saved by older versions.
+///
+/// The file is merged over the serialized defaults like in `load_settings_layered()`: tables are merged recursively,
+/// while values and arrays present in the file are kept as they are. Unlike `load_settings_layered()`, a missing file is an error.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::layered::load_settings_merged;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct SettingsV1{
+/// volume: u32,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct SettingsV2{
+/// volume: u32,
+/// muted: bool,
+/// }
+///
+/// impl Default for SettingsV2 {
+///     fn default() -> Self {
+///         Self{ volume: 50, muted: false }
+///     }
+/// }
+///
+/// save_settings!(SettingsV1{ volume: 80 }, "merged.ser").unwrap();
+///
+/// // `muted` is missing from the file, so a plain load fails
+/// assert!(load_settings!(SettingsV2, "merged.ser").is_err());
+///
+/// let loaded = load_settings_merged::<SettingsV2>(env!("CARGO_CRATE_NAME"), "merged.ser").unwrap();
+/// assert_eq!(loaded, SettingsV2{ volume: 80, muted: false });
+/// ```
+pub fn load_settings_merged<T>(crate_name: &str, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Default + Serialize + Deserialize<'a>,
+{
+    let mut value =
+        Value::try_from(T::default()).map_err(LoadSettingsError::DefaultsNotSerializable)?;
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let file_value = deserialize_settings::<Value>(settings_file_path, file_data)?;
+    merge_values(&mut value, file_value);
+    value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
+
+/// Loads settings like `load_settings_merged()`, then saves the merged settings back to the file,
+/// upgrading it in place so it contains every field of `T`.
+pub fn load_settings_merged_and_save<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, SettingsError>
+where
+    for<'a> T: Default + Serialize + Deserialize<'a>,
+{
+    let settings = load_settings_merged::<T>(crate_name, file_name)?;
+    save_settings_with_filename(crate_name, file_name, &settings)?;
+    Ok(settings)
+}
+
+/// Merges `overlay` on top of `base`, tables are merged recursively, any other value replaces the value in `base`
+pub(crate) fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Table(base), Value::Table(overlay)) => {
+            for (key, overlay_value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base_value) => merge_values(base_value, overlay_value),
+                    None => {
+                        base.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
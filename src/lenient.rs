@@ -0,0 +1,165 @@
+//! Lenient loading source file, salvages what it can from a settings file that does not fully load
+#![warn(missing_docs)]
+
+use crate::layered::merge_values;
+use crate::{
+    decode_settings_text, read_settings_bytes, track_settings_path, LoadSettingsError, ParseError,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use toml::{Table, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Part of a settings file that `load_settings_lenient()` could not use
+pub enum RecoveryIssue {
+    /// A line that is not valid TOML was skipped. If the line is a table header, e.g. `[window`,
+    /// every line up to the next table header was skipped along with it
+    SyntaxError {
+        /// The line of the error, starting at 1
+        line: usize,
+        /// The text of the line
+        content: String,
+        /// Why the line could not be parsed
+        error: toml::de::Error,
+    },
+    /// A top level field whose value does not fit the settings struct, its value from `T::default()` was used instead
+    InvalidField {
+        /// The key of the field
+        path: String,
+        /// Why the value could not be deserialized
+        error: toml::de::Error,
+    },
+}
+
+impl Display for RecoveryIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecoveryIssue::SyntaxError { line, content, .. } => {
+                write!(f, "skipped line {}: {}", line, content)
+            }
+            RecoveryIssue::InvalidField { path, error } => {
+                write!(f, "used the default for {}: {}", path, error.message())
+            }
+        }
+    }
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, salvaging every valid field of a file that does not fully load
+/// rather than failing, so one malformed line does not cost the user all of their settings.
+///
+/// Lines that are not valid TOML are skipped, then each top level field of the file is used only if the settings
+/// still deserialize with it, any other field keeps its value from `T::default()`. Returns the settings along with
+/// everything that was skipped, which is empty if the file loads normally.
+/// A missing or unreadable file is still an error, as is a file that is not valid UTF-8.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::lenient::{load_settings_lenient, RecoveryIssue};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Default)]
+/// struct Settings{
+/// volume: u32,
+/// name: String,
+/// muted: bool,
+/// }
+///
+/// let settings_dir = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME"));
+/// std::fs::create_dir_all(&settings_dir).unwrap();
+/// std::fs::write(settings_dir.join("lenient.ser"), "volume = \"loud\"\nname = \"Ferris\"\nmuted = tru\n").unwrap();
+///
+/// let (settings, issues) = load_settings_lenient::<Settings>(env!("CARGO_CRATE_NAME"), "lenient.ser").unwrap();
+/// assert_eq!(settings, Settings{ volume: 0, name: "Ferris".to_string(), muted: false });
+/// assert!(matches!(&issues[0], RecoveryIssue::SyntaxError{ line: 3, .. }));
+/// assert!(matches!(&issues[1], RecoveryIssue::InvalidField{ path, .. } if path == "volume"));
+/// ```
+pub fn load_settings_lenient<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<(T, Vec<RecoveryIssue>), LoadSettingsError>
+where
+    for<'a> T: Default + Serialize + Deserialize<'a>,
+{
+    let defaults =
+        Value::try_from(T::default()).map_err(LoadSettingsError::DefaultsNotSerializable)?;
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let text = decode_settings_text(&settings_file_path, &file_data)?;
+
+    let mut issues = vec![];
+    let file_table = parse_lenient(text, &mut issues).map_err(|(err, document)| {
+        LoadSettingsError::ParseError(Box::new(ParseError::new(
+            settings_file_path.clone(),
+            &document,
+            err,
+        )))
+    })?;
+
+    let mut value = defaults;
+    for (key, field_value) in file_table {
+        let mut candidate = value.clone();
+        merge_values(
+            &mut candidate,
+            Value::Table(Table::from_iter([(key.clone(), field_value)])),
+        );
+        match candidate.clone().try_into::<T>() {
+            Ok(_) => value = candidate,
+            Err(error) => {
+                log_warn!(
+                    "using the default for {} in {}: {:?}",
+                    key,
+                    settings_file_path.display(),
+                    error
+                );
+                issues.push(RecoveryIssue::InvalidField { path: key, error });
+            }
+        }
+    }
+    let settings = value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)?;
+    track_settings_path(settings_file_path);
+    Ok((settings, issues))
+}
+
+/// Parses `text` into a table, blanking out the line of each syntax error, and the table it belongs to if the line is
+/// a table header, until the rest parses. Lines are blanked rather than removed so errors keep their line numbers.
+/// An error that skipping lines can not fix is returned along with the document it was found in
+fn parse_lenient(
+    text: &str,
+    issues: &mut Vec<RecoveryIssue>,
+) -> Result<Table, (toml::de::Error, String)> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    loop {
+        let document = lines.join("\n");
+        let error = match document.parse::<Table>() {
+            Ok(table) => return Ok(table),
+            Err(error) => error,
+        };
+        let line_index = match error.span() {
+            Some(span) => document[..span.start.min(document.len())]
+                .matches('\n')
+                .count(),
+            None => return Err((error, document)),
+        };
+        // an error without an offending line can not be fixed by skipping lines
+        if lines[line_index].trim().is_empty() {
+            return Err((error, document));
+        }
+        issues.push(RecoveryIssue::SyntaxError {
+            line: line_index + 1,
+            content: lines[line_index].to_string(),
+            error,
+        });
+        let is_header = lines[line_index].trim_start().starts_with('[');
+        lines[line_index] = "";
+        if is_header {
+            for line in lines.iter_mut().skip(line_index + 1) {
+                if line.trim_start().starts_with('[') {
+                    break;
+                }
+                *line = "";
+            }
+        }
+    }
+}
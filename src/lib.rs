@@ -4,40 +4,463 @@
 /// Global settings file path list, paths are added when successfully loaded, or when successfully saved.
 pub static SETTINGS_PATHS: RwLock<Vec<PathBuf>> = RwLock::new(vec![]);
 
-use crate::LoadSettingsError::{DeserializationError, IOError};
+use crate::backend::{get_backend, BackendError, SettingsBackend};
+use crate::instrument::SettingsOperation;
+use crate::validation::ValidationError;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
+use std::cell::RefCell;
 use std::io::{Error, Read, Write};
-use std::path::PathBuf;
-use std::sync::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 use std::{fs, io};
 
+#[cfg(feature = "logging")]
+/// Logs with `log::debug!` when the `logging` feature is enabled
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        log::debug!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+/// Does nothing, logging is only done with the `logging` feature
+macro_rules! log_debug {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "logging")]
+/// Logs with `log::info!` when the `logging` feature is enabled
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        log::info!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+/// Does nothing, logging is only done with the `logging` feature
+macro_rules! log_info {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "logging")]
+/// Logs with `log::warn!` when the `logging` feature is enabled
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        log::warn!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+/// Does nothing, logging is only done with the `logging` feature
+macro_rules! log_warn {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "logging")]
+/// Logs with `log::error!` when the `logging` feature is enabled
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        log::error!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+/// Does nothing, logging is only done with the `logging` feature
+macro_rules! log_error {
+    ($($arg:tt)*) => {};
+}
+
 /// Prelude module that contains all the imports for `cr_program_settings`;
 pub mod prelude {
     pub use crate::{
-        delete_setting_file, delete_settings, get_user_home, load_settings,
-        load_settings_with_filename, save_settings, save_settings_with_filename,
-        settings_container, SETTINGS_PATHS,
+        clear_settings, delete_default_settings_file, delete_setting_file,
+        delete_setting_file_if_exists, delete_settings_folder, delete_settings_folder_if_exists,
+        delete_settings_folder_with_symlinks, delete_settings_if_exists,
+        env_overrides::load_settings_with_env_overrides,
+        get_user_home, list_settings_files, load_all_settings, load_settings, load_settings_at,
+        load_settings_borrowed, load_settings_from_path, load_settings_in_dir, load_settings_or,
+        load_settings_or_default, load_settings_or_with_filename, load_settings_raw,
+        load_settings_value, load_settings_with_filename, load_settings_with_limit,
+        program_settings::{ProgramSettings, Settings},
+        quarantine_settings_file, read_settings_string, refresh_user_home, rename_settings_file,
+        save_settings, save_settings_at, save_settings_in_dir, save_settings_new,
+        save_settings_raw, save_settings_to_path, save_settings_to_writer, save_settings_value,
+        save_settings_with_filename, save_settings_with_header, save_settings_with_limit,
+        settings_container, settings_modified_time, update_all_settings, validate_serializable,
+        validation::{
+            load_settings_validated, load_settings_with_validator, save_settings_validated,
+            Validate, ValidationError,
+        },
+        ErrorLocation, LoadSettingsError, ParseError, SaveSettingsError, SettingsError,
+        SETTINGS_PATHS,
+    };
+
+    // kept in the prelude so code written against older versions still compiles, with a deprecation warning where it is called
+    #[allow(deprecated)]
+    pub use crate::delete_settings;
+
+    #[cfg(feature = "bincode")]
+    pub use crate::binary::{load_settings_bin, save_settings_bin};
+
+    #[cfg(feature = "yaml")]
+    pub use crate::yaml::{load_settings_yaml, save_settings_yaml};
+
+    #[cfg(feature = "encryption")]
+    pub use crate::encryption::{load_settings_encrypted, save_settings_encrypted};
+
+    #[cfg(feature = "derive")]
+    pub use cr_program_settings_derive::{settings_path, ProgramSettings};
+
+    #[cfg(feature = "async")]
+    pub use crate::{
+        load_settings_async, load_settings_with_filename_async, save_settings_async,
+        save_settings_with_filename_async,
     };
 }
 
+#[cfg(feature = "derive")]
+pub use cr_program_settings_derive::{settings_path, ProgramSettings};
+
+mod instrument;
+
 /// Source code for the settings container.
 pub mod settings_container;
 
-/// Returns the users home as an optional using the "home" crate
+/// Source code for settings validation.
+pub mod validation;
+
+/// Source code for the storage backends.
+pub mod backend;
+
+#[cfg(feature = "testing")]
+/// Source code for test fixtures that isolate settings from the real home directory.
+pub mod testing;
+
+/// Source code for overriding settings with environment variables.
+pub mod env_overrides;
+
+/// Source code for loading settings from layered defaults, files, and environment variables.
+pub mod layered;
+
+/// Source code for managing settings file permissions.
+pub mod permissions;
+
+/// Source code for storing settings next to the executable.
+pub mod portable;
+
+/// Source code for the settings file formats.
+pub mod format;
+
+/// Source code for saving several settings files at once.
+pub mod batch;
+
+/// Source code for named settings profiles.
+pub mod profiles;
+
+/// Source code for loading settings while reporting unknown keys.
+pub mod strict;
+
+/// Source code for system-wide settings defaults.
+pub mod system;
+
+/// Source code for project-local settings found from the current directory.
+pub mod project;
+
+/// Source code for settings file statistics.
+pub mod stats;
+
+/// Source code for reading and writing single settings keys.
+pub mod keys;
+
+/// Source code for comparing settings files.
+pub mod diff;
+
+/// Source code for the registry of settings types.
+pub mod registry;
+
+/// Source code for exporting and importing settings files.
+pub mod export;
+
+/// Source code for keeping settings of each app version in its own folder.
+pub mod versioned;
+
+/// Source code for the options settings files are saved with.
+pub mod options;
+
+/// Source code for loading what can be salvaged from a corrupt settings file.
+pub mod lenient;
+
+/// Source code for storing several settings as sections of one file.
+pub mod sections;
+
+/// Source code for writing example settings files from defaults.
+pub mod template;
+
+/// Source code for settings files that include other settings files.
+pub mod includes;
+
+/// Source code for path settings fields that expand `~` and environment variables.
+pub mod paths;
+
+/// Source code for the `ProgramSettings` trait.
+pub mod program_settings;
+
+/// Source code for settings file locations, as created by the `settings_path!` macro.
+pub mod location;
+
+#[cfg(feature = "bincode")]
+/// Source code for saving and loading settings in a binary format.
+pub mod binary;
+
+#[cfg(feature = "yaml")]
+/// Source code for saving and loading settings as YAML.
+pub mod yaml;
+
+#[cfg(feature = "encryption")]
+/// Source code for saving and loading encrypted settings.
+pub mod encryption;
+
+#[cfg(feature = "signing")]
+/// Source code for saving and loading signed settings.
+pub mod signing;
+
+#[cfg(feature = "keyring")]
+/// Source code for storing secret fields in the OS keyring.
+pub mod secrets;
+
+#[cfg(feature = "async")]
+/// Source code for the async save and load functions.
+pub mod asynchronous;
+
+#[cfg(feature = "async")]
+pub use asynchronous::{
+    load_settings_async, load_settings_with_filename_async, save_settings_async,
+    save_settings_with_filename_async,
+};
+
+#[cfg(feature = "autosave")]
+/// Source code for the debounced background autosave.
+pub mod autosave;
+
+#[cfg(feature = "watch")]
+/// Source code for watching settings files for changes.
+pub mod watch;
+
+#[cfg(feature = "arc-swap")]
+/// Source code for the lock-free settings handle.
+pub mod handle;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+/// Source code for the browser `localStorage` backend.
+pub mod local_storage;
+
+thread_local! {
+    /// Per thread override of the users home, set by `testing::TempSettingsDir`
+    static HOME_OVERRIDE: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Type of a custom users home resolver set with `set_home_resolver()`
+type HomeResolver = Arc<dyn Fn() -> Option<PathBuf> + Send + Sync>;
+
+/// Global custom users home resolver, `None` means the "home" crate is used.
+static HOME_RESOLVER: RwLock<Option<HomeResolver>> = RwLock::new(None);
+
+/// The users home from the operating system, looked up on first use, the outer `None` means it was not looked up yet
+static SYSTEM_HOME_CACHE: RwLock<Option<Option<PathBuf>>> = RwLock::new(None);
+
+/// Returns the users home as an optional using the "home" crate, this is always `None` on `wasm32`.
+/// The operating system is only asked once, later calls return the cached directory, see `refresh_user_home()`.
+/// If a resolver was set with `set_home_resolver()`, its result is returned instead.
+/// If neither finds a directory, the directory set with `set_home_fallback()` is returned, if any.
+/// Within `testing::with_temp_settings_dir` or while a `testing::TempSettingsDir` exists, this returns the temporary directory, taking priority over both.
 pub fn get_user_home() -> Option<PathBuf> {
+    if let Some(home_dir) = get_home_override() {
+        return Some(home_dir);
+    }
+    let resolver = HOME_RESOLVER.read().unwrap().clone();
+    let home_dir = match resolver {
+        Some(resolver) => resolver(),
+        None => cached_system_home_dir(),
+    };
+    home_dir.or_else(home_fallback_dir)
+}
+
+/// Directory used when the users home can not be found, set with `set_home_fallback()`
+static HOME_FALLBACK: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets a directory that `get_user_home()` returns when the users home can not be found, e.g. in containers or CI
+/// environments without a home directory, so settings are still saved instead of every save failing with `FailedToGetUserHome`.
+/// A relative directory, e.g. `"."` for the current directory, is resolved against the current directory each time it is used.
+/// Off by default, the fallback is never used while the users home can be found.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::{reset_home_fallback, set_home_fallback};
+///
+/// // keep settings next to the program when there is no home directory
+/// set_home_fallback(".");
+///
+/// reset_home_fallback();
+/// ```
+pub fn set_home_fallback(dir: impl Into<PathBuf>) {
+    *HOME_FALLBACK.write().unwrap() = Some(dir.into());
+}
+
+/// Removes the directory set with `set_home_fallback()`, so a missing users home is an error again
+pub fn reset_home_fallback() {
+    *HOME_FALLBACK.write().unwrap() = None;
+}
+
+/// Returns the absolute directory set with `set_home_fallback()`, if any
+fn home_fallback_dir() -> Option<PathBuf> {
+    let fallback = HOME_FALLBACK.read().unwrap().clone()?;
+    // only fails for an empty path
+    let fallback = std::path::absolute(fallback).ok()?;
+    log_debug!(
+        "unable to find the users home, using {}",
+        fallback.display()
+    );
+    Some(fallback)
+}
+
+/// Looks up the users home from the operating system again, replacing the directory cached by `get_user_home()`,
+/// for the rare program that changes e.g. the `HOME` environment variable while running. Returns `get_user_home()` afterwards.
+pub fn refresh_user_home() -> Option<PathBuf> {
+    *SYSTEM_HOME_CACHE.write().unwrap() = Some(system_home_dir());
+    get_user_home()
+}
+
+/// Returns the cached users home from the operating system, looking it up if this is the first call
+fn cached_system_home_dir() -> Option<PathBuf> {
+    if let Some(home_dir) = SYSTEM_HOME_CACHE.read().unwrap().as_ref() {
+        return home_dir.clone();
+    }
+    SYSTEM_HOME_CACHE
+        .write()
+        .unwrap()
+        .get_or_insert_with(system_home_dir)
+        .clone()
+}
+
+/// Sets a global resolver that `get_user_home()` consults instead of the "home" crate, so every function in the library
+/// stores settings under the directory it returns. Returning `None` makes saving and loading fail with `FailedToGetUserHome`.
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::{reset_home_resolver, set_home_resolver};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// // e.g. a portable app that keeps its settings next to the executable
+/// let portable_dir = std::env::temp_dir().join("cr_program_settings_doctest_resolver");
+/// let resolver_dir = portable_dir.clone();
+/// set_home_resolver(move || Some(resolver_dir.clone()));
+///
+/// save_settings!(Settings{ setting1: 5 }, "resolved.ser").unwrap();
+/// assert!(portable_dir.join(env!("CARGO_CRATE_NAME")).join("resolved.ser").is_file());
+///
+/// reset_home_resolver();
+/// std::fs::remove_dir_all(portable_dir).unwrap();
+/// ```
+pub fn set_home_resolver(resolver: impl Fn() -> Option<PathBuf> + Send + Sync + 'static) {
+    *HOME_RESOLVER.write().unwrap() = Some(Arc::new(resolver));
+}
+
+/// Removes the resolver set with `set_home_resolver()`, so the "home" crate is used again
+pub fn reset_home_resolver() {
+    *HOME_RESOLVER.write().unwrap() = None;
+}
+
+/// Returns the users home override of the current thread
+pub(crate) fn get_home_override() -> Option<PathBuf> {
+    HOME_OVERRIDE.with(|home_override| home_override.borrow().clone())
+}
+
+#[cfg(feature = "testing")]
+/// Sets the users home override of the current thread, returning the previous override
+pub(crate) fn set_home_override(home_dir: Option<PathBuf>) -> Option<PathBuf> {
+    HOME_OVERRIDE.with(|home_override| home_override.replace(home_dir))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Returns the users home directory from the operating system
+fn system_home_dir() -> Option<PathBuf> {
     home::home_dir()
 }
 
+#[cfg(target_arch = "wasm32")]
+/// Returns the users home directory from the operating system, which does not exist on `wasm32`
+fn system_home_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Global default settings folder used by the macros, `None` means the crate name is used.
+static DEFAULT_FOLDER: RwLock<Option<String>> = RwLock::new(None);
+
+/// Sets the folder the macros use when no folder argument is given, e.g. a product name that differs from the crate name.
+///
+/// Precedence: an explicit folder argument, then the folder set here, then the crate name.
+/// The folder is also used as the file name by `save_settings!(settings)` and `load_settings!(Type)`.
+///
+/// The macros read `CARGO_CRATE_NAME` where they are written, so a library that calls them on behalf of an application
+/// stores settings in a folder named after the library. Applications using such a library should call this function
+/// once at startup, so the library's settings end up in the application's folder instead.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::{reset_default_folder, set_default_folder};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// set_default_folder("ProductName");
+/// save_settings!(Settings{ setting1: 4 }, "product.ser").unwrap();
+/// assert!(get_user_home().unwrap().join("ProductName").join("product.ser").is_file());
+///
+/// // an explicit folder still takes priority
+/// save_settings!(Settings{ setting1: 4 }, "product.ser", "OtherFolder").unwrap();
+/// assert!(get_user_home().unwrap().join("OtherFolder").join("product.ser").is_file());
+///
+/// reset_default_folder();
+/// delete_settings_folder("ProductName").unwrap();
+/// delete_settings_folder("OtherFolder").unwrap();
+/// ```
+pub fn set_default_folder(folder: &str) {
+    *DEFAULT_FOLDER.write().unwrap() = Some(folder.to_string());
+}
+
+/// Removes the folder set with `set_default_folder()`, so the macros use the crate name again
+pub fn reset_default_folder() {
+    *DEFAULT_FOLDER.write().unwrap() = None;
+}
+
+/// Returns the folder set with `set_default_folder()`, or `crate_name` if none was set
+pub fn default_folder(crate_name: &str) -> String {
+    match DEFAULT_FOLDER.read().unwrap().as_ref() {
+        Some(folder) => folder.clone(),
+        None => crate_name.to_string(),
+    }
+}
+
 #[macro_export]
-/// Saves settings given a struct to save, to the home directory with a name matching the crate name
+/// Saves settings given a struct to save, to the home directory with a name matching the crate name,
+/// or the folder set with `set_default_folder()`
 ///
 /// Syntax:
 ///     save_settings!(settings_struct)
 ///     save_settings!(settings_struct, file_name)
 ///     save_settings!(settings_struct, file_name, folder_name)
+///     save_settings!(settings_struct, options = save_options) // with `options::SaveOptions`, after any of the arguments above
 ///
 /// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
 /// use serde::{Deserialize, Serialize};
 /// use cr_program_settings::prelude::*;
 ///
@@ -72,11 +495,43 @@ pub fn get_user_home() -> Option<PathBuf> {
 /// assert_eq!(settings,specific_settings_loaded);
 /// ```
 macro_rules! save_settings {
+    ($settings:expr, options = $options:expr) => {{
+        let folder = $crate::default_folder(env!("CARGO_CRATE_NAME"));
+        $crate::options::save_settings_with_options(
+            &folder,
+            format!("{}.ser", folder),
+            &$settings,
+            &$options,
+        )
+    }};
+    ($settings: expr, $file_name: expr, options = $options:expr) => {
+        $crate::options::save_settings_with_options(
+            $crate::default_folder(env!("CARGO_CRATE_NAME")),
+            &$file_name,
+            &$settings,
+            &$options,
+        )
+    };
+    ($settings: expr, $file_name: expr, $folder_name: expr, options = $options:expr) => {
+        $crate::options::save_settings_with_options(
+            $folder_name,
+            &$file_name,
+            &$settings,
+            &$options,
+        )
+    };
     ($settings:expr) => {
-        save_settings(env!("CARGO_CRATE_NAME"), &$settings)
+        save_settings(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            &$settings,
+        )
     };
     ($settings: expr, $file_name: expr) => {
-        save_settings_with_filename(env!("CARGO_CRATE_NAME"), &$file_name, &$settings)
+        save_settings_with_filename(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            &$file_name,
+            &$settings,
+        )
     };
     ($settings: expr, $file_name: expr, $folder_name: expr) => {
         save_settings_with_filename($folder_name, &$file_name, &$settings)
@@ -84,15 +539,18 @@ macro_rules! save_settings {
 }
 
 #[macro_export]
-/// Loads settings given a type to load, from the home directory with a name matching the crate name
+/// Loads settings given a type to load, from the home directory with a name matching the crate name,
+/// or the folder set with `set_default_folder()`
 ///
 /// Syntax:
 ///     load_settings!(SETTINGS_TYPE)
 ///     load_settings!(SETTINGS_TYPE, file_name)
 ///     load_settings!(SETTINGS_TYPE, file_name,folder_name)
+///     load_settings!(SETTINGS_TYPE, options = load_options) // with `options::LoadOptions`, after any of the arguments above
 ///
 /// For more usage examples, see save_settings!() documentation.
 /// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
 /// use serde::{Deserialize, Serialize};
 /// use cr_program_settings::prelude::*;
 ///
@@ -117,11 +575,36 @@ macro_rules! save_settings {
 /// assert_eq!(settings,loaded_settings);
 /// ```
 macro_rules! load_settings {
+    ($setting_type:ty, options = $options:expr) => {{
+        let folder = $crate::default_folder(env!("CARGO_CRATE_NAME"));
+        $crate::options::load_settings_with_options::<$setting_type>(
+            &folder,
+            format!("{}.ser", folder),
+            &$options,
+        )
+    }};
+    ($setting_type:ty, $file_name: expr, options = $options:expr) => {
+        $crate::options::load_settings_with_options::<$setting_type>(
+            $crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+            &$options,
+        )
+    };
+    ($setting_type:ty, $file_name: expr, $folder_name: expr, options = $options:expr) => {
+        $crate::options::load_settings_with_options::<$setting_type>(
+            $folder_name,
+            $file_name,
+            &$options,
+        )
+    };
     ($setting_type:ty) => {
-        load_settings::<$setting_type>(env!("CARGO_CRATE_NAME"))
+        load_settings::<$setting_type>(&$crate::default_folder(env!("CARGO_CRATE_NAME")))
     };
     ($setting_type:ty,$file_name: expr) => {
-        load_settings_with_filename::<$setting_type>(env!("CARGO_CRATE_NAME"), $file_name)
+        load_settings_with_filename::<$setting_type>(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+        )
     };
     ($setting_type:ty,$file_name: expr,$folder_name: expr) => {
         load_settings_with_filename::<$setting_type>($folder_name, $file_name)
@@ -129,128 +612,1629 @@ macro_rules! load_settings {
 }
 
 #[macro_export]
-/// Deletes settings located at the home directory with a name matching the crate name
+/// Loads settings like `load_settings!`, but returns the settings directly, falling back to `Default::default()`
+/// if the settings file does not exist. If the file exists but can not be loaded, e.g. it is corrupt,
+/// the macro panics unless `on_corrupt = default` is given, in which case the default is returned.
+///
+/// Syntax:
+///     load_settings_or_default!(SETTINGS_TYPE)
+///     load_settings_or_default!(SETTINGS_TYPE, file_name)
+///     load_settings_or_default!(SETTINGS_TYPE, file_name, folder_name)
+///     load_settings_or_default!(SETTINGS_TYPE, on_corrupt = default) // or on_corrupt = panic or quarantine, with any of the arities above
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Default)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// // nothing has been saved yet
+/// let settings = load_settings_or_default!(Settings, "never_saved.ser");
+/// assert_eq!(settings, Settings::default());
+///
+/// save_settings!(Settings{ setting1: 2 }, "saved_or_default.ser").unwrap();
+/// assert_eq!(load_settings_or_default!(Settings, "saved_or_default.ser", on_corrupt = default).setting1, 2);
+/// ```
+macro_rules! load_settings_or_default {
+    ($setting_type:ty, on_corrupt = $policy:ident) => {{
+        let folder = $crate::default_folder(env!("CARGO_CRATE_NAME"));
+        $crate::load_settings_or_default::<$setting_type>(
+            &folder,
+            &format!("{}.ser", folder),
+            $crate::__corrupt_policy!($policy),
+        )
+    }};
+    ($setting_type:ty, $file_name: expr, on_corrupt = $policy:ident) => {
+        $crate::load_settings_or_default::<$setting_type>(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+            $crate::__corrupt_policy!($policy),
+        )
+    };
+    ($setting_type:ty, $file_name: expr, $folder_name: expr, on_corrupt = $policy:ident) => {
+        $crate::load_settings_or_default::<$setting_type>(
+            $folder_name,
+            $file_name,
+            $crate::__corrupt_policy!($policy),
+        )
+    };
+    ($setting_type:ty) => {
+        $crate::load_settings_or_default!($setting_type, on_corrupt = panic)
+    };
+    ($setting_type:ty, $file_name: expr) => {
+        $crate::load_settings_or_default!($setting_type, $file_name, on_corrupt = panic)
+    };
+    ($setting_type:ty, $file_name: expr, $folder_name: expr) => {
+        $crate::load_settings_or_default!(
+            $setting_type,
+            $file_name,
+            $folder_name,
+            on_corrupt = panic
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Maps the `on_corrupt` argument of `load_settings_or_default!` to a `CorruptPolicy`
+macro_rules! __corrupt_policy {
+    (panic) => {
+        $crate::CorruptPolicy::Panic
+    };
+    (default) => {
+        $crate::CorruptPolicy::Default
+    };
+    (quarantine) => {
+        $crate::CorruptPolicy::Quarantine
+    };
+}
+
+#[macro_export]
+/// Wraps settings in a `SettingsContainer` using the same names as `save_settings!()`,
+/// see `SettingsContainer::with_default_names()`
+///
+/// Syntax:
+///     settings_container!(settings) // the file named: env!("CARGO_CRATE_NAME").ser stored in the folder named: env!("CARGO_CRATE_NAME")
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::settings_container;
+///
+/// let settings = settings_container!(1u32);
+/// assert_eq!(settings.crate_name(), env!("CARGO_CRATE_NAME"));
+/// assert_eq!(settings.file_name(), format!("{}.ser", env!("CARGO_CRATE_NAME")));
+/// ```
+macro_rules! settings_container {
+    ($settings: expr) => {
+        $crate::settings_container::SettingsContainer::with_default_names(
+            $settings,
+            env!("CARGO_CRATE_NAME"),
+        )
+    };
+}
+
+#[macro_export]
+/// Saves settings to any path, outside the users home, see `save_settings_to_path()`
+///
+/// Syntax:
+///     save_settings_at!(settings, path)
+///
+/// For example usage, see `save_settings_to_path()` documentation.
+macro_rules! save_settings_at {
+    ($settings: expr, $path: expr) => {
+        $crate::save_settings_to_path(::std::path::Path::new($path), &$settings)
+    };
+}
+
+#[macro_export]
+/// Loads settings from any path, outside the users home, see `load_settings_from_path()`
+///
+/// Syntax:
+///     load_settings_at!(SETTINGS_TYPE, path)
+///
+/// For example usage, see `save_settings_to_path()` documentation.
+macro_rules! load_settings_at {
+    ($setting_type:ty, $path: expr) => {
+        $crate::load_settings_from_path::<$setting_type>(::std::path::Path::new($path))
+    };
+}
+
+#[macro_export]
+/// Deletes a settings file located at the home directory in the folder matching the crate name,
+/// or the folder set with `set_default_folder()`. Other files in the folder are never deleted,
+/// use `delete_settings_folder()` to delete the whole folder.
 /// Syntax:
-///     delete_settings!() // deletes file named: env!("CARGO_CRATE_NAME") file stored in the folder named: env!("CARGO_CRATE_NAME")
+///     delete_settings!() // deletes the file named: env!("CARGO_CRATE_NAME").ser stored in the folder named: env!("CARGO_CRATE_NAME")
 ///     delete_settings!(file_name) // deletes the file named: file_name stored in the folder named: env!("CARGO_CRATE_NAME")
 ///     delete_settings!(file_name, folder_name) // deletes the file named: file_name stored in the folder named: folder_name
 macro_rules! delete_settings {
     () => {
-        delete_settings(env!("CARGO_CRATE_NAME"))
+        delete_default_settings_file(&$crate::default_folder(env!("CARGO_CRATE_NAME")))
     };
     ($file_name: expr) => {
-        delete_setting_file(env!("CARGO_CRATE_NAME"), $file_name)
+        delete_setting_file(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+        )
     };
     ($file_name: expr,$folder_name: expr) => {
         delete_setting_file($folder_name, $file_name)
     };
 }
 
+#[macro_export]
+/// Deletes a settings file like `delete_settings!`, but treats a missing file as success,
+/// returning `io::Result<bool>` with whether the file existed.
+/// Syntax:
+///     delete_settings_if_exists!() // deletes the file named: env!("CARGO_CRATE_NAME").ser stored in the folder named: env!("CARGO_CRATE_NAME")
+///     delete_settings_if_exists!(file_name) // deletes the file named: file_name stored in the folder named: env!("CARGO_CRATE_NAME")
+///     delete_settings_if_exists!(file_name, folder_name) // deletes the file named: file_name stored in the folder named: folder_name
+///
+/// For example usage, see `delete_setting_file_if_exists()` documentation.
+macro_rules! delete_settings_if_exists {
+    () => {{
+        let folder = $crate::default_folder(env!("CARGO_CRATE_NAME"));
+        delete_setting_file_if_exists(&folder, &format!("{}.ser", folder))
+    }};
+    ($file_name: expr) => {
+        delete_setting_file_if_exists(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+        )
+    };
+    ($file_name: expr,$folder_name: expr) => {
+        delete_setting_file_if_exists($folder_name, $file_name)
+    };
+}
+
+#[cfg(feature = "async")]
+#[macro_export]
+/// Async version of `save_settings!()`, expands to a future that must be `.await`ed
+///
+/// Syntax:
+///     save_settings_async!(settings_struct).await
+///     save_settings_async!(settings_struct, file_name).await
+///     save_settings_async!(settings_struct, file_name, folder_name).await
+macro_rules! save_settings_async {
+    ($settings:expr) => {
+        save_settings_async(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            &$settings,
+        )
+    };
+    ($settings: expr, $file_name: expr) => {
+        save_settings_with_filename_async(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            &$file_name,
+            &$settings,
+        )
+    };
+    ($settings: expr, $file_name: expr, $folder_name: expr) => {
+        save_settings_with_filename_async($folder_name, &$file_name, &$settings)
+    };
+}
+
+#[cfg(feature = "async")]
+#[macro_export]
+/// Async version of `load_settings!()`, expands to a future that must be `.await`ed
+///
+/// Syntax:
+///     load_settings_async!(SETTINGS_TYPE).await
+///     load_settings_async!(SETTINGS_TYPE, file_name).await
+///     load_settings_async!(SETTINGS_TYPE, file_name, folder_name).await
+macro_rules! load_settings_async {
+    ($setting_type:ty) => {
+        load_settings_async::<$setting_type>(&$crate::default_folder(env!("CARGO_CRATE_NAME")))
+    };
+    ($setting_type:ty,$file_name: expr) => {
+        load_settings_with_filename_async::<$setting_type>(
+            &$crate::default_folder(env!("CARGO_CRATE_NAME")),
+            $file_name,
+        )
+    };
+    ($setting_type:ty,$file_name: expr,$folder_name: expr) => {
+        load_settings_with_filename_async::<$setting_type>($folder_name, $file_name)
+    };
+}
+
 #[derive(Debug)]
 /// An enum state representing the kinds of errors that saving settings has
 pub enum SaveSettingsError {
     /// The library was unable to find the users home directory
     FailedToGetUserHome,
+    /// In portable mode, the library was unable to find the directory of the executable
+    FailedToGetExecutableDir(Error),
     /// The library encountered an io error when saving or creating the file or directory
     IOError(Error),
     /// The library encountered an error while serializing the struct
     SerializationError(toml::ser::Error),
+    /// The settings serialized to a toml value that is not a table, e.g. an `array` or `integer`.
+    /// TOML documents must be a table at the top level, so settings must be a struct or map,
+    /// wrap other values in a struct, e.g. `struct Wrapper { value: Vec<u32> }`
+    RootNotATable(String),
+    /// The target settings file path exists, but is a directory rather than a file
+    PathIsDirectory(PathBuf),
+    /// The settings file path, or one of its components, is longer than the platform allows,
+    /// e.g. a file name over 255 bytes, usually caused by crate or file names built from user input
+    PathTooLong(PathBuf),
+    /// The settings storage, e.g. the browsers `localStorage`, is not available
+    StorageUnavailable,
+    /// The settings storage is full, e.g. the browsers `localStorage` quota was exceeded
+    QuotaExceeded,
+    /// The settings failed validation, nothing was written
+    ValidationFailed(ValidationError),
+    /// The settings file was modified outside the program since it was loaded, nothing was written.
+    /// `on_disk_modified` is the modification time of the file, if the backend stores it on the filesystem
+    ConflictDetected {
+        /// When the settings file on disk was last modified
+        on_disk_modified: Option<std::time::SystemTime>,
+    },
+    /// The profile name is not a safe file name, see `profiles::is_valid_profile_name()`
+    InvalidProfileName(String),
+    /// The settings file already exists and was left untouched, returned by `save_settings_new()`
+    AlreadyExists(PathBuf),
+    /// The extension of the file name, e.g. `json` without the `json` feature, names a format that is not compiled in
+    FormatNotEnabled(String),
+    /// The dotted key path is empty, or can not be followed through the settings file, the message describes why
+    InvalidKeyPath(String),
+    /// Saving is disabled with `set_read_only()`, nothing was written
+    ReadOnlyMode,
+    /// The serialized settings are larger than the allowed size, nothing was written
+    FileTooLarge {
+        /// Size of the serialized settings in bytes
+        size: u64,
+        /// The largest allowed size in bytes
+        limit: u64,
+        /// The settings file that would have been written
+        path: PathBuf,
+    },
+    /// Saving kept failing with an io error that is usually temporary, e.g. a file locked by an antivirus scanner,
+    /// and the retries configured in `options::SaveOptions` ran out
+    IOErrorAfterRetries {
+        /// How many times saving was attempted
+        attempts: u32,
+        /// The io error of the last attempt
+        source: Error,
+    },
+    #[cfg(feature = "bincode")]
+    /// The library encountered an error while encoding the struct using bincode
+    BincodeEncodeError(bincode::Error),
+    #[cfg(feature = "json")]
+    /// The library encountered an error while serializing the struct using serde_json
+    JsonSerializationError(serde_json::Error),
+    #[cfg(feature = "yaml")]
+    /// The library encountered an error while serializing the struct using serde_yaml
+    YamlSerializationError(serde_yaml::Error),
+    #[cfg(feature = "encryption")]
+    /// The library was unable to encrypt the serialized settings
+    EncryptionFailed,
+    #[cfg(feature = "keyring")]
+    /// The OS keyring could not store a secret, e.g. it is locked or unavailable
+    KeyringError(keyring::Error),
 }
 
-/// Saves a serializable settings object to a given filename in `USER_HOME/crate_name/file_name`
+/// Saves a serializable settings object to a given filename in `USER_HOME/crate_name/file_name`.
+/// The names can be given as `&str`, `String`, or `&String`, and the crate name also as a `Path` or `OsStr`.
+/// Since 0.2.0, a crate name that is empty, `.`, or contains `..`, or a file name that is not a plain file name,
+/// is rejected with an `InvalidInput` io error instead of saving outside the settings folder.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = env!("CARGO_CRATE_NAME").to_string();
+/// let file_name = format!("{}.ser", "named");
+///
+/// save_settings_with_filename(&crate_name, &file_name, &Settings{ setting1: 4 }).unwrap();
+/// let loaded = load_settings_with_filename::<Settings>(crate_name, file_name).unwrap();
+/// assert_eq!(loaded, Settings{ setting1: 4 });
+/// ```
 pub fn save_settings_with_filename<T>(
-    crate_name: &str,
-    file_name: &str,
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
     settings: &T,
 ) -> Result<(), SaveSettingsError>
 where
     T: Serialize,
 {
-    match get_user_home() {
-        None => Err(SaveSettingsError::FailedToGetUserHome),
-        Some(home_dir) => {
-            let settings_path = home_dir.join(PathBuf::from(crate_name));
-            let settings_file_path = settings_path.join(PathBuf::from(file_name));
-            match fs::create_dir_all(&settings_path) {
-                Ok(_) => match File::create(&settings_file_path) {
-                    Ok(mut file) => match toml::to_string_pretty(&settings) {
-                        Ok(serialized_data) => match file.write_all(serialized_data.as_bytes()) {
-                            Ok(_) => {
-                                {
-                                    let mut lock = SETTINGS_PATHS.write().unwrap();
-                                    lock.push(settings_file_path);
-                                }
-                                Ok(())
-                            }
-                            Err(err) => Err(SaveSettingsError::IOError(err)),
-                        },
-                        Err(err) => Err(SaveSettingsError::SerializationError(err)),
-                    },
-                    Err(err) => Err(SaveSettingsError::IOError(err)),
-                },
-                Err(err) => Err(SaveSettingsError::IOError(err)),
-            }
-        }
-    }
-}
-
-/// Saves the settings file given in a directory named using the crate name
-/// Given a struct and a crate name of `my_cool_rust_project`, the program
-/// would save it to `/home/username/my_cool_rust_project/my_cool_rust_project.ser`
-pub fn save_settings<T>(crate_name: &str, settings: &T) -> Result<(), SaveSettingsError>
-where
-    T: Serialize,
-{
-    save_settings_with_filename(crate_name, format!("{}.ser", crate_name).as_str(), settings)
+    save_settings_in_dir(crate_name.as_ref(), file_name.as_ref(), settings)
 }
 
-#[derive(Debug)]
-/// Enum state representing the possible errors that can occur when loading settings
-pub enum LoadSettingsError {
-    /// The library was unable to find the users home directory
-    FailedToGetUserHome,
+/// Saves a serializable settings object to `USER_HOME/relative_dir/file_name`, where `relative_dir` can be
+/// more than one folder deep, e.g. `CompanyName/AppName`.
+/// `relative_dir` must be a non-empty relative path, and `file_name` a plain file name. An absolute path, or one containing
+/// `..` or `.`, is rejected with an `InvalidInput` io error by every save, load, and delete function,
+/// so settings are never written outside the settings root.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::path::Path;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct ToolSettings{
+/// setting1: u32,
+/// }
+///
+/// let settings = ToolSettings{ setting1: 15 };
+/// let vendor_dir = Path::new("cr_program_settings_vendor").join("tool_one");
+///
+/// save_settings_in_dir(&vendor_dir, "tool_one.ser", &settings).expect("Unable to save settings in vendor folder");
+///
+/// let loaded_settings = load_settings_in_dir::<ToolSettings>(&vendor_dir, "tool_one.ser").expect("Unable to load settings from vendor folder");
+///
+/// assert_eq!(settings,loaded_settings);
+///
+/// delete_settings_folder("cr_program_settings_vendor").unwrap();
+/// ```
+pub fn save_settings_in_dir<T>(
+    relative_dir: &Path,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    options::save_settings_with_options(
+        relative_dir,
+        file_name,
+        settings,
+        &options::SaveOptions::default(),
+    )
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/file_name`, preceded by a comment block,
+/// e.g. a generated-file warning or documentation of each field. Every line of `header` is prefixed with `# `,
+/// and the comment is separated from the settings by an empty line, so loading the file is unaffected.
+/// Shorthand for `save_settings_with_options()` with `SaveOptions::new().header(header)`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// volume: u32,
+/// }
+///
+/// let header = "Generated by my_app, edits are kept.\nvolume: 0 to 100";
+/// save_settings_with_header(env!("CARGO_CRATE_NAME"), "header.ser", &Settings{ volume: 50 }, header).unwrap();
+///
+/// assert_eq!(
+///     read_settings_string(env!("CARGO_CRATE_NAME"), "header.ser").unwrap(),
+///     "# Generated by my_app, edits are kept.\n# volume: 0 to 100\n\nvolume = 50\n"
+/// );
+/// assert_eq!(load_settings!(Settings, "header.ser").unwrap(), Settings{ volume: 50 });
+/// ```
+pub fn save_settings_with_header<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+    header: &str,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let options = options::SaveOptions::new().header(header);
+    options::save_settings_with_options(crate_name, file_name, settings, &options)
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/file_name` only if the file does not exist yet,
+/// otherwise returns `SaveSettingsError::AlreadyExists` and leaves the file untouched.
+///
+/// The check and the creation of the file are a single operation on the `FileSystemBackend`, so when two code paths
+/// initialize the same settings at once, exactly one of them succeeds, e.g. for writing defaults on the first run.
+/// Shorthand for `save_settings_with_options()` with `SaveOptions::new().create_new(true)`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings_new(env!("CARGO_CRATE_NAME"), "first_run.ser", &Settings{ setting1: 1 }).expect("Unable to save default settings");
+///
+/// // the users settings are never replaced
+/// assert!(matches!(
+///     save_settings_new(env!("CARGO_CRATE_NAME"), "first_run.ser", &Settings{ setting1: 2 }),
+///     Err(SaveSettingsError::AlreadyExists(_))
+/// ));
+/// assert_eq!(load_settings!(Settings, "first_run.ser").unwrap(), Settings{ setting1: 1 });
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "first_run.ser").unwrap();
+/// ```
+pub fn save_settings_new<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let options = options::SaveOptions::new().create_new(true);
+    options::save_settings_with_options(crate_name, file_name, settings, &options)
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/file_name` only if it serializes to at most `max_bytes`,
+/// otherwise returns `SaveSettingsError::FileTooLarge` and leaves the file untouched,
+/// e.g. so a runaway `Vec` caused by a bug can not fill the users disk.
+/// Shorthand for `save_settings_with_options()` with `SaveOptions::new().max_file_size(max_bytes)`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// history: Vec<String>,
+/// }
+///
+/// let settings = Settings{ history: vec!["a".to_string(); 10] };
+/// save_settings_with_limit(env!("CARGO_CRATE_NAME"), "limited.ser", &settings, 1024).expect("Unable to save settings");
+/// assert_eq!(load_settings_with_limit::<Settings>(env!("CARGO_CRATE_NAME"), "limited.ser", 1024).unwrap(), settings);
+///
+/// let runaway = Settings{ history: vec!["a".to_string(); 1000] };
+/// assert!(matches!(
+///     save_settings_with_limit(env!("CARGO_CRATE_NAME"), "limited.ser", &runaway, 1024),
+///     Err(SaveSettingsError::FileTooLarge { limit: 1024, .. })
+/// ));
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "limited.ser").unwrap();
+/// ```
+pub fn save_settings_with_limit<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+    max_bytes: u64,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let options = options::SaveOptions::new().max_file_size(max_bytes);
+    options::save_settings_with_options(crate_name, file_name, settings, &options)
+}
+
+/// Saves a serializable settings object to any path, e.g. one the user picked in a file dialog to export their settings.
+/// Unlike the other save functions, the users home, storage mode, and backend are not used, the file is always written
+/// to the filesystem. Missing parent folders are created, a relative path is resolved against the current directory,
+/// and the resolved path is added to `SETTINGS_PATHS`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let export_path = std::env::temp_dir().join("cr_program_settings_doctest_export").join("exported.toml");
+/// save_settings_to_path(&export_path, &Settings{ setting1: 12 }).expect("Unable to export settings");
+///
+/// let imported = load_settings_from_path::<Settings>(&export_path).expect("Unable to import settings");
+/// assert_eq!(imported, Settings{ setting1: 12 });
+///
+/// // the macros take the same arguments
+/// save_settings_at!(Settings{ setting1: 13 }, &export_path).unwrap();
+/// assert_eq!(load_settings_at!(Settings, &export_path).unwrap(), Settings{ setting1: 13 });
+///
+/// std::fs::remove_dir_all(export_path.parent().unwrap()).unwrap();
+/// ```
+pub fn save_settings_to_path<T>(path: &Path, settings: &T) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    if is_read_only() {
+        return Err(SaveSettingsError::ReadOnlyMode);
+    }
+    let serialized_data = serialize_settings(settings)?;
+    let path = std::path::absolute(path).map_err(SaveSettingsError::IOError)?;
+    if path.is_dir() {
+        return Err(SaveSettingsError::PathIsDirectory(path));
+    }
+    let write = || -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            permissions::create_settings_dir(parent)?;
+        }
+        let mut file = permissions::create_settings_file(&path)?;
+        file.write_all(serialized_data.as_bytes())?;
+        if backend::sync_writes() {
+            file.sync_all()?;
+        }
+        Ok(())
+    };
+    let operation = SettingsOperation::start("save");
+    if let Err(err) = write() {
+        log_error!("failed to save settings {}: {:?}", path.display(), err);
+        operation.failed(&path, &err);
+        return Err(SaveSettingsError::IOError(err));
+    }
+    log_info!("saved settings to {}", path.display());
+    operation.succeeded(&path, Some(serialized_data.len()));
+    track_settings_path(path);
+    Ok(())
+}
+
+/// Loads settings from any path, e.g. one the user picked in a file dialog to import their settings.
+/// Unlike the other load functions, the users home, storage mode, and backend are not used.
+/// A relative path is resolved against the current directory, and the resolved path is added to `SETTINGS_PATHS`.
+///
+/// For example usage, see `save_settings_to_path()` documentation.
+pub fn load_settings_from_path<T>(path: &Path) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let path = std::path::absolute(path).map_err(LoadSettingsError::IOError)?;
+    if path.is_dir() {
+        return Err(LoadSettingsError::PathIsDirectory(path));
+    }
+    let operation = SettingsOperation::start("load");
+    match fs::read(&path) {
+        Ok(file_data) => {
+            operation.succeeded(&path, Some(file_data.len()));
+            deserialize_settings(path, file_data)
+        }
+        Err(err) => {
+            log_warn!("failed to read settings {}: {:?}", path.display(), err);
+            operation.failed(&path, &err);
+            Err(LoadSettingsError::IOError(err))
+        }
+    }
+}
+
+/// Serializes a settings object into pretty TOML and writes it to any sink, e.g. a buffer, a network stream, or stdout.
+/// Unlike the other save functions, nothing is written to the users home and the path is not added to `SETTINGS_PATHS`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let mut buffer = Vec::new();
+/// save_settings_to_writer(&mut buffer, &Settings{ setting1: 3 }).expect("Unable to write settings");
+/// assert_eq!(String::from_utf8(buffer).unwrap(), "setting1 = 3\n");
+///
+/// // e.g. print the settings for debugging
+/// save_settings_to_writer(std::io::stdout(), &Settings{ setting1: 3 }).unwrap();
+/// ```
+pub fn save_settings_to_writer<T>(
+    mut writer: impl Write,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let serialized_data = serialize_settings(settings)?;
+    writer
+        .write_all(serialized_data.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(SaveSettingsError::IOError)
+}
+
+/// Checks that settings can be saved, e.g. once at startup, by serializing them in memory exactly as a save would,
+/// without touching the filesystem. Catches values TOML can not represent, such as settings that are not a struct or map,
+/// a `None` outside of a struct field, map keys that are not strings, or a `u64` above `i64::MAX`.
+/// This costs a full serialization pass, so avoid calling it before every save.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::collections::HashMap;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// window_sizes: HashMap<u32, u32>,
+/// }
+///
+/// let settings = Settings{ setting1: 3, window_sizes: HashMap::from([(1, 800)]) };
+/// // TOML keys must be strings
+/// assert!(matches!(validate_serializable(&settings), Err(SaveSettingsError::SerializationError(_))));
+/// assert!(matches!(validate_serializable(&vec![1, 2]), Err(SaveSettingsError::RootNotATable(_))));
+/// ```
+pub fn validate_serializable<T>(settings: &T) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    serialize_settings(settings).map(|_| ())
+}
+
+/// Serializes settings into a pretty toml string, reporting `RootNotATable` when the settings are not a struct or map
+fn serialize_settings<T>(settings: &T) -> Result<String, SaveSettingsError>
+where
+    T: Serialize,
+{
+    serialize_settings_with(settings, &options::TomlOptions::default())
+}
+
+/// Serializes settings into a toml string formatted with `toml_options`, reporting `RootNotATable` when the settings are not a struct or map
+fn serialize_settings_with<T>(
+    settings: &T,
+    toml_options: &options::TomlOptions,
+) -> Result<String, SaveSettingsError>
+where
+    T: Serialize,
+{
+    let serialized = if toml_options.get_multiline_arrays() {
+        toml::to_string_pretty(settings)
+    } else {
+        toml::to_string(settings)
+    };
+    match serialized {
+        Ok(serialized_data) => Ok(serialized_data),
+        Err(err) => match toml::Value::try_from(settings) {
+            Ok(value) if !value.is_table() => Err(SaveSettingsError::RootNotATable(
+                value.type_str().to_string(),
+            )),
+            _ => Err(SaveSettingsError::SerializationError(err)),
+        },
+    }
+}
+
+/// Whether every save and delete is refused, see `set_read_only()`
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables read-only mode, e.g. for a `--dry-run` flag. While enabled, every save returns
+/// `SaveSettingsError::ReadOnlyMode` and every delete or rename returns a `PermissionDenied` io error,
+/// without touching the settings directory, while loads work normally. Disabled by default.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::{is_read_only, set_read_only};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 1 }, "dry_run.ser").unwrap();
+///
+/// set_read_only(true);
+/// assert!(matches!(save_settings!(Settings{ setting1: 2 }, "dry_run.ser"), Err(SaveSettingsError::ReadOnlyMode)));
+/// assert!(delete_settings!("dry_run.ser").is_err());
+/// assert_eq!(load_settings!(Settings, "dry_run.ser").unwrap(), Settings{ setting1: 1 });
+///
+/// set_read_only(false);
+/// assert!(!is_read_only());
+/// delete_settings!("dry_run.ser").unwrap();
+/// ```
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+/// Returns true if read-only mode is enabled with `set_read_only()`
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Returns the error of a delete or rename refused in read-only mode
+fn read_only_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "settings are in read-only mode, see set_read_only()",
+    )
+}
+
+/// Writes already serialized settings data to `relative_dir/file_name` using the active backend
+fn write_settings_bytes(
+    relative_dir: &Path,
+    file_name: &str,
+    data: &[u8],
+) -> Result<(), SaveSettingsError> {
+    write_settings_bytes_with(
+        relative_dir,
+        file_name,
+        data,
+        |backend, relative_dir, file_name, data| backend.write(relative_dir, file_name, data),
+    )
+}
+
+/// Writes already serialized settings data to `relative_dir/file_name` using the given write method of the active backend
+fn write_settings_bytes_with(
+    relative_dir: &Path,
+    file_name: &str,
+    data: &[u8],
+    write: impl FnOnce(&dyn SettingsBackend, &Path, &str, &[u8]) -> Result<(), BackendError>,
+) -> Result<(), SaveSettingsError> {
+    if is_read_only() {
+        log_warn!(
+            "refusing to save settings {} in read-only mode",
+            relative_dir.join(file_name).display()
+        );
+        return Err(SaveSettingsError::ReadOnlyMode);
+    }
+    let backend = get_backend();
+    let operation = SettingsOperation::start("save");
+    if let Err(err) = write(backend.as_ref(), relative_dir, file_name, data) {
+        log_error!(
+            "failed to save settings {}: {:?}",
+            relative_dir.join(file_name).display(),
+            err
+        );
+        operation.failed(
+            &resolve_settings_file(backend.as_ref(), relative_dir, file_name),
+            &err,
+        );
+        return Err(err.into());
+    }
+    match backend.resolve(relative_dir) {
+        Some(settings_path) => {
+            log_info!(
+                "saved settings to {}",
+                settings_path.join(file_name).display()
+            );
+            operation.succeeded(&settings_path.join(file_name), Some(data.len()));
+            track_settings_path(settings_path.join(file_name));
+        }
+        None => operation.succeeded(&relative_dir.join(file_name), Some(data.len())),
+    }
+    Ok(())
+}
+
+/// Returns where the backend stores `relative_dir/file_name`, or the relative path if the backend does not store files at a path
+fn resolve_settings_file(
+    backend: &dyn SettingsBackend,
+    relative_dir: &Path,
+    file_name: &str,
+) -> PathBuf {
+    backend
+        .resolve(relative_dir)
+        .unwrap_or_else(|| relative_dir.to_path_buf())
+        .join(file_name)
+}
+
+/// Adds a settings file path to `SETTINGS_PATHS` if it is not already present
+fn track_settings_path(settings_file_path: PathBuf) {
+    let mut lock = SETTINGS_PATHS.write().unwrap();
+    if !lock.contains(&settings_file_path) {
+        lock.push(settings_file_path);
+    }
+}
+
+/// Saves the settings file given in a directory named using the crate name
+/// Given a struct and a crate name of `my_cool_rust_project`, the program
+/// would save it to `/home/username/my_cool_rust_project/my_cool_rust_project.ser`
+pub fn save_settings<T>(crate_name: &str, settings: &T) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_with_filename(crate_name, format!("{}.ser", crate_name).as_str(), settings)
+}
+
+#[derive(Debug)]
+/// Enum state representing the possible errors that can occur when loading settings
+pub enum LoadSettingsError {
+    /// The library was unable to find the users home directory
+    FailedToGetUserHome,
+    /// In portable mode, the library was unable to find the directory of the executable
+    FailedToGetExecutableDir(Error),
     /// The library encountered an io error while reading the file or accessing the directory
     IOError(Error),
-    /// The library encountered an error while deserializing the settings file
+    /// The library encountered an error while deserializing settings that were not read from a file, e.g. defaults merged with a file
     DeserializationError(toml::de::Error),
+    /// The settings file could not be parsed as TOML or did not match the settings struct.
+    /// Before 0.2.0, these failures were reported as `DeserializationError`
+    ParseError(Box<ParseError>),
+    /// The settings file path exists, but is a directory rather than a file
+    PathIsDirectory(PathBuf),
+    /// The settings file path, or one of its components, is longer than the platform allows
+    PathTooLong(PathBuf),
+    /// The settings storage, e.g. the browsers `localStorage`, is not available
+    StorageUnavailable,
+    /// The settings file was loaded, but failed validation, the file is left untouched
+    ValidationFailed(ValidationError),
+    /// The default settings given to a layered load could not be serialized to be merged with the file
+    DefaultsNotSerializable(toml::ser::Error),
+    /// The profile name is not a safe file name, see `profiles::is_valid_profile_name()`
+    InvalidProfileName(String),
+    /// No profile has been set as the active profile
+    NoActiveProfile,
+    /// The extension of the file name, e.g. `json` without the `json` feature, names a format that is not compiled in
+    FormatNotEnabled(String),
+    /// The dotted key path is empty, or can not be followed through the settings file, the message describes why
+    InvalidKeyPath(String),
+    /// The settings file has no section with this name, see `sections::load_section()`
+    SectionNotFound(String),
+    /// A string value references an environment variable that is not set, see `LoadOptions::expand_env()`
+    UnknownEnvVar(String),
+    /// The `include` key of a settings file is not an array of file names in the settings folder, the message describes why,
+    /// see `LoadOptions::includes()`
+    InvalidInclude(String),
+    /// A settings file includes itself, directly or through other included files.
+    /// The paths are the chain of includes, starting with the loaded file and ending with the file included a second time
+    IncludeCycle(Vec<PathBuf>),
+    /// The settings file is not valid UTF-8, e.g. it was saved as Latin-1 or UTF-16 by a text editor.
+    /// Settings files must be saved as UTF-8, with or without a byte order mark
+    InvalidEncoding {
+        /// The settings file that could not be decoded
+        path: PathBuf,
+        /// Byte offset of the first byte that is not valid UTF-8
+        position: usize,
+    },
+    /// The settings file is larger than the allowed size, it was not read
+    FileTooLarge {
+        /// Size of the settings file in bytes, at least `limit + 1` if the file grew while it was read
+        size: u64,
+        /// The largest allowed size in bytes
+        limit: u64,
+        /// The settings file that was rejected
+        path: PathBuf,
+    },
+    /// Loading kept failing with an io error that is usually temporary, e.g. a file locked by an antivirus scanner,
+    /// and the retries configured in `options::LoadOptions` ran out
+    IOErrorAfterRetries {
+        /// How many times loading was attempted
+        attempts: u32,
+        /// The io error of the last attempt
+        source: Error,
+    },
+    #[cfg(feature = "bincode")]
+    /// The library encountered an error while decoding the settings file using bincode
+    BincodeDecodeError(bincode::Error),
+    #[cfg(feature = "json")]
+    /// The library encountered an error while deserializing the settings file using serde_json
+    JsonDeserializationError(serde_json::Error),
+    #[cfg(feature = "yaml")]
+    /// The library encountered an error while deserializing the settings file using serde_yaml
+    YamlDeserializationError(serde_yaml::Error),
+    #[cfg(feature = "encryption")]
+    /// The settings file is not an encrypted settings file, e.g. it was saved in plaintext
+    NotEncrypted,
+    #[cfg(feature = "encryption")]
+    /// The settings file could not be decrypted, either the key is wrong or the file was modified
+    DecryptionFailed,
+    #[cfg(feature = "keyring")]
+    /// The OS keyring could not provide a secret, e.g. it is locked, unavailable, or the entry is missing
+    KeyringError(keyring::Error),
+    #[cfg(feature = "signing")]
+    /// The signature file of the settings does not exist
+    SignatureMissing,
+    #[cfg(feature = "signing")]
+    /// The settings file does not match its signature, it was modified outside the program or signed with a different key
+    SignatureInvalid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Where a settings file failed to parse, e.g. for a settings editor to highlight the offending line
+pub struct ErrorLocation {
+    /// The line of the error, starting at 1
+    pub line: usize,
+    /// The column of the error within its line in characters, starting at 1
+    pub column: usize,
+    /// Description of the error, without the location
+    pub message: String,
+}
+
+impl ErrorLocation {
+    /// Finds where a TOML error is located in `text`, the text that was parsed
+    fn from_toml_error(err: &toml::de::Error, text: &str) -> Option<ErrorLocation> {
+        // spans do not count the byte order mark skipped while loading
+        let text = text.strip_prefix(UTF8_BOM).unwrap_or(text);
+        let start = err.span()?.start.min(text.len());
+        let before = text.get(..start)?;
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Some(ErrorLocation {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: err.message().trim_end().to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A settings file that failed to parse, with the path of the file and the line the error is on
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("parse_error.ser");
+/// std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+/// std::fs::write(&path, "# hand edited\nsetting1 = \"eleven\"\n").unwrap();
+///
+/// match load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "parse_error.ser") {
+///     Err(LoadSettingsError::ParseError(err)) => {
+///         assert_eq!(err.path(), path);
+///         assert_eq!(err.line(), Some(2));
+///         assert_eq!(err.line_text(), Some("setting1 = \"eleven\""));
+///         assert!(err.to_string().starts_with(&format!("failed to parse {} at line 2: ", path.display())));
+///     }
+///     other => panic!("expected a parse error, got {:?}", other),
+/// }
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "parse_error.ser").unwrap();
+/// ```
+pub struct ParseError {
+    /// The settings file that failed to parse
+    path: PathBuf,
+    /// Where in the file the error is, if the error has a location
+    location: Option<ErrorLocation>,
+    /// The text of the line the error is on
+    line_text: Option<String>,
+    /// The error reported by `toml`
+    source: toml::de::Error,
+}
+
+impl ParseError {
+    /// Creates a parse error for the file at `path`, `text` is the text that was parsed
+    pub(crate) fn new(path: PathBuf, text: &str, source: toml::de::Error) -> Self {
+        let location = ErrorLocation::from_toml_error(&source, text);
+        let line_text = location.as_ref().and_then(|location| {
+            text.strip_prefix(UTF8_BOM)
+                .unwrap_or(text)
+                .lines()
+                .nth(location.line - 1)
+                .map(str::to_string)
+        });
+        Self {
+            path,
+            location,
+            line_text,
+            source,
+        }
+    }
+
+    /// Returns the path of the settings file that failed to parse
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the line of the error, starting at 1
+    pub fn line(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.line)
+    }
+
+    /// Returns the column of the error within its line in characters, starting at 1
+    pub fn column(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.column)
+    }
+
+    /// Returns the text of the line the error is on
+    pub fn line_text(&self) -> Option<&str> {
+        self.line_text.as_deref()
+    }
+
+    /// Returns the description of the error, without the path or location
+    pub fn message(&self) -> &str {
+        self.source.message().trim_end()
+    }
+
+    /// Returns the error reported by `toml`
+    pub fn toml_error(&self) -> &toml::de::Error {
+        &self.source
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line() {
+            Some(line) => write!(
+                f,
+                "failed to parse {} at line {}: {}",
+                self.path.display(),
+                line,
+                self.message()
+            ),
+            None => write!(
+                f,
+                "failed to parse {}: {}",
+                self.path.display(),
+                self.message()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl LoadSettingsError {
+    /// Returns true if the settings file does not exist, e.g. on the first run of a program
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::prelude::*;
+    ///
+    /// let err = load_settings_with_filename::<u32>(env!("CARGO_CRATE_NAME"), "not_a_settings_file.ser").unwrap_err();
+    /// assert!(err.is_not_found());
+    /// assert!(!err.is_corrupt());
+    /// ```
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            LoadSettingsError::IOError(err)
+            | LoadSettingsError::IOErrorAfterRetries { source: err, .. } => {
+                err.kind() == std::io::ErrorKind::NotFound
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if the settings file exists but its contents could not be decoded, i.e. it is corrupt or was edited into an invalid state
+    pub fn is_corrupt(&self) -> bool {
+        match self {
+            LoadSettingsError::DeserializationError(_)
+            | LoadSettingsError::ParseError(_)
+            | LoadSettingsError::InvalidEncoding { .. } => true,
+            #[cfg(feature = "bincode")]
+            LoadSettingsError::BincodeDecodeError(_) => true,
+            #[cfg(feature = "json")]
+            LoadSettingsError::JsonDeserializationError(_) => true,
+            #[cfg(feature = "yaml")]
+            LoadSettingsError::YamlDeserializationError(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the byte range of the settings file that failed to parse, if this is a TOML `ParseError` or `DeserializationError` that has one
+    pub fn span(&self) -> Option<std::ops::Range<usize>> {
+        match self {
+            LoadSettingsError::DeserializationError(err) => err.span(),
+            LoadSettingsError::ParseError(err) => err.toml_error().span(),
+            _ => None,
+        }
+    }
+
+    /// Returns the line and column where the settings file failed to parse, along with the error message.
+    /// `file_contents` must be the contents of the file that was loaded, as TOML errors only store a byte range of it.
+    /// Returns `None` for errors that are not parse errors, or parse errors without a location.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use serde::{Deserialize, Serialize};
+    /// use cr_program_settings::prelude::*;
+    ///
+    /// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+    /// struct Settings{
+    /// setting1: u32,
+    /// }
+    ///
+    /// let file_contents = "# hand edited\nsetting1 = \"eleven\"\n";
+    /// let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("hand_edited.ser");
+    /// std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    /// std::fs::write(&path, file_contents).unwrap();
+    ///
+    /// let err = load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "hand_edited.ser").unwrap_err();
+    /// let location = err.location(file_contents).expect("parse errors have a location");
+    /// assert_eq!((location.line, location.column), (2, 12));
+    ///
+    /// delete_setting_file(env!("CARGO_CRATE_NAME"), "hand_edited.ser").unwrap();
+    /// ```
+    pub fn location(&self, file_contents: &str) -> Option<ErrorLocation> {
+        match self {
+            LoadSettingsError::DeserializationError(err) => {
+                ErrorLocation::from_toml_error(err, file_contents)
+            }
+            LoadSettingsError::ParseError(err) => {
+                ErrorLocation::from_toml_error(err.toml_error(), file_contents)
+            }
+            #[cfg(feature = "json")]
+            LoadSettingsError::JsonDeserializationError(err) if err.line() > 0 => {
+                let message = err.to_string();
+                let suffix = format!(" at line {} column {}", err.line(), err.column());
+                Some(ErrorLocation {
+                    line: err.line(),
+                    column: err.column(),
+                    message: message
+                        .strip_suffix(&suffix)
+                        .unwrap_or(&message)
+                        .to_string(),
+                })
+            }
+            #[cfg(feature = "yaml")]
+            LoadSettingsError::YamlDeserializationError(err) => {
+                let location = err.location()?;
+                let message = err.to_string();
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                Some(ErrorLocation {
+                    line: location.line(),
+                    column: location.column(),
+                    message: message
+                        .split_once(&suffix)
+                        .map_or(message.as_str(), |(message, _)| message)
+                        .to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Enum state representing the errors of operations that both load and save settings
+pub enum SettingsError {
+    /// An error occurred while loading settings
+    Load(LoadSettingsError),
+    /// An error occurred while saving settings
+    Save(SaveSettingsError),
+    /// An io error occurred while managing settings files
+    IOError(Error),
+}
+
+impl From<LoadSettingsError> for SettingsError {
+    fn from(err: LoadSettingsError) -> Self {
+        SettingsError::Load(err)
+    }
+}
+
+impl From<SaveSettingsError> for SettingsError {
+    fn from(err: SaveSettingsError) -> Self {
+        SettingsError::Save(err)
+    }
+}
+
+impl From<Error> for SettingsError {
+    fn from(err: Error) -> Self {
+        SettingsError::IOError(err)
+    }
+}
+
+/// Loads a settings serialized file from `USER_HOME/crate_name/file_name`.
+/// The names are accepted in the same forms as `save_settings_with_filename()`.
+/// Files larger than `max_settings_file_size()`, 64 MiB by default, are rejected with `LoadSettingsError::FileTooLarge` without being read.
+pub fn load_settings_with_filename<T>(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_in_dir(crate_name.as_ref(), file_name.as_ref())
+}
+
+/// Loads a settings serialized file from `USER_HOME/relative_dir/file_name`, where `relative_dir` can be
+/// more than one folder deep, e.g. `CompanyName/AppName`.
+/// Like `save_settings_in_dir()`, a `relative_dir` or `file_name` that could point outside the settings root is rejected with an `InvalidInput` io error.
+///
+/// For example usage, see `save_settings_in_dir()` documentation.
+pub fn load_settings_in_dir<T>(relative_dir: &Path, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    options::load_settings_with_options(relative_dir, file_name, &options::LoadOptions::default())
+}
+
+/// Returns an `InvalidInput` io error unless `relative_dir` is a non-empty path made only of folder names, e.g. `CompanyName/AppName`,
+/// so it can not point outside the settings root with an absolute path, `.`, or `..`, or be the settings root itself
+fn check_relative_dir(relative_dir: &Path) -> io::Result<()> {
+    let is_plain = relative_dir
+        .components()
+        .all(|component| matches!(component, std::path::Component::Normal(_)));
+    if is_plain && relative_dir.components().next().is_some() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{:?} is not a relative settings folder",
+                relative_dir.display().to_string()
+            ),
+        ))
+    }
+}
+
+/// Returns an `InvalidInput` io error unless `file_name` names a file directly inside its settings folder,
+/// e.g. not `../other.ser` or an absolute path
+fn check_file_name(file_name: &str) -> io::Result<()> {
+    let mut components = Path::new(file_name).components();
+    match (components.next(), components.next()) {
+        (Some(std::path::Component::Normal(_)), None) => Ok(()),
+        _ => Err(Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{:?} is not a settings file name", file_name),
+        )),
+    }
+}
+
+/// Returns the path of `file_name` in the settings folder `relative_dir`, after checking both names with
+/// `check_relative_dir()` and `check_file_name()`
+fn get_settings_file_path(relative_dir: &Path, file_name: &str) -> Result<PathBuf, BackendError> {
+    check_file_name(file_name).map_err(BackendError::IOError)?;
+    get_settings_dir(relative_dir).map(|settings_path| settings_path.join(file_name))
+}
+
+/// The largest settings file loaded unless configured otherwise, 64 MiB
+pub const DEFAULT_MAX_SETTINGS_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The largest settings file every load accepts, `None` means `DEFAULT_MAX_SETTINGS_FILE_SIZE`
+static MAX_SETTINGS_FILE_SIZE: RwLock<Option<u64>> = RwLock::new(None);
+
+/// Sets the size in bytes above which every load rejects a settings file with `LoadSettingsError::FileTooLarge`
+/// without reading it, `DEFAULT_MAX_SETTINGS_FILE_SIZE` unless set. This keeps a corrupted or swapped file
+/// of several gigabytes from being read into memory. A single load can use another limit with `options::LoadOptions`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::{reset_max_settings_file_size, set_max_settings_file_size};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: String,
+/// }
+///
+/// save_settings_with_filename(env!("CARGO_CRATE_NAME"), "limited_globally.ser", &Settings{ setting1: "x".repeat(100) }).unwrap();
+///
+/// set_max_settings_file_size(16);
+/// assert!(matches!(
+///     load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "limited_globally.ser"),
+///     Err(LoadSettingsError::FileTooLarge { limit: 16, .. })
+/// ));
+///
+/// reset_max_settings_file_size();
+/// assert!(load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "limited_globally.ser").is_ok());
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "limited_globally.ser").unwrap();
+/// ```
+pub fn set_max_settings_file_size(max_bytes: u64) {
+    *MAX_SETTINGS_FILE_SIZE.write().unwrap() = Some(max_bytes);
+}
+
+/// Restores the limit of `set_max_settings_file_size()` to `DEFAULT_MAX_SETTINGS_FILE_SIZE`
+pub fn reset_max_settings_file_size() {
+    *MAX_SETTINGS_FILE_SIZE.write().unwrap() = None;
+}
+
+/// Returns the size in bytes above which loads reject a settings file
+pub fn max_settings_file_size() -> u64 {
+    MAX_SETTINGS_FILE_SIZE
+        .read()
+        .unwrap()
+        .unwrap_or(DEFAULT_MAX_SETTINGS_FILE_SIZE)
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name` only if the file is at most `max_bytes` large,
+/// otherwise returns `LoadSettingsError::FileTooLarge` without reading it, e.g. for files that may have been replaced by untrusted data.
+///
+/// For example usage, see `save_settings_with_limit()` documentation.
+pub fn load_settings_with_limit<T>(
+    crate_name: &str,
+    file_name: &str,
+    max_bytes: u64,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    options::load_settings_with_options(
+        crate_name,
+        file_name,
+        &options::LoadOptions::new().max_file_size(max_bytes),
+    )
+}
+
+/// Loads the settings file at `USER_HOME/crate_name/file_name` as a `toml::Value`, without a concrete settings type,
+/// e.g. for showing the user what is in a file that no longer matches the settings struct.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 7 }, "raw.ser").unwrap();
+///
+/// let mut value = load_settings_raw(env!("CARGO_CRATE_NAME"), "raw.ser").unwrap();
+/// assert_eq!(value["setting1"].as_integer(), Some(7));
+/// assert_eq!(read_settings_string(env!("CARGO_CRATE_NAME"), "raw.ser").unwrap(), "setting1 = 7\n");
+///
+/// value.as_table_mut().unwrap().insert("setting2".to_string(), toml::Value::Boolean(true));
+/// save_settings_raw(env!("CARGO_CRATE_NAME"), "raw.ser", &value).unwrap();
+/// assert_eq!(read_settings_string(env!("CARGO_CRATE_NAME"), "raw.ser").unwrap(), "setting1 = 7\nsetting2 = true\n");
+/// ```
+pub fn load_settings_raw(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<toml::Value, LoadSettingsError> {
+    load_settings_with_filename(crate_name, file_name)
+}
+
+/// Saves a `toml::Value` to `USER_HOME/crate_name/file_name`, the value must be a table.
+///
+/// For example usage, see `load_settings_raw()` documentation.
+pub fn save_settings_raw(
+    crate_name: &str,
+    file_name: &str,
+    value: &toml::Value,
+) -> Result<(), SaveSettingsError> {
+    save_settings_with_filename(crate_name, file_name, value)
+}
+
+/// Parses the settings file at `USER_HOME/crate_name/file_name` into a `toml::Value` tree, for tools that edit settings
+/// without knowing their struct, e.g. a config editor showing arbitrary keys. This is `load_settings_raw()` under the name
+/// used alongside `save_settings_value()`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::prelude::*;
+///
+/// let crate_name = "cr_program_settings_doctest_value";
+/// let mut value = toml::Value::Table(toml::value::Table::new());
+/// value.as_table_mut().unwrap().insert("volume".to_string(), toml::Value::Integer(40));
+/// save_settings_value(crate_name, "editor.toml", &value).unwrap();
+///
+/// let mut loaded = load_settings_value(crate_name, "editor.toml").unwrap();
+/// assert_eq!(loaded["volume"].as_integer(), Some(40));
+///
+/// // edit in place and write it back
+/// loaded.as_table_mut().unwrap().insert("muted".to_string(), toml::Value::Boolean(true));
+/// save_settings_value(crate_name, "editor.toml", &loaded).unwrap();
+/// assert_eq!(load_settings_value(crate_name, "editor.toml").unwrap()["muted"].as_bool(), Some(true));
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn load_settings_value(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<toml::Value, LoadSettingsError> {
+    load_settings_raw(crate_name, file_name)
+}
+
+/// Writes a `toml::Value` tree to `USER_HOME/crate_name/file_name`, the value must be a table.
+/// This is `save_settings_raw()` under the name used alongside `load_settings_value()`.
+///
+/// For example usage, see `load_settings_value()` documentation.
+pub fn save_settings_value(
+    crate_name: &str,
+    file_name: &str,
+    value: &toml::Value,
+) -> Result<(), SaveSettingsError> {
+    save_settings_raw(crate_name, file_name, value)
+}
+
+/// Reads the unparsed text of the settings file at `USER_HOME/crate_name/file_name`, without a leading byte order mark,
+/// a file that is not valid UTF-8 returns `LoadSettingsError::InvalidEncoding`.
+///
+/// For example usage, see `load_settings_raw()` documentation.
+pub fn read_settings_string(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<String, LoadSettingsError> {
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let text = decode_settings_text(&settings_file_path, &file_data)?.to_string();
+    track_settings_path(settings_file_path);
+    Ok(text)
+}
+
+/// Deserializes settings from a buffer the caller keeps alive, usually filled by `read_settings_string()`,
+/// so `T` can hold fields that borrow from the buffer, e.g. `Cow<'a, str>` marked `#[serde(borrow)]`,
+/// and one buffer can be parsed into several settings structs.
+///
+/// The TOML parser unescapes every string it reads, so `Cow` fields always come back `Cow::Owned`
+/// and plain `&'a str` fields fail to load, use `Cow` to stay compatible with any input.
+/// Formats that can hand out slices of the buffer, e.g. JSON, borrow without copying through `Format::deserialize_borrowed()`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::borrow::Cow;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings<'a>{
+/// #[serde(borrow)]
+/// motd: Cow<'a, str>,
+/// }
+///
+/// save_settings!(Settings{ motd: Cow::Borrowed("hello") }, "borrowed.ser").unwrap();
+///
+/// let buffer = read_settings_string(env!("CARGO_CRATE_NAME"), "borrowed.ser").unwrap();
+/// let settings = load_settings_borrowed::<Settings>(&buffer).unwrap();
+/// assert_eq!(settings.motd, "hello");
+/// ```
+pub fn load_settings_borrowed<'a, T>(buffer: &'a str) -> Result<T, LoadSettingsError>
+where
+    T: Deserialize<'a>,
+{
+    T::deserialize(toml::Deserializer::new(buffer)).map_err(LoadSettingsError::DeserializationError)
 }
 
-/// Loads a settings serialized file from `USER_HOME/crate_name/file_name`
-pub fn load_settings_with_filename<T>(
-    crate_name: &str,
-    file_name: &str,
+/// Deserializes toml settings data that was read from `settings_file_path`, adding the path to `SETTINGS_PATHS` on success
+fn deserialize_settings<T>(
+    settings_file_path: PathBuf,
+    file_data: Vec<u8>,
 ) -> Result<T, LoadSettingsError>
 where
     for<'a> T: Deserialize<'a>,
 {
-    match get_user_home() {
-        None => Err(LoadSettingsError::FailedToGetUserHome),
-        Some(home_dir) => {
-            let settings_path = home_dir.join(PathBuf::from(crate_name));
-            let settings_file_path = settings_path.join(PathBuf::from(file_name));
-            match File::open(&settings_file_path) {
-                Ok(mut file) => {
-                    let mut file_data = String::new();
-                    match file.read_to_string(&mut file_data) {
-                        Ok(_) => match toml::from_str::<T>(&file_data) {
-                            Ok(thing) => {
-                                {
-                                    let mut lock = SETTINGS_PATHS.write().unwrap();
-                                    if !lock.contains(&settings_file_path) {
-                                        lock.push(settings_file_path);
-                                    }
-                                }
-                                Ok(thing)
-                            }
-                            Err(err) => Err(DeserializationError(err)),
-                        },
-                        Err(err) => Err(IOError(err)),
+    let parsed = decode_settings_text(&settings_file_path, &file_data).and_then(|text| {
+        paths::with_settings_file_dir(&settings_file_path, || toml::from_str::<T>(text)).map_err(
+            |err| {
+                LoadSettingsError::ParseError(Box::new(ParseError::new(
+                    settings_file_path.clone(),
+                    text,
+                    err,
+                )))
+            },
+        )
+    });
+    match parsed {
+        Ok(settings) => {
+            log_info!("loaded settings from {}", settings_file_path.display());
+            track_settings_path(settings_file_path);
+            Ok(settings)
+        }
+        Err(err) => {
+            log_warn!(
+                "failed to parse settings {}: {:?}",
+                settings_file_path.display(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+/// The byte order mark some editors, e.g. Notepad, put at the start of UTF-8 files
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Decodes the text of the settings file read from `settings_file_path`, skipping a leading byte order mark
+fn decode_settings_text<'a>(
+    settings_file_path: &Path,
+    file_data: &'a [u8],
+) -> Result<&'a str, LoadSettingsError> {
+    let (bom_len, text) = match file_data.strip_prefix(UTF8_BOM.as_bytes()) {
+        Some(text) => (UTF8_BOM.len(), text),
+        None => (0, file_data),
+    };
+    std::str::from_utf8(text).map_err(|err| LoadSettingsError::InvalidEncoding {
+        path: settings_file_path.to_path_buf(),
+        position: bom_len + err.valid_up_to(),
+    })
+}
+
+/// Returns the directory `USER_HOME/relative_dir` that settings are stored in, or `EXECUTABLE_DIR/relative_dir` in portable mode.
+/// Within `testing::with_temp_settings_dir` or while a `testing::TempSettingsDir` exists, the temporary directory is always used.
+/// A `relative_dir` that is empty, absolute, or contains `.` or `..` is rejected with an `InvalidInput` io error.
+fn get_settings_dir(relative_dir: &Path) -> Result<PathBuf, BackendError> {
+    check_relative_dir(relative_dir).map_err(BackendError::IOError)?;
+    let home_settings_dir = || match get_user_home() {
+        None => Err(BackendError::FailedToGetUserHome),
+        Some(home_dir) => Ok(home_dir.join(relative_dir)),
+    };
+    let settings_dir = if get_home_override().is_some() {
+        home_settings_dir()
+    } else {
+        match portable::get_storage_mode() {
+            portable::StorageMode::Home => home_settings_dir(),
+            portable::StorageMode::Portable => match portable::executable_dir() {
+                Ok(executable_dir) => Ok(executable_dir.join(relative_dir)),
+                Err(err) => Err(BackendError::FailedToGetExecutableDir(err)),
+            },
+            portable::StorageMode::Auto => match portable::executable_dir() {
+                Ok(executable_dir) => {
+                    portable::set_storage_warning(None);
+                    if portable::has_portable_flag(&executable_dir) {
+                        Ok(executable_dir.join(relative_dir))
+                    } else {
+                        home_settings_dir()
                     }
                 }
-                Err(err) => Err(IOError(err)),
-            }
+                Err(err) => {
+                    let warning = format!(
+                        "unable to find the executable directory, storing settings in the users home: {}",
+                        err
+                    );
+                    log_warn!("{}", warning);
+                    portable::set_storage_warning(Some(warning));
+                    home_settings_dir()
+                }
+            },
         }
-    }
+    };
+    log_debug!(
+        "resolved settings directory {}: {:?}",
+        relative_dir.display(),
+        settings_dir
+    );
+    settings_dir
+}
+
+/// Reads the raw bytes of `relative_dir/file_name` using the active backend, returning them along with the path they were read from.
+/// Files larger than `max_settings_file_size()` are rejected with `FileTooLarge` without being read.
+/// The path is not added to `SETTINGS_PATHS` until the caller successfully deserializes the data.
+fn read_settings_bytes(
+    relative_dir: &Path,
+    file_name: &str,
+) -> Result<(PathBuf, Vec<u8>), LoadSettingsError> {
+    read_settings_bytes_limited(relative_dir, file_name, max_settings_file_size())
+}
+
+/// Reads `relative_dir/file_name` like `read_settings_bytes()`, but fails with `FileTooLarge` instead of reading more than `limit` bytes.
+/// The size is looked up before the file is opened, e.g. with `fs::metadata`, so an oversized file is never allocated
+fn read_settings_bytes_limited(
+    relative_dir: &Path,
+    file_name: &str,
+    limit: u64,
+) -> Result<(PathBuf, Vec<u8>), LoadSettingsError> {
+    let backend = get_backend();
+    let operation = SettingsOperation::start("load");
+    let settings_file_path = resolve_settings_file(backend.as_ref(), relative_dir, file_name);
+    let too_large = |size: u64| LoadSettingsError::FileTooLarge {
+        size,
+        limit,
+        path: settings_file_path.clone(),
+    };
+    let read = || {
+        let size = backend.file_size(relative_dir, file_name)?;
+        if size > limit {
+            return Err(too_large(size));
+        }
+        let reader = backend.reader(relative_dir, file_name)?;
+        // the file may have grown since its size was checked, so never read past the limit
+        let mut file_data = Vec::with_capacity(usize::try_from(size).unwrap_or(0));
+        reader
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut file_data)
+            .map_err(LoadSettingsError::IOError)?;
+        if file_data.len() as u64 > limit {
+            return Err(too_large(file_data.len() as u64));
+        }
+        Ok(file_data)
+    };
+    let file_data = match read() {
+        Ok(file_data) => file_data,
+        Err(err) => {
+            log_warn!(
+                "failed to read settings {}: {:?}",
+                relative_dir.join(file_name).display(),
+                err
+            );
+            operation.failed(&settings_file_path, &err);
+            return Err(err);
+        }
+    };
+    operation.succeeded(&settings_file_path, Some(file_data.len()));
+    log_debug!(
+        "read {} bytes from {}",
+        file_data.len(),
+        settings_file_path.display()
+    );
+    Ok((settings_file_path, file_data))
+}
+
+/// Opens `relative_dir/file_name` for reading through the active backend, returning the resolved path alongside the reader
+fn open_settings_reader(
+    relative_dir: &Path,
+    file_name: &str,
+) -> Result<(PathBuf, Box<dyn io::BufRead>), LoadSettingsError> {
+    let backend = get_backend();
+    let operation = SettingsOperation::start("open");
+    let settings_file_path = resolve_settings_file(backend.as_ref(), relative_dir, file_name);
+    let reader = match backend.reader(relative_dir, file_name) {
+        Ok(reader) => reader,
+        Err(err) => {
+            log_warn!(
+                "failed to read settings {}: {:?}",
+                relative_dir.join(file_name).display(),
+                err
+            );
+            operation.failed(&settings_file_path, &err);
+            return Err(err.into());
+        }
+    };
+    operation.succeeded(&settings_file_path, None);
+    log_debug!("reading settings from {}", settings_file_path.display());
+    Ok((settings_file_path, reader))
 }
 
 /// Loads a given settings file from the home directory and the given crate name.
@@ -262,24 +2246,479 @@ where
     load_settings_with_filename(crate_name, format!("{}.ser", crate_name).as_str())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// What `load_settings_or_default()` does when the settings file exists but can not be loaded, e.g. it is corrupt
+pub enum CorruptPolicy {
+    /// Panic with the load error, so a corrupt file is noticed rather than replaced by defaults the next time settings are saved
+    #[default]
+    Panic,
+    /// Return the default settings, logging the load error with the `logging` feature
+    Default,
+    /// Return the default settings like `Default`, first moving a corrupt file out of the way with `quarantine_settings_file()`
+    /// so the user's data is not lost the next time settings are saved
+    Quarantine,
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, returning `T::default()` if the file does not exist,
+/// and following `on_corrupt` if the file exists but can not be loaded.
+///
+/// For example usage, see `load_settings_or_default!()` documentation.
+pub fn load_settings_or_default<T>(
+    crate_name: &str,
+    file_name: &str,
+    on_corrupt: CorruptPolicy,
+) -> T
+where
+    for<'a> T: Deserialize<'a> + Default,
+{
+    match load_settings_with_filename(crate_name, file_name) {
+        Ok(settings) => settings,
+        Err(err) if err.is_not_found() => T::default(),
+        Err(err) => match on_corrupt {
+            CorruptPolicy::Panic => panic!(
+                "unable to load settings {}/{}: {:?}",
+                crate_name, file_name, err
+            ),
+            CorruptPolicy::Default => {
+                log_warn!(
+                    "using default settings, unable to load {}/{}: {:?}",
+                    crate_name,
+                    file_name,
+                    err
+                );
+                T::default()
+            }
+            CorruptPolicy::Quarantine => {
+                log_warn!(
+                    "using default settings, unable to load {}/{}: {:?}",
+                    crate_name,
+                    file_name,
+                    err
+                );
+                if err.is_corrupt() {
+                    if let Err(_err) = quarantine_settings_file(crate_name, file_name) {
+                        log_error!(
+                            "failed to quarantine settings {}/{}: {:?}",
+                            crate_name,
+                            file_name,
+                            _err
+                        );
+                    }
+                }
+                T::default()
+            }
+        },
+    }
+}
+
+/// Loads settings with `load_settings()`, returning `fallback` if they can not be loaded for any reason,
+/// for settings types whose default is computed rather than given by a `Default` implementation.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// threads: usize,
+/// }
+///
+/// let crate_name = "cr_program_settings_doctest_load_or";
+/// let fallback = Settings{ threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()) };
+/// let settings = load_settings_or(crate_name, fallback);
+/// assert!(settings.threads >= 1);
+/// ```
+pub fn load_settings_or<T>(crate_name: &str, fallback: T) -> T
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_or_with_filename(crate_name, &format!("{}.ser", crate_name), fallback)
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, returning `fallback` if they can not be loaded for any reason,
+/// logging the load error with the `logging` feature.
+///
+/// For example usage, see `load_settings_or()` documentation.
+pub fn load_settings_or_with_filename<T>(crate_name: &str, file_name: &str, fallback: T) -> T
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match load_settings_with_filename(crate_name, file_name) {
+        Ok(settings) => settings,
+        Err(_err) => {
+            log_warn!(
+                "using fallback settings, unable to load {}/{}: {:?}",
+                crate_name,
+                file_name,
+                _err
+            );
+            fallback
+        }
+    }
+}
+
+/// Lists every settings file directly inside `USER_HOME/crate_name`, sorted by path.
+/// Subdirectories are not included.
+pub fn list_settings_files(crate_name: &str) -> io::Result<Vec<PathBuf>> {
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let files = backend.list(folder)?;
+    let settings_path = backend
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf());
+    Ok(files
+        .into_iter()
+        .map(|file_name| settings_path.join(file_name))
+        .collect())
+}
+
+/// Returns when the settings file `USER_HOME/crate_name/file_name` was last modified, without reading it,
+/// e.g. to cheaply check whether the file changed since it was last loaded.
+/// Returns a `NotFound` error if the file does not exist, and `Unsupported` if the backend does not record modification times.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 1 }, "modified.ser").unwrap();
+/// let loaded_at = settings_modified_time(env!("CARGO_CRATE_NAME"), "modified.ser").unwrap();
+///
+/// // nothing changed, so there is no need to load the file again
+/// assert_eq!(settings_modified_time(env!("CARGO_CRATE_NAME"), "modified.ser").unwrap(), loaded_at);
+///
+/// let err = settings_modified_time(env!("CARGO_CRATE_NAME"), "never_saved.ser").unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+/// ```
+pub fn settings_modified_time(crate_name: &str, file_name: &str) -> io::Result<SystemTime> {
+    Ok(get_backend().modified(Path::new(crate_name), file_name)?)
+}
+
+/// Attempts to load every settings file in `USER_HOME/crate_name` as `T`, returning the result for each file.
+/// Files that fail to load appear as `Err` entries rather than stopping the whole operation.
+/// If the directory itself cannot be read, an empty list is returned.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Profile{
+/// name: String,
+/// }
+///
+/// let crate_name = "cr_program_settings_load_all";
+/// save_settings_with_filename(crate_name, "work.ser", &Profile{ name: "work".to_string() }).unwrap();
+/// save_settings_with_filename(crate_name, "home.ser", &Profile{ name: "home".to_string() }).unwrap();
+/// save_settings_with_filename(crate_name, "broken.ser", &vec![("not", "a profile")].into_iter().collect::<std::collections::HashMap<_, _>>()).unwrap();
+///
+/// let profiles = load_all_settings::<Profile>(crate_name);
+/// assert_eq!(profiles.len(), 3);
+/// assert_eq!(profiles.iter().filter(|(_, profile)| profile.is_ok()).count(), 2);
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn load_all_settings<T>(crate_name: &str) -> Vec<(PathBuf, Result<T, LoadSettingsError>)>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match get_backend().list(Path::new(crate_name)) {
+        Ok(files) => files
+            .into_iter()
+            .map(
+                |file_name| match read_settings_bytes(Path::new(crate_name), &file_name) {
+                    Ok((settings_file_path, file_data)) => (
+                        settings_file_path.clone(),
+                        deserialize_settings(settings_file_path, file_data),
+                    ),
+                    Err(err) => (Path::new(crate_name).join(file_name), Err(err)),
+                },
+            )
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Loads every settings file in `USER_HOME/crate_name` as `T`, applies `f` to it, and saves it again, returning the result for each file.
+/// Files that fail to load are skipped and reported as `Err` entries, so one broken file does not stop the others from being updated,
+/// e.g. to scrub a leaked token from every profile. Subfolders are not included, pass e.g. `my_app/profiles` to update the files in a subfolder.
+/// If the directory itself cannot be read, an empty list is returned.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Profile{
+/// name: String,
+/// token: Option<String>,
+/// }
+///
+/// let crate_name = "cr_program_settings_update_all";
+/// save_settings_with_filename(crate_name, "work.ser", &Profile{ name: "work".to_string(), token: Some("leaked".to_string()) }).unwrap();
+/// save_settings_with_filename(crate_name, "home.ser", &Profile{ name: "home".to_string(), token: None }).unwrap();
+///
+/// let results = update_all_settings::<Profile>(crate_name, |profile| profile.token = None);
+/// assert!(results.iter().all(|(_, result)| result.is_ok()));
+///
+/// assert_eq!(load_settings_with_filename::<Profile>(crate_name, "work.ser").unwrap().token, None);
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn update_all_settings<T>(
+    crate_name: &str,
+    mut f: impl FnMut(&mut T),
+) -> Vec<(PathBuf, Result<(), SettingsError>)>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    let folder = Path::new(crate_name);
+    let files = match get_backend().list(folder) {
+        Ok(files) => files,
+        Err(_) => return vec![],
+    };
+    files
+        .into_iter()
+        .map(|file_name| {
+            let settings_file_path =
+                resolve_settings_file(get_backend().as_ref(), folder, &file_name);
+            let load_options = options::LoadOptions::default();
+            let mut settings =
+                match options::load_settings_with_options::<T>(folder, &file_name, &load_options) {
+                    Ok(settings) => settings,
+                    Err(err) => return (settings_file_path, Err(err.into())),
+                };
+            f(&mut settings);
+            let save_options = options::SaveOptions::default();
+            let result =
+                options::save_settings_with_options(folder, &file_name, &settings, &save_options)
+                    .map_err(SettingsError::from);
+            (settings_file_path, result)
+        })
+        .collect()
+}
+
 /// Deletes the settings directory found in the `<user home>/crate_name`
-/// e.g. `/home/username/my_cool_project`
+/// e.g. `/home/username/my_cool_project`. If the directory is a symlink, only the link is removed
+#[deprecated(
+    since = "0.2.0",
+    note = "deletes every file in the folder, use `delete_settings_folder()` for the same behavior, or `delete_default_settings_file()` to delete only `crate_name.ser`"
+)]
 pub fn delete_settings(crate_name: &str) -> io::Result<()> {
-    let home_dir = get_user_home().unwrap();
-    let settings_path = home_dir.join(PathBuf::from(crate_name));
-    fs::remove_dir_all(&settings_path)?;
-    SETTINGS_PATHS
-        .write()
-        .unwrap()
-        .retain(|path| match path.parent() {
-            None => true,
-            Some(parent) => parent != settings_path,
-        });
+    delete_settings_folder(crate_name)
+}
+
+/// Deletes the settings file saved by `save_settings()`, `USER_HOME/crate_name/crate_name.ser`,
+/// leaving any other files in the folder, e.g. profiles or backups, untouched
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_doctest_delete_default";
+/// save_settings(crate_name, &Settings{ setting1: 1 }).unwrap();
+/// save_settings_with_filename(crate_name, "backup.ser", &Settings{ setting1: 1 }).unwrap();
+///
+/// delete_default_settings_file(crate_name).unwrap();
+/// assert!(load_settings::<Settings>(crate_name).is_err());
+/// assert!(load_settings_with_filename::<Settings>(crate_name, "backup.ser").is_ok());
+///
+/// // deleting the folder deletes every file in it
+/// delete_settings_folder(crate_name).unwrap();
+/// assert!(load_settings_with_filename::<Settings>(crate_name, "backup.ser").is_err());
+/// ```
+pub fn delete_default_settings_file(crate_name: &str) -> io::Result<()> {
+    delete_setting_file(crate_name, format!("{}.ser", crate_name))
+}
+
+/// Deletes the settings directory found in the `<user home>/crate_name` along with every file inside it,
+/// e.g. `/home/username/my_cool_project`
+///
+/// Saving and loading follow symlinks, so `<user home>/crate_name` can be a link to a folder elsewhere.
+/// If it is a link, only the link is removed and the folder it points to is left untouched,
+/// use `delete_settings_folder_with_symlinks()` to delete the linked folder as well.
+pub fn delete_settings_folder(crate_name: &str) -> io::Result<()> {
+    delete_settings_folder_with_symlinks(crate_name, false)
+}
+
+/// Deletes the settings directory `<user home>/crate_name` like `delete_settings_folder()`.
+/// If the directory is a symlink and `follow_symlinks` is true, the folder it points to is deleted along with every file inside it,
+/// before the link itself is removed. If `follow_symlinks` is false, only the link is removed.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_doctest_delete_symlinks";
+/// save_settings(crate_name, &Settings{ setting1: 1 }).unwrap();
+///
+/// // a regular folder is deleted the same way either way
+/// delete_settings_folder_with_symlinks(crate_name, true).unwrap();
+/// assert!(!get_user_home().unwrap().join(crate_name).exists());
+/// ```
+pub fn delete_settings_folder_with_symlinks(
+    crate_name: &str,
+    follow_symlinks: bool,
+) -> io::Result<()> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let operation = SettingsOperation::start("delete");
+    let settings_path = backend
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf());
+    let result = if follow_symlinks {
+        backend.delete_folder_following_symlinks(folder)
+    } else {
+        backend.delete_folder(folder)
+    };
+    if let Err(err) = result {
+        log_warn!("failed to delete settings folder {}: {:?}", crate_name, err);
+        operation.failed(&settings_path, &err);
+        return Err(err.into());
+    }
+    log_info!("deleted settings folder {}", crate_name);
+    operation.succeeded(&settings_path, None);
+    untrack_settings_folder(backend.as_ref(), folder);
     Ok(())
 }
 
+/// Deletes the settings directory `<user home>/crate_name` like `delete_settings_folder()`, but treats a missing folder as success.
+/// A symlinked directory is handled like `delete_settings_folder()`, only the link is removed.
+/// Returns true if the folder existed and was deleted.
+///
+/// For example usage, see `delete_setting_file_if_exists()` documentation.
+pub fn delete_settings_folder_if_exists(crate_name: &str) -> io::Result<bool> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let operation = SettingsOperation::start("delete");
+    let settings_path = backend
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf());
+    let deleted = match backend.delete_folder(folder).map_err(io::Error::from) {
+        Ok(()) => true,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+        Err(err) => {
+            log_warn!("failed to delete settings folder {}: {:?}", crate_name, err);
+            operation.failed(&settings_path, &err);
+            return Err(err);
+        }
+    };
+    if deleted {
+        log_info!("deleted settings folder {}", crate_name);
+    }
+    operation.succeeded(&settings_path, None);
+    untrack_settings_folder(backend.as_ref(), folder);
+    Ok(deleted)
+}
+
+/// Deletes every settings file inside `USER_HOME/crate_name`, but keeps the folder itself, e.g. a folder shared with other tools.
+/// Files in subfolders, e.g. profiles, are deleted as well if `recursive` is true, the subfolders themselves are kept.
+/// The deleted files are removed from `SETTINGS_PATHS`. Returns how many files were deleted.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::profiles::{load_profile, save_profile};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_clear_doc";
+/// save_settings_with_filename(crate_name, "window.ser", &Settings{ setting1: 1 }).unwrap();
+/// save_profile(crate_name, "work", &Settings{ setting1: 2 }).unwrap();
+///
+/// assert_eq!(clear_settings(crate_name, false).unwrap(), 1);
+/// assert!(get_user_home().unwrap().join(crate_name).is_dir());
+/// assert!(load_profile::<Settings>(crate_name, "work").is_ok());
+///
+/// assert_eq!(clear_settings(crate_name, true).unwrap(), 1);
+/// assert!(load_profile::<Settings>(crate_name, "work").is_err());
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn clear_settings(crate_name: &str, recursive: bool) -> io::Result<usize> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let operation = SettingsOperation::start("delete");
+    let settings_path = backend
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf());
+    match clear_folder(backend.as_ref(), folder, recursive) {
+        Ok(deleted) => {
+            log_info!("cleared {} settings files from {}", deleted, crate_name);
+            operation.succeeded(&settings_path, None);
+            Ok(deleted)
+        }
+        Err(err) => {
+            log_warn!("failed to clear settings folder {}: {:?}", crate_name, err);
+            operation.failed(&settings_path, &err);
+            Err(err)
+        }
+    }
+}
+
+/// Deletes every settings file inside `folder`, and inside its subfolders if `recursive` is true, returning how many were deleted
+fn clear_folder(
+    backend: &dyn SettingsBackend,
+    folder: &Path,
+    recursive: bool,
+) -> io::Result<usize> {
+    // listed up front, as backends without real folders drop a folder along with its last file
+    let folder_names = if recursive {
+        backend.list_folders(folder)?
+    } else {
+        vec![]
+    };
+    let mut deleted = 0;
+    for file_name in backend.list(folder)? {
+        backend.delete(folder, &file_name)?;
+        untrack_settings_file(backend, folder, &file_name);
+        deleted += 1;
+    }
+    for folder_name in folder_names {
+        deleted += clear_folder(backend, &folder.join(folder_name), true)?;
+    }
+    Ok(deleted)
+}
+
+/// Removes every path inside `folder` from `SETTINGS_PATHS`
+fn untrack_settings_folder(backend: &dyn SettingsBackend, folder: &Path) {
+    if let Some(settings_path) = backend.resolve(folder) {
+        SETTINGS_PATHS
+            .write()
+            .unwrap()
+            .retain(|path| !path.starts_with(&settings_path));
+    }
+}
+
 /// Deletes a specific settings file
 /// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
 /// use std::ffi::OsStr;
 /// use serde::{Deserialize, Serialize};
 /// use cr_program_settings::prelude::*;
@@ -307,14 +2746,209 @@ pub fn delete_settings(crate_name: &str) -> io::Result<()> {
 ///
 ///
 /// ```
-pub fn delete_setting_file(crate_name: &str, file_name: &str) -> io::Result<()> {
-    let home_dir = get_user_home().unwrap();
-    let settings_path = home_dir.join(PathBuf::from(crate_name));
-    let settings_file = settings_path.join(file_name);
-    fs::remove_file(&settings_file)?;
-    SETTINGS_PATHS
-        .write()
-        .unwrap()
-        .retain(|path| path != &settings_file);
+pub fn delete_setting_file(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+) -> io::Result<()> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = crate_name.as_ref();
+    let file_name = file_name.as_ref();
+    let operation = SettingsOperation::start("delete");
+    let settings_file_path = resolve_settings_file(backend.as_ref(), folder, file_name);
+    if let Err(err) = backend.delete(folder, file_name) {
+        log_warn!(
+            "failed to delete settings {}/{}: {:?}",
+            folder.display(),
+            file_name,
+            err
+        );
+        operation.failed(&settings_file_path, &err);
+        return Err(err.into());
+    }
+    log_info!("deleted settings {}/{}", folder.display(), file_name);
+    operation.succeeded(&settings_file_path, None);
+    untrack_settings_file(backend.as_ref(), folder, file_name);
+    Ok(())
+}
+
+/// Deletes a specific settings file like `delete_setting_file()`, but treats a missing file as success,
+/// e.g. for cleanup code that runs whether or not the settings were ever saved.
+/// Returns true if the file existed and was deleted, either way its path is removed from `SETTINGS_PATHS`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 1 }, "cleanup.ser").unwrap();
+///
+/// assert!(delete_setting_file_if_exists(env!("CARGO_CRATE_NAME"), "cleanup.ser").unwrap());
+/// // the file is already gone, which is not an error
+/// assert!(!delete_settings_if_exists!("cleanup.ser").unwrap());
+///
+/// assert!(!delete_settings_folder_if_exists("cr_program_settings_never_saved").unwrap());
+/// ```
+pub fn delete_setting_file_if_exists(crate_name: &str, file_name: &str) -> io::Result<bool> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let operation = SettingsOperation::start("delete");
+    let settings_file_path = resolve_settings_file(backend.as_ref(), folder, file_name);
+    let deleted = match backend.delete(folder, file_name).map_err(io::Error::from) {
+        Ok(()) => true,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+        Err(err) => {
+            log_warn!(
+                "failed to delete settings {}/{}: {:?}",
+                crate_name,
+                file_name,
+                err
+            );
+            operation.failed(&settings_file_path, &err);
+            return Err(err);
+        }
+    };
+    if deleted {
+        log_info!("deleted settings {}/{}", crate_name, file_name);
+    }
+    // a missing file is not a failure, so it is traced as a successful delete
+    operation.succeeded(&settings_file_path, None);
+    untrack_settings_file(backend.as_ref(), folder, file_name);
+    Ok(deleted)
+}
+
+/// Renames the settings file `USER_HOME/crate_name/old_name` to `new_name` within the same folder,
+/// replacing its path in `SETTINGS_PATHS` if it was tracked.
+///
+/// Returns the `NotFound` io error if `old_name` does not exist, and the `AlreadyExists` io error,
+/// without touching either file, if `new_name` already exists.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Profile{
+/// name: String,
+/// }
+///
+/// save_settings!(Profile{ name: "work".to_string() }, "work.ser").unwrap();
+/// rename_settings_file(env!("CARGO_CRATE_NAME"), "work.ser", "office.ser").unwrap();
+///
+/// assert!(load_settings!(Profile, "work.ser").is_err());
+/// assert_eq!(load_settings!(Profile, "office.ser").unwrap().name, "work");
+///
+/// delete_settings!("office.ser").unwrap();
+/// ```
+pub fn rename_settings_file(crate_name: &str, old_name: &str, new_name: &str) -> io::Result<()> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    if !backend.exists(folder, old_name) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("settings file {}/{} does not exist", crate_name, old_name),
+        ));
+    }
+    if backend.exists(folder, new_name) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("settings file {}/{} already exists", crate_name, new_name),
+        ));
+    }
+    if let Err(err) = backend.rename(folder, old_name, new_name) {
+        log_warn!(
+            "failed to rename settings {}/{} to {}: {:?}",
+            crate_name,
+            old_name,
+            new_name,
+            err
+        );
+        return Err(err.into());
+    }
+    log_info!(
+        "renamed settings {}/{} to {}",
+        crate_name,
+        old_name,
+        new_name
+    );
+    if let Some(settings_path) = backend.resolve(folder) {
+        let old_path = settings_path.join(old_name);
+        let mut lock = SETTINGS_PATHS.write().unwrap();
+        if lock.contains(&old_path) {
+            lock.retain(|path| path != &old_path);
+            drop(lock);
+            track_settings_path(settings_path.join(new_name));
+        }
+    }
     Ok(())
 }
+
+/// Moves a settings file that can not be loaded out of the way, renaming it to `file_name.corrupt-<timestamp>` in the same folder,
+/// where `<timestamp>` is the current unix time in seconds. Returns the path of the quarantined copy,
+/// so a program can tell the user where their old settings were preserved.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings!(Settings{ setting1: 1 }, "quarantined.ser").unwrap();
+/// let quarantined = quarantine_settings_file(env!("CARGO_CRATE_NAME"), "quarantined.ser").unwrap();
+///
+/// assert!(quarantined.file_name().unwrap().to_str().unwrap().starts_with("quarantined.ser.corrupt-"));
+/// assert!(load_settings!(Settings, "quarantined.ser").is_err());
+///
+/// std::fs::remove_file(quarantined).unwrap();
+/// ```
+pub fn quarantine_settings_file(crate_name: &str, file_name: &str) -> io::Result<PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let backend = get_backend();
+    let folder = Path::new(crate_name);
+    let mut quarantine_name = format!("{}.corrupt-{}", file_name, timestamp);
+    let mut copy = 1;
+    while backend.exists(folder, &quarantine_name) {
+        quarantine_name = format!("{}.corrupt-{}-{}", file_name, timestamp, copy);
+        copy += 1;
+    }
+    rename_settings_file(crate_name, file_name, &quarantine_name)?;
+    log_warn!(
+        "quarantined settings {}/{} as {}",
+        crate_name,
+        file_name,
+        quarantine_name
+    );
+    Ok(backend
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf())
+        .join(quarantine_name))
+}
+
+/// Removes the path of `folder/file_name` from `SETTINGS_PATHS`
+fn untrack_settings_file(backend: &dyn SettingsBackend, folder: &Path, file_name: &str) {
+    if let Some(settings_path) = backend.resolve(folder) {
+        let settings_file = settings_path.join(file_name);
+        SETTINGS_PATHS
+            .write()
+            .unwrap()
+            .retain(|path| path != &settings_file);
+    }
+}
@@ -0,0 +1,147 @@
+//! Browser `localStorage` backend source file, used by default on `wasm32` with the `wasm` feature
+#![warn(missing_docs)]
+
+use crate::backend::{BackendError, SettingsBackend};
+use std::path::{Path, PathBuf};
+use wasm_bindgen::JsCast;
+use web_sys::{DomException, Storage};
+
+#[derive(Debug, Default, Clone, Copy)]
+/// A backend that stores settings in the browsers `localStorage`, keyed by `"{crate_name}/{file_name}"`.
+/// Values are stored as strings, so only text formats such as the default TOML can be saved.
+pub struct LocalStorageBackend;
+
+impl LocalStorageBackend {
+    /// Returns the browsers `localStorage`, or `StorageUnavailable` when there is no window or storage is disabled
+    fn storage(&self) -> Result<Storage, BackendError> {
+        match web_sys::window().map(|window| window.local_storage()) {
+            Some(Ok(Some(storage))) => Ok(storage),
+            _ => Err(BackendError::StorageUnavailable),
+        }
+    }
+
+    /// Returns the `localStorage` key prefix for a folder
+    fn folder_key(&self, folder: &Path) -> String {
+        format!("{}/", folder.to_string_lossy())
+    }
+
+    /// Returns the `localStorage` key for a settings file
+    fn file_key(&self, folder: &Path, file: &str) -> String {
+        format!("{}{}", self.folder_key(folder), file)
+    }
+
+    /// Returns every `localStorage` key currently stored
+    fn keys(&self, storage: &Storage) -> Result<Vec<String>, BackendError> {
+        let length = storage
+            .length()
+            .map_err(|_| BackendError::StorageUnavailable)?;
+        Ok((0..length)
+            .filter_map(|index| storage.key(index).ok().flatten())
+            .collect())
+    }
+}
+
+impl SettingsBackend for LocalStorageBackend {
+    fn read(&self, folder: &Path, file: &str) -> Result<Vec<u8>, BackendError> {
+        let key = self.file_key(folder, file);
+        match self.storage()?.get_item(&key) {
+            Ok(Some(value)) => Ok(value.into_bytes()),
+            Ok(None) => Err(not_found(&key)),
+            Err(_) => Err(BackendError::StorageUnavailable),
+        }
+    }
+
+    fn write(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        let value = std::str::from_utf8(bytes).map_err(|err| {
+            BackendError::IOError(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        })?;
+        match self
+            .storage()?
+            .set_item(&self.file_key(folder, file), value)
+        {
+            Ok(_) => Ok(()),
+            Err(err) => match err.dyn_ref::<DomException>() {
+                Some(exception) if exception.name() == "QuotaExceededError" => {
+                    Err(BackendError::QuotaExceeded)
+                }
+                _ => Err(BackendError::StorageUnavailable),
+            },
+        }
+    }
+
+    fn delete(&self, folder: &Path, file: &str) -> Result<(), BackendError> {
+        if !self.exists(folder, file) {
+            return Err(not_found(&self.file_key(folder, file)));
+        }
+        self.storage()?
+            .remove_item(&self.file_key(folder, file))
+            .map_err(|_| BackendError::StorageUnavailable)
+    }
+
+    fn delete_folder(&self, folder: &Path) -> Result<(), BackendError> {
+        let storage = self.storage()?;
+        let prefix = self.folder_key(folder);
+        let keys: Vec<String> = self
+            .keys(&storage)?
+            .into_iter()
+            .filter(|key| key.starts_with(&prefix))
+            .collect();
+        if keys.is_empty() {
+            return Err(not_found(&prefix));
+        }
+        for key in keys {
+            storage
+                .remove_item(&key)
+                .map_err(|_| BackendError::StorageUnavailable)?;
+        }
+        Ok(())
+    }
+
+    fn exists(&self, folder: &Path, file: &str) -> bool {
+        match self.storage() {
+            Ok(storage) => matches!(storage.get_item(&self.file_key(folder, file)), Ok(Some(_))),
+            Err(_) => false,
+        }
+    }
+
+    fn list(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let storage = self.storage()?;
+        let prefix = self.folder_key(folder);
+        let mut names: Vec<String> = self
+            .keys(&storage)?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&prefix).map(|name| name.to_string()))
+            .filter(|name| !name.contains('/'))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn list_folders(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        let storage = self.storage()?;
+        let prefix = self.folder_key(folder);
+        let mut names: Vec<String> = self
+            .keys(&storage)?
+            .into_iter()
+            .filter_map(|key| {
+                let (name, _) = key.strip_prefix(&prefix)?.split_once('/')?;
+                Some(name.to_string())
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn resolve(&self, folder: &Path) -> Option<PathBuf> {
+        Some(folder.to_path_buf())
+    }
+}
+
+/// Returns a `NotFound` io error for a missing `localStorage` key
+fn not_found(key: &str) -> BackendError {
+    BackendError::IOError(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("{} does not exist in localStorage", key),
+    ))
+}
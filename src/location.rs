@@ -0,0 +1,97 @@
+//! Settings location source file, a folder and file name pair, as created by the `settings_path!` macro
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::{
+    delete_setting_file, load_settings_with_filename, save_settings_with_filename,
+    LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The location of a settings file, `USER_HOME/folder/file_name`.
+///
+/// With the `derive` feature, `settings_path!("my_app", "config.toml")` creates a location whose folder and file name
+/// were checked at compile time to be non-empty and free of path separators, so a typo such as `"my_app/config.toml"`
+/// is a compile error rather than a file saved somewhere unexpected. Like the other macros, the folder defaults to
+/// the crate name, or the folder set with `set_default_folder()`, when only the file name is given.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::location::SettingsLocation;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let location = SettingsLocation::new("cr_program_settings_doctest_location", "config.toml");
+/// location.save(&Settings{ setting1: 4 }).unwrap();
+/// assert!(location.exists());
+/// assert!(location.path().ends_with("cr_program_settings_doctest_location/config.toml"));
+/// assert_eq!(location.load::<Settings>().unwrap(), Settings{ setting1: 4 });
+///
+/// location.delete().unwrap();
+/// ```
+pub struct SettingsLocation {
+    /// The name of the folder in the users home directory
+    folder: String,
+    /// The name of the settings file
+    file_name: String,
+}
+
+impl SettingsLocation {
+    /// Creates the location `USER_HOME/folder/file_name`, without checking the names, see `settings_path!` for checked names
+    pub fn new(folder: impl AsRef<str>, file_name: impl AsRef<str>) -> Self {
+        Self {
+            folder: folder.as_ref().to_string(),
+            file_name: file_name.as_ref().to_string(),
+        }
+    }
+
+    /// Returns the name of the folder in the users home directory
+    pub fn folder(&self) -> &str {
+        &self.folder
+    }
+
+    /// Returns the name of the settings file
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Saves the settings to `USER_HOME/folder/file_name`
+    pub fn save<T>(&self, settings: &T) -> Result<(), SaveSettingsError>
+    where
+        T: Serialize,
+    {
+        save_settings_with_filename(&self.folder, &self.file_name, settings)
+    }
+
+    /// Loads the settings from `USER_HOME/folder/file_name`
+    pub fn load<T>(&self) -> Result<T, LoadSettingsError>
+    where
+        for<'a> T: Deserialize<'a>,
+    {
+        load_settings_with_filename(&self.folder, &self.file_name)
+    }
+
+    /// Returns true if the settings file exists at `USER_HOME/folder/file_name`
+    pub fn exists(&self) -> bool {
+        get_backend().exists(Path::new(&self.folder), &self.file_name)
+    }
+
+    /// Deletes the settings file at `USER_HOME/folder/file_name`
+    pub fn delete(&self) -> io::Result<()> {
+        delete_setting_file(&self.folder, &self.file_name)
+    }
+
+    /// Returns the path of the settings file, this path is relative if the users home could not be found
+    pub fn path(&self) -> PathBuf {
+        get_backend()
+            .resolve(Path::new(&self.folder))
+            .unwrap_or_else(|| PathBuf::from(&self.folder))
+            .join(&self.file_name)
+    }
+}
@@ -0,0 +1,776 @@
+//! Options source file, settings for how settings files are written and read
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::env_overrides::expand_env_vars;
+use crate::includes::{resolve_includes, MissingInclude};
+use crate::paths::with_settings_file_dir;
+use crate::{
+    decode_settings_text, deserialize_settings, max_settings_file_size,
+    read_settings_bytes_limited, resolve_settings_file, serialize_settings_with,
+    write_settings_bytes_with, LoadSettingsError, ParseError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::Value;
+
+/// The delay before the first retry, unless configured otherwise
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// The line endings a settings file is saved with, loading accepts either
+pub enum LineEnding {
+    #[default]
+    /// `\n`, what the library writes on every platform unless told otherwise
+    Lf,
+    /// `\r\n`
+    Crlf,
+    /// `\r\n` on Windows and `\n` everywhere else
+    Native,
+}
+
+impl LineEnding {
+    /// Returns the characters that end a line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native => "\n",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How TOML settings files are formatted, passed through to the `toml` serializer.
+/// The defaults format files the same way as `save_settings_with_filename()`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::options::TomlOptions;
+///
+/// let options = TomlOptions::new().multiline_arrays(false);
+/// assert!(!options.get_multiline_arrays());
+/// ```
+pub struct TomlOptions {
+    /// Whether every array element is written on its own line
+    multiline_arrays: bool,
+}
+
+impl Default for TomlOptions {
+    fn default() -> Self {
+        Self {
+            multiline_arrays: true,
+        }
+    }
+}
+
+impl TomlOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether arrays are written with every element on its own line, true by default.
+    /// With false, arrays are written on a single line, e.g. `values = [1, 2, 3]`, which keeps long arrays of numbers short
+    pub fn multiline_arrays(mut self, multiline_arrays: bool) -> Self {
+        self.multiline_arrays = multiline_arrays;
+        self
+    }
+
+    /// Returns whether arrays are written with every element on its own line
+    pub fn get_multiline_arrays(&self) -> bool {
+        self.multiline_arrays
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `save_settings_with_options()`, the defaults save the same file as `save_settings_with_filename()`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use cr_program_settings::options::{LineEnding, SaveOptions};
+///
+/// let options = SaveOptions::new()
+///     .line_endings(LineEnding::Crlf)
+///     .retries(3)
+///     .retry_delay(Duration::from_millis(20));
+/// assert_eq!(options.get_line_endings(), LineEnding::Crlf);
+/// assert_eq!(options.get_retries(), 3);
+/// ```
+pub struct SaveOptions {
+    /// The line endings of the saved file
+    line_endings: LineEnding,
+    /// How many times a failed write is retried
+    retries: u32,
+    /// The delay before the first retry
+    retry_delay: Duration,
+    /// How the TOML is formatted
+    toml: TomlOptions,
+    /// The comment block written above the settings
+    header: Option<String>,
+    /// Whether the save fails if the file already exists
+    create_new: bool,
+    /// The largest file that is written, `None` for no limit
+    max_file_size: Option<u64>,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            toml: TomlOptions::default(),
+            line_endings: LineEnding::default(),
+            retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            header: None,
+            create_new: false,
+            max_file_size: None,
+        }
+    }
+}
+
+impl SaveOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many times a write that failed with a temporary io error is retried, 0 by default.
+    /// See `is_retryable()` for the errors that are retried, any other error fails the save immediately
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns how many times a failed write is retried
+    pub fn get_retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Sets the delay before the first retry, 50 ms by default. The delay doubles after every failed retry
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Returns the delay before the first retry
+    pub fn get_retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+
+    /// Sets the line endings of the saved file, `LineEnding::Lf` by default
+    pub fn line_endings(mut self, line_endings: LineEnding) -> Self {
+        self.line_endings = line_endings;
+        self
+    }
+
+    /// Returns the line endings of the saved file
+    pub fn get_line_endings(&self) -> LineEnding {
+        self.line_endings
+    }
+
+    /// Sets how the TOML is formatted, `TomlOptions::default()` by default
+    pub fn toml(mut self, toml: TomlOptions) -> Self {
+        self.toml = toml;
+        self
+    }
+
+    /// Returns how the TOML is formatted
+    pub fn get_toml(&self) -> &TomlOptions {
+        &self.toml
+    }
+
+    /// Sets a comment block written above the settings, none by default, e.g. a generated-file warning or documentation of each field.
+    /// Every line of `header` is prefixed with `# `, and the comment is separated from the settings by an empty line,
+    /// so loading the file is unaffected. An empty header adds nothing
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Returns the comment block written above the settings
+    pub fn get_header(&self) -> Option<&str> {
+        self.header.as_deref()
+    }
+
+    /// Sets whether the save fails with `SaveSettingsError::AlreadyExists` if the file already exists, false by default.
+    /// The check and the creation of the file are a single operation on the `FileSystemBackend`
+    pub fn create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Returns whether the save fails if the file already exists
+    pub fn get_create_new(&self) -> bool {
+        self.create_new
+    }
+
+    /// Sets the size in bytes above which the save fails with `SaveSettingsError::FileTooLarge` without writing, no limit by default
+    pub fn max_file_size(mut self, max_bytes: u64) -> Self {
+        self.max_file_size = Some(max_bytes);
+        self
+    }
+
+    /// Returns the size in bytes above which the save fails, if any
+    pub fn get_max_file_size(&self) -> Option<u64> {
+        self.max_file_size
+    }
+
+    /// Serializes the settings into the text of the settings file, with the header and line endings applied
+    pub(crate) fn render<T>(&self, settings: &T) -> Result<String, SaveSettingsError>
+    where
+        T: Serialize,
+    {
+        let serialized_data = serialize_settings_with(settings, &self.toml)?;
+        let mut file_data = String::new();
+        for line in self.header.as_deref().unwrap_or_default().lines() {
+            if line.is_empty() {
+                file_data.push_str("#\n");
+            } else {
+                file_data.push_str("# ");
+                file_data.push_str(line);
+                file_data.push('\n');
+            }
+        }
+        if !file_data.is_empty() {
+            file_data.push('\n');
+        }
+        file_data.push_str(&serialized_data);
+        Ok(self.normalize(&file_data))
+    }
+
+    /// Applies the options to serialized settings text: every line ends with the configured line ending,
+    /// and the text ends with exactly one of them
+    fn normalize(&self, serialized_data: &str) -> String {
+        let line_ending = self.line_endings.as_str();
+        let mut normalized = String::with_capacity(serialized_data.len() + line_ending.len());
+        for line in serialized_data.trim_end().lines() {
+            normalized.push_str(line);
+            normalized.push_str(line_ending);
+        }
+        if normalized.is_empty() {
+            normalized.push_str(line_ending);
+        }
+        normalized
+    }
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/file_name` using the given options,
+/// e.g. with `\n` line endings on every platform so a settings folder synced between machines does not produce noisy diffs.
+/// The file always ends with exactly one line ending.
+///
+/// Every plain save function, e.g. `save_settings_with_filename()`, is this function with `SaveOptions::default()`,
+/// and the `save_settings!` macro takes the options as a final `options = expr` argument.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::options::{save_settings_with_options, LineEnding, SaveOptions};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// setting2: bool,
+/// }
+///
+/// let settings = Settings{ setting1: 3, setting2: true };
+/// let options = SaveOptions::new().line_endings(LineEnding::Crlf);
+/// save_settings_with_options(env!("CARGO_CRATE_NAME"), "crlf.ser", &settings, &options).expect("Unable to save settings");
+///
+/// assert_eq!(read_settings_string(env!("CARGO_CRATE_NAME"), "crlf.ser").unwrap(), "setting1 = 3\r\nsetting2 = true\r\n");
+/// // loading accepts either line ending
+/// assert_eq!(load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "crlf.ser").unwrap(), settings);
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "crlf.ser").unwrap();
+/// ```
+pub fn save_settings_with_options<T>(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+    settings: &T,
+    options: &SaveOptions,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let file_data = options.render(settings)?;
+    write_settings_with_options(crate_name.as_ref(), file_name.as_ref(), &file_data, options)
+}
+
+/// Writes settings text rendered with `SaveOptions::render()` to `relative_dir/file_name`,
+/// enforcing the size limit and retrying the write as configured
+pub(crate) fn write_settings_with_options(
+    relative_dir: &Path,
+    file_name: &str,
+    file_data: &str,
+    options: &SaveOptions,
+) -> Result<(), SaveSettingsError> {
+    let size = file_data.len() as u64;
+    if let Some(limit) = options.max_file_size.filter(|limit| size > *limit) {
+        let path = resolve_settings_file(get_backend().as_ref(), relative_dir, file_name);
+        log_error!(
+            "refusing to save settings {}, {} bytes is over the limit of {} bytes",
+            path.display(),
+            size,
+            limit
+        );
+        return Err(SaveSettingsError::FileTooLarge { size, limit, path });
+    }
+    let result = retry(
+        options.retries,
+        options.retry_delay,
+        |err| match err {
+            SaveSettingsError::IOError(err) => Some(err),
+            _ => None,
+        },
+        || {
+            write_settings_bytes_with(
+                relative_dir,
+                file_name,
+                file_data.as_bytes(),
+                |backend, relative_dir, file_name, data| {
+                    if options.create_new {
+                        backend.write_new(relative_dir, file_name, data)
+                    } else {
+                        backend.write(relative_dir, file_name, data)
+                    }
+                },
+            )
+        },
+    );
+    match result {
+        Ok(()) => Ok(()),
+        Err((attempts, SaveSettingsError::IOError(source)))
+            if attempts > 1 && is_retryable(&source) =>
+        {
+            Err(SaveSettingsError::IOErrorAfterRetries { attempts, source })
+        }
+        Err((_, err)) => Err(err),
+    }
+}
+
+/// Saves settings like `save_settings_with_filename()`, formatting the TOML with the given options.
+/// Shorthand for `save_settings_with_options()` with `SaveOptions::new().toml(toml_options.clone())`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::options::{save_settings_with_toml_opts, TomlOptions};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// recent: Vec<u32>,
+/// }
+///
+/// let settings = Settings{ recent: vec![1, 2, 3] };
+/// let options = TomlOptions::new().multiline_arrays(false);
+/// save_settings_with_toml_opts(env!("CARGO_CRATE_NAME"), "inline_arrays.ser", &settings, &options).expect("Unable to save settings");
+///
+/// assert_eq!(read_settings_string(env!("CARGO_CRATE_NAME"), "inline_arrays.ser").unwrap(), "recent = [1, 2, 3]\n");
+/// assert_eq!(load_settings_with_filename::<Settings>(env!("CARGO_CRATE_NAME"), "inline_arrays.ser").unwrap(), settings);
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "inline_arrays.ser").unwrap();
+/// ```
+pub fn save_settings_with_toml_opts<T>(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+    settings: &T,
+    toml_options: &TomlOptions,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let options = SaveOptions::new().toml(toml_options.clone());
+    save_settings_with_options(crate_name, file_name, settings, &options)
+}
+
+/// Saves settings like `save_settings_with_filename()`, retrying the write up to `retries` times on temporary io errors,
+/// e.g. a file briefly locked by an antivirus scanner, waiting `delay` before the first retry and twice as long before each following one.
+/// Errors that retrying cannot fix, such as a serialization failure, are returned immediately.
+/// Shorthand for `save_settings_with_options()` with `SaveOptions::new().retries(retries).retry_delay(delay)`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::options::save_settings_with_retry;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings_with_retry(env!("CARGO_CRATE_NAME"), "retry.ser", &Settings{ setting1: 3 }, 3, Duration::from_millis(50))
+///     .expect("Unable to save settings");
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "retry.ser").unwrap();
+/// ```
+pub fn save_settings_with_retry<T>(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+    settings: &T,
+    retries: u32,
+    delay: Duration,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let options = SaveOptions::new().retries(retries).retry_delay(delay);
+    save_settings_with_options(crate_name, file_name, settings, &options)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// What expanding environment variables does with a reference to a variable that is not set, see `LoadOptions::expand_env()`
+pub enum UnknownEnvVar {
+    #[default]
+    /// The reference is kept as written, e.g. `${MISSING}`
+    Keep,
+    /// The load fails with `LoadSettingsError::UnknownEnvVar`
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `load_settings_with_options()`, the defaults load the same way as `load_settings_with_filename()`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use cr_program_settings::options::LoadOptions;
+///
+/// let options = LoadOptions::new().retries(3).retry_delay(Duration::from_millis(20)).max_file_size(1024 * 1024);
+/// assert_eq!(options.get_retry_delay(), Duration::from_millis(20));
+/// assert_eq!(options.get_max_file_size(), 1024 * 1024);
+/// ```
+pub struct LoadOptions {
+    /// How many times a failed read is retried
+    retries: u32,
+    /// The delay before the first retry
+    retry_delay: Duration,
+    /// The largest file that is read, `None` uses the global limit
+    max_file_size: Option<u64>,
+    /// Whether environment variables in string values are expanded
+    expand_env: bool,
+    /// What is done with a variable that is not set
+    unknown_env: UnknownEnvVar,
+    /// Whether files listed in the top-level `include` array are merged in
+    includes: bool,
+    /// What is done with an included file that does not exist
+    missing_include: MissingInclude,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            max_file_size: None,
+            expand_env: false,
+            unknown_env: UnknownEnvVar::Keep,
+            includes: false,
+            missing_include: MissingInclude::Error,
+        }
+    }
+}
+
+impl LoadOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many times a read that failed with a temporary io error is retried, 0 by default.
+    /// See `is_retryable()` for the errors that are retried, any other error, e.g. a missing file, fails the load immediately
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns how many times a failed read is retried
+    pub fn get_retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Sets the delay before the first retry, 50 ms by default. The delay doubles after every failed retry
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Returns the delay before the first retry
+    pub fn get_retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+
+    /// Sets the size in bytes above which the file is rejected with `LoadSettingsError::FileTooLarge` without being read,
+    /// the global `max_settings_file_size()` by default
+    pub fn max_file_size(mut self, max_bytes: u64) -> Self {
+        self.max_file_size = Some(max_bytes);
+        self
+    }
+
+    /// Returns the size in bytes above which the file is rejected
+    pub fn get_max_file_size(&self) -> u64 {
+        self.max_file_size.unwrap_or_else(max_settings_file_size)
+    }
+
+    /// Sets whether references to environment variables in string values are replaced with their values before
+    /// the settings are deserialized, off by default. `${VAR}` and `$VAR` are expanded, as is `%VAR%` on Windows,
+    /// and `$$` is a literal `$`, or `%%` a literal `%` on Windows. Only string values are expanded, never keys,
+    /// including strings in nested tables and arrays.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use serde::{Deserialize, Serialize};
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+    ///
+    /// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+    /// struct Settings{
+    /// cache_dir: String,
+    /// price: String,
+    /// }
+    ///
+    /// let settings = Settings{ cache_dir: "${DOCTEST_CACHE_ROOT}/cache".to_string(), price: "$$5".to_string() };
+    /// save_settings_with_filename(env!("CARGO_CRATE_NAME"), "expand_env.ser", &settings).unwrap();
+    ///
+    /// std::env::set_var("DOCTEST_CACHE_ROOT", "/tmp/app");
+    /// let options = LoadOptions::new().expand_env(true);
+    /// assert_eq!(
+    ///     load_settings_with_options::<Settings>(env!("CARGO_CRATE_NAME"), "expand_env.ser", &options).unwrap(),
+    ///     Settings{ cache_dir: "/tmp/app/cache".to_string(), price: "$5".to_string() }
+    /// );
+    ///
+    /// delete_setting_file(env!("CARGO_CRATE_NAME"), "expand_env.ser").unwrap();
+    /// ```
+    pub fn expand_env(mut self, expand_env: bool) -> Self {
+        self.expand_env = expand_env;
+        self
+    }
+
+    /// Returns whether environment variables in string values are expanded
+    pub fn get_expand_env(&self) -> bool {
+        self.expand_env
+    }
+
+    /// Sets what expanding environment variables does with a variable that is not set, `UnknownEnvVar::Keep` by default
+    pub fn unknown_env(mut self, unknown_env: UnknownEnvVar) -> Self {
+        self.unknown_env = unknown_env;
+        self
+    }
+
+    /// Returns what expanding environment variables does with a variable that is not set
+    pub fn get_unknown_env(&self) -> UnknownEnvVar {
+        self.unknown_env
+    }
+
+    /// Sets whether the files listed in a top-level `include` array are loaded and merged in, off by default.
+    /// Included files are file names in the same settings folder, and may include further files.
+    /// They are deep merged in order, each over the one before it, and the including file is merged over all of them,
+    /// so its own values win. The `include` key itself is removed before the settings are deserialized.
+    /// A file that includes itself, directly or through other files, fails the load with `LoadSettingsError::IncludeCycle`.
+    /// Every included file is added to `SETTINGS_PATHS`.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use serde::{Deserialize, Serialize};
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+    ///
+    /// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+    /// struct Settings{
+    /// theme: String,
+    /// volume: u32,
+    /// }
+    ///
+    /// let crate_name = "cr_program_settings_doctest_includes";
+    /// let settings_dir = get_user_home().unwrap().join(crate_name);
+    /// std::fs::create_dir_all(&settings_dir).unwrap();
+    /// std::fs::write(settings_dir.join("theme.toml"), "theme = \"dark\"\nvolume = 10").unwrap();
+    /// std::fs::write(settings_dir.join("main.toml"), "include = [\"theme.toml\"]\nvolume = 80").unwrap();
+    ///
+    /// let options = LoadOptions::new().includes(true);
+    /// assert_eq!(
+    ///     load_settings_with_options::<Settings>(crate_name, "main.toml", &options).unwrap(),
+    ///     Settings{ theme: "dark".to_string(), volume: 80 }
+    /// );
+    ///
+    /// delete_settings_folder(crate_name).unwrap();
+    /// ```
+    pub fn includes(mut self, includes: bool) -> Self {
+        self.includes = includes;
+        self
+    }
+
+    /// Returns whether the files listed in a top-level `include` array are loaded and merged in
+    pub fn get_includes(&self) -> bool {
+        self.includes
+    }
+
+    /// Sets what loading with includes does with an included file that does not exist, `MissingInclude::Error` by default
+    pub fn missing_include(mut self, missing_include: MissingInclude) -> Self {
+        self.missing_include = missing_include;
+        self
+    }
+
+    /// Returns what loading with includes does with an included file that does not exist
+    pub fn get_missing_include(&self) -> MissingInclude {
+        self.missing_include
+    }
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name` using the given options,
+/// e.g. retrying a read that fails while a file sync tool briefly locks the file.
+/// Only reading the file is retried, a file that fails to parse fails the load immediately.
+///
+/// Every plain load function, e.g. `load_settings_with_filename()`, is this function with `LoadOptions::default()`,
+/// and the `load_settings!` macro takes the options as a final `options = expr` argument.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// save_settings_with_filename(env!("CARGO_CRATE_NAME"), "retried.ser", &Settings{ setting1: 5 }).unwrap();
+/// let options = LoadOptions::new().retries(3);
+/// assert_eq!(load_settings_with_options::<Settings>(env!("CARGO_CRATE_NAME"), "retried.ser", &options).unwrap(), Settings{ setting1: 5 });
+///
+/// // a missing file is not retried
+/// assert!(matches!(
+///     load_settings_with_options::<Settings>(env!("CARGO_CRATE_NAME"), "never_saved.ser", &options),
+///     Err(LoadSettingsError::IOError(_))
+/// ));
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "retried.ser").unwrap();
+/// ```
+pub fn load_settings_with_options<T>(
+    crate_name: impl AsRef<Path>,
+    file_name: impl AsRef<str>,
+    options: &LoadOptions,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (crate_name, file_name) = (crate_name.as_ref(), file_name.as_ref());
+    match read_settings_with_options(crate_name, file_name, options)? {
+        (settings_file_path, file_data) if options.expand_env || options.includes => {
+            let table = deserialize_settings(settings_file_path.clone(), file_data.clone())?;
+            let mut value = if options.includes {
+                resolve_includes(
+                    crate_name,
+                    &settings_file_path,
+                    table,
+                    options.missing_include,
+                    options.get_max_file_size(),
+                )?
+            } else {
+                Value::Table(table)
+            };
+            if options.expand_env {
+                expand_env_vars(&mut value, options.unknown_env)
+                    .map_err(LoadSettingsError::UnknownEnvVar)?;
+            }
+            with_settings_file_dir(&settings_file_path, || T::deserialize(value)).map_err(|err| {
+                let text =
+                    decode_settings_text(&settings_file_path, &file_data).unwrap_or_default();
+                LoadSettingsError::ParseError(Box::new(locate_value_error::<T>(
+                    settings_file_path,
+                    text,
+                    err,
+                )))
+            })
+        }
+        (settings_file_path, file_data) => deserialize_settings(settings_file_path, file_data),
+    }
+}
+
+/// Reads `relative_dir/file_name`, enforcing the size limit and retrying the read as configured,
+/// returning the resolved path of the file along with its contents
+pub(crate) fn read_settings_with_options(
+    relative_dir: &Path,
+    file_name: &str,
+    options: &LoadOptions,
+) -> Result<(PathBuf, Vec<u8>), LoadSettingsError> {
+    let result = retry(
+        options.retries,
+        options.retry_delay,
+        |err| match err {
+            LoadSettingsError::IOError(err) => Some(err),
+            _ => None,
+        },
+        || read_settings_bytes_limited(relative_dir, file_name, options.get_max_file_size()),
+    );
+    match result {
+        Ok(read) => Ok(read),
+        Err((attempts, LoadSettingsError::IOError(source)))
+            if attempts > 1 && is_retryable(&source) =>
+        {
+            Err(LoadSettingsError::IOErrorAfterRetries { attempts, source })
+        }
+        Err((_, err)) => Err(err),
+    }
+}
+
+/// Builds the parse error for settings that failed to deserialize from a `toml::Value` built from the file at `path`,
+/// e.g. after expanding environment variables. Such errors carry no position, so the file `text` is parsed again,
+/// and if it fails with the same message, the error is reported at that position in the file.
+/// Otherwise the offending value came from elsewhere, e.g. an included file, and the error is reported without a position
+fn locate_value_error<T>(path: PathBuf, text: &str, err: toml::de::Error) -> ParseError
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match with_settings_file_dir(&path, || toml::from_str::<T>(text)) {
+        Err(text_err) if text_err.span().is_some() && text_err.message() == err.message() => {
+            ParseError::new(path, text, text_err)
+        }
+        _ => ParseError::new(path, text, err),
+    }
+}
+
+/// Returns true if an io error is usually temporary and the operation is worth retrying:
+/// `PermissionDenied`, e.g. a sharing violation while another program has the file open, `Interrupted`, and `WouldBlock`.
+/// On Windows, sharing and lock violations are retried as well
+pub fn is_retryable(err: &io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+    if cfg!(windows) && matches!(err.raw_os_error(), Some(32) | Some(33)) {
+        return true;
+    }
+    matches!(
+        err.kind(),
+        io::ErrorKind::PermissionDenied | io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+    )
+}
+
+/// Runs `operation` until it succeeds, fails with an error that is not retryable, or has been retried `retries` times,
+/// waiting `retry_delay` before the first retry and twice as long before each following one.
+/// `io_error` returns the io error within an error, if any. On failure returns how many attempts were made along with the last error
+fn retry<T, E>(
+    retries: u32,
+    retry_delay: Duration,
+    io_error: impl Fn(&E) -> Option<&io::Error>,
+    mut operation: impl FnMut() -> Result<T, E>,
+) -> Result<T, (u32, E)> {
+    let mut attempts = 0;
+    let mut delay = retry_delay;
+    loop {
+        attempts += 1;
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let retryable = io_error(&err).is_some_and(is_retryable);
+                if !retryable || attempts > retries {
+                    return Err((attempts, err));
+                }
+                log_warn!(
+                    "attempt {} of {} failed, retrying in {:?}",
+                    attempts,
+                    retries + 1,
+                    delay
+                );
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+        }
+    }
+}
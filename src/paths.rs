@@ -0,0 +1,142 @@
+//! Settings path source file, a path settings field that expands `~` and environment variables when loaded
+#![warn(missing_docs)]
+
+use crate::env_overrides::expand_env_str;
+use crate::get_user_home;
+use crate::options::UnknownEnvVar;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    /// Directory of the settings file currently being loaded, relative `SettingsPath`s are resolved against it
+    static SETTINGS_FILE_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A path settings field as the user typed it, e.g. `~/Downloads` or `$XDG_CACHE_HOME/my_app`.
+///
+/// When loaded, a leading `~` is replaced with the users home, environment variables are expanded like
+/// `LoadOptions::expand_env()` does, and a relative path is resolved against the folder of the settings file.
+/// When saved, the original string is written back, so the settings file stays as the user wrote it.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::paths::SettingsPath;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// downloads: SettingsPath,
+/// cache: SettingsPath,
+/// }
+///
+/// let crate_name = "cr_program_settings_doctest_paths";
+/// let settings = Settings{ downloads: SettingsPath::new("~/Downloads"), cache: SettingsPath::new("cache") };
+/// save_settings(crate_name, &settings).unwrap();
+///
+/// let loaded = load_settings::<Settings>(crate_name).unwrap();
+/// assert_eq!(loaded.downloads.original(), "~/Downloads");
+/// assert_eq!(loaded.downloads.as_resolved(), get_user_home().unwrap().join("Downloads"));
+/// assert_eq!(loaded.cache.as_resolved(), get_user_home().unwrap().join(crate_name).join("cache"));
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub struct SettingsPath {
+    /// The path as written in the settings file
+    original: String,
+    /// The path with `~` and environment variables expanded, and resolved against the settings file folder
+    resolved: PathBuf,
+}
+
+impl SettingsPath {
+    /// Creates a path from the string the user typed, expanding `~` and environment variables.
+    /// A relative path is resolved against the folder of the settings file when loaded, and kept relative otherwise
+    pub fn new(original: impl Into<String>) -> Self {
+        let original = original.into();
+        let resolved = resolve_path(&original);
+        Self { original, resolved }
+    }
+
+    /// Returns the path with `~` and environment variables expanded, resolved against the settings file folder
+    pub fn as_resolved(&self) -> &Path {
+        &self.resolved
+    }
+
+    /// Returns the path as written in the settings file
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+}
+
+impl AsRef<Path> for SettingsPath {
+    fn as_ref(&self) -> &Path {
+        &self.resolved
+    }
+}
+
+impl Serialize for SettingsPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.original)
+    }
+}
+
+impl<'de> Deserialize<'de> for SettingsPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(SettingsPath::new)
+    }
+}
+
+/// Expands `~` and environment variables in `original`, and resolves it against `SETTINGS_FILE_DIR` if it is relative
+fn resolve_path(original: &str) -> PathBuf {
+    // a variable that is not set is kept as written, so this never fails
+    let expanded =
+        expand_env_str(original, UnknownEnvVar::Keep).unwrap_or_else(|_| original.to_string());
+    let path = match expanded.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(std::path::is_separator) => {
+            match get_user_home() {
+                Some(home_dir) => home_dir.join(rest.trim_start_matches(std::path::is_separator)),
+                None => PathBuf::from(&expanded),
+            }
+        }
+        _ => PathBuf::from(&expanded),
+    };
+    if path.is_relative() {
+        if let Some(settings_dir) = SETTINGS_FILE_DIR.with(|dir| dir.borrow().clone()) {
+            return settings_dir.join(path);
+        }
+    }
+    path
+}
+
+/// Calls `f` with relative `SettingsPath`s resolved against the folder of `settings_file_path`,
+/// restoring the previous folder afterwards, so nested loads resolve against their own file
+pub(crate) fn with_settings_file_dir<R>(settings_file_path: &Path, f: impl FnOnce() -> R) -> R {
+    let _dir_guard = SettingsFileDirGuard::new(settings_file_path.parent());
+    f()
+}
+
+/// Sets `SETTINGS_FILE_DIR` on the current thread until dropped, then restores the previous folder
+struct SettingsFileDirGuard {
+    previous: Option<PathBuf>,
+}
+
+impl SettingsFileDirGuard {
+    fn new(settings_dir: Option<&Path>) -> Self {
+        let previous =
+            SETTINGS_FILE_DIR.with(|dir| dir.replace(settings_dir.map(Path::to_path_buf)));
+        Self { previous }
+    }
+}
+
+impl Drop for SettingsFileDirGuard {
+    fn drop(&mut self) {
+        SETTINGS_FILE_DIR.with(|dir| *dir.borrow_mut() = self.previous.take());
+    }
+}
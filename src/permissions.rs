@@ -0,0 +1,196 @@
+//! Settings file permissions source file, these functions only apply to the default `FileSystemBackend`
+#![warn(missing_docs)]
+
+use crate::backend::BackendError;
+use crate::{get_settings_file_path, is_read_only, read_only_error, SaveSettingsError};
+use serde::Serialize;
+use std::fs::{File, Permissions};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{fs, io};
+
+/// Whether settings files and folders are created with owner only permissions
+static RESTRICTIVE_PERMISSIONS: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether the `FileSystemBackend` restricts settings to the current user, enabled by default.
+///
+/// When enabled on unix, settings files are saved with mode `0o600` and newly created settings folders with mode `0o700`,
+/// so settings containing tokens are not readable by other users. This is a no-op on Windows,
+/// where files in the users home are already private to the user by default.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// api_token: String,
+/// }
+///
+/// save_settings!(Settings{ api_token: "hunter2".to_string() }, "private.ser").unwrap();
+///
+/// #[cfg(unix)]
+/// {
+///     use std::os::unix::fs::PermissionsExt;
+///     let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("private.ser");
+///     assert_eq!(std::fs::metadata(path).unwrap().permissions().mode() & 0o777, 0o600);
+/// }
+/// ```
+pub fn set_restrictive_permissions(enabled: bool) {
+    RESTRICTIVE_PERMISSIONS.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether settings files and folders are created with owner only permissions
+pub fn restrictive_permissions() -> bool {
+    RESTRICTIVE_PERMISSIONS.load(Ordering::Relaxed)
+}
+
+#[cfg(unix)]
+/// Creates the settings folder and its missing parents, with mode `0o700` if `restrictive_permissions()` is enabled
+pub(crate) fn create_settings_dir(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    if restrictive_permissions() {
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(path)
+    } else {
+        fs::create_dir_all(path)
+    }
+}
+
+#[cfg(not(unix))]
+/// Creates the settings folder and its missing parents
+pub(crate) fn create_settings_dir(path: &Path) -> io::Result<()> {
+    fs::create_dir_all(path)
+}
+
+#[cfg(unix)]
+/// Creates or truncates a settings file, with mode `0o600` if `restrictive_permissions()` is enabled,
+/// which also tightens the permissions of files saved by older versions
+pub(crate) fn create_settings_file(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    if !restrictive_permissions() {
+        return File::create(path);
+    }
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.set_permissions(Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+/// Creates or truncates a settings file
+pub(crate) fn create_settings_file(path: &Path) -> io::Result<File> {
+    File::create(path)
+}
+
+#[cfg(unix)]
+/// Creates a settings file that must not exist yet, with mode `0o600` if `restrictive_permissions()` is enabled
+pub(crate) fn create_new_settings_file(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    if restrictive_permissions() {
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+#[cfg(not(unix))]
+/// Creates a settings file that must not exist yet
+pub(crate) fn create_new_settings_file(path: &Path) -> io::Result<File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+}
+
+/// Saves settings to `USER_HOME/crate_name/file_name`, then marks the file read-only to discourage manual edits.
+///
+/// A normal save will fail on a read-only file, so managed settings should always be re-saved with this function,
+/// which clears the read-only flag before writing and sets it again afterwards.
+/// To hand the file back to the user, call `set_settings_readonly(crate_name, file_name, false)`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::permissions::{save_settings_readonly, set_settings_readonly};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct ManagedSettings{
+/// server: String,
+/// }
+///
+/// let settings = ManagedSettings{ server: "https://example.com".to_string() };
+/// save_settings_readonly(env!("CARGO_CRATE_NAME"), "managed.ser", &settings).expect("Unable to save managed settings");
+///
+/// let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("managed.ser");
+/// assert!(std::fs::metadata(&path).unwrap().permissions().readonly());
+///
+/// // saving again clears and restores the read-only flag
+/// save_settings_readonly(env!("CARGO_CRATE_NAME"), "managed.ser", &settings).expect("Unable to re-save managed settings");
+///
+/// set_settings_readonly(env!("CARGO_CRATE_NAME"), "managed.ser", false).unwrap();
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "managed.ser").unwrap();
+/// ```
+pub fn save_settings_readonly<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    if is_read_only() {
+        return Err(SaveSettingsError::ReadOnlyMode);
+    }
+    let settings_file_path = settings_file_path(crate_name, file_name)?;
+    if settings_file_path.is_file() {
+        set_readonly(&settings_file_path, false).map_err(SaveSettingsError::IOError)?;
+    }
+    crate::save_settings_with_filename(crate_name, file_name, settings)?;
+    set_readonly(&settings_file_path, true).map_err(SaveSettingsError::IOError)
+}
+
+/// Sets or clears the read-only flag of the settings file at `USER_HOME/crate_name/file_name`
+pub fn set_settings_readonly(crate_name: &str, file_name: &str, readonly: bool) -> io::Result<()> {
+    if is_read_only() {
+        return Err(read_only_error());
+    }
+    set_readonly(&settings_file_path(crate_name, file_name)?, readonly)
+}
+
+/// Returns the path of the settings file in the users home
+fn settings_file_path(crate_name: &str, file_name: &str) -> Result<PathBuf, BackendError> {
+    get_settings_file_path(Path::new(crate_name), file_name)
+}
+
+/// Sets or clears the read-only flag of a file
+fn set_readonly(path: &Path, readonly: bool) -> io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    if readonly {
+        permissions.set_readonly(true);
+    } else {
+        make_writable(&mut permissions);
+    }
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(unix)]
+/// Gives the owner write permission, without making the file writable by everyone
+fn make_writable(permissions: &mut Permissions) {
+    use std::os::unix::fs::PermissionsExt;
+    permissions.set_mode(permissions.mode() | 0o200);
+}
+
+#[cfg(not(unix))]
+/// Clears the read-only flag
+fn make_writable(permissions: &mut Permissions) {
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+}
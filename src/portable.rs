@@ -0,0 +1,95 @@
+//! Portable mode source file, stores settings next to the executable instead of in the users home
+#![warn(missing_docs)]
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Global storage mode
+static STORAGE_MODE: RwLock<StorageMode> = RwLock::new(StorageMode::Home);
+
+/// Why `StorageMode::Auto` last fell back to the users home, if it did
+static STORAGE_WARNING: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Where the `FileSystemBackend` stores settings folders
+pub enum StorageMode {
+    /// Settings are stored in `USER_HOME/crate_name`, the default
+    #[default]
+    Home,
+    /// Settings are stored in `EXECUTABLE_DIR/crate_name`, e.g. for builds that run from a USB drive
+    Portable,
+    /// Settings are stored like `Portable` if a `PORTABLE_FLAG_FILE_NAME` file exists next to the executable, otherwise like `Home`.
+    /// If the directory of the executable can not be found, `Home` is used rather than failing,
+    /// and the reason is returned by `last_storage_warning()`, as well as logged with the `logging` feature.
+    Auto,
+}
+
+/// Name of the file that switches `StorageMode::Auto` to portable storage when it exists next to the executable
+pub const PORTABLE_FLAG_FILE_NAME: &str = "portable.flag";
+
+/// Sets where the `FileSystemBackend` stores settings folders, `StorageMode::Home` is the default.
+///
+/// In `StorageMode::Portable`, saving and loading fail with `FailedToGetExecutableDir` if the directory of the executable
+/// can not be found. `get_user_home()` is not affected by the storage mode, and neither is `testing::with_temp_settings_dir`.
+/// ```no_run
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::portable::{executable_dir, set_storage_mode, StorageMode};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// set_storage_mode(StorageMode::Portable);
+/// save_settings!(Settings{ setting1: 1 }, "portable.ser").unwrap();
+/// assert!(executable_dir().unwrap().join(env!("CARGO_CRATE_NAME")).join("portable.ser").is_file());
+/// ```
+pub fn set_storage_mode(mode: StorageMode) {
+    *STORAGE_MODE.write().unwrap() = mode;
+    set_storage_warning(None);
+}
+
+/// Returns where the `FileSystemBackend` currently stores settings folders
+pub fn get_storage_mode() -> StorageMode {
+    *STORAGE_MODE.read().unwrap()
+}
+
+/// Returns why `StorageMode::Auto` stored settings in the users home instead of checking for the portable flag,
+/// e.g. because the directory of the executable could not be found, so the program can tell the user.
+/// Returns `None` if the last settings folder was resolved without falling back, and after `set_storage_mode()`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::portable::last_storage_warning;
+///
+/// if let Some(warning) = last_storage_warning() {
+///     eprintln!("portable mode is unavailable: {}", warning);
+/// }
+/// ```
+pub fn last_storage_warning() -> Option<String> {
+    STORAGE_WARNING.read().unwrap().clone()
+}
+
+/// Sets or clears the reason returned by `last_storage_warning()`
+pub(crate) fn set_storage_warning(warning: Option<String>) {
+    *STORAGE_WARNING.write().unwrap() = warning;
+}
+
+/// Returns true if `dir` contains a `PORTABLE_FLAG_FILE_NAME` file, which `StorageMode::Auto` checks for next to the executable
+pub fn has_portable_flag(dir: &Path) -> bool {
+    dir.join(PORTABLE_FLAG_FILE_NAME).is_file()
+}
+
+/// Returns the directory containing the running executable, symlinks to the executable are resolved where the platform allows
+pub fn executable_dir() -> io::Result<PathBuf> {
+    let executable = std::env::current_exe()?;
+    match executable.parent() {
+        Some(executable_dir) => Ok(executable_dir.to_path_buf()),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} has no parent directory", executable.display()),
+        )),
+    }
+}
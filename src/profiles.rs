@@ -0,0 +1,182 @@
+//! Named profiles source file, e.g. separate "work" and "home" settings with one of them active
+//!
+//! Profiles are stored in `USER_HOME/crate_name/profiles/profile.toml`, and the active profile is recorded in
+//! `USER_HOME/crate_name/active_profile.toml`.
+#![warn(missing_docs)]
+
+use crate::{
+    delete_setting_file, list_settings_files, load_settings_in_dir, load_settings_with_filename,
+    save_settings_in_dir, save_settings_with_filename, update_all_settings, LoadSettingsError,
+    SaveSettingsError, SettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the file the active profile is recorded in
+pub const ACTIVE_PROFILE_FILE_NAME: &str = "active_profile.toml";
+
+/// Extension of profile files
+const PROFILE_EXTENSION: &str = "toml";
+
+#[derive(Serialize, Deserialize)]
+/// Contents of `ACTIVE_PROFILE_FILE_NAME`
+struct ActiveProfile {
+    profile: String,
+}
+
+/// Returns true if the profile name can safely be used as a file name: 1 to 64 ASCII letters, digits, `-`, or `_`
+pub fn is_valid_profile_name(profile: &str) -> bool {
+    !profile.is_empty()
+        && profile.len() <= 64
+        && profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the folder profiles of the crate are stored in, relative to the users home
+fn profiles_dir(crate_name: &str) -> PathBuf {
+    Path::new(crate_name).join("profiles")
+}
+
+/// Returns the file name of a profile
+fn profile_file_name(profile: &str) -> String {
+    format!("{}.{}", profile, PROFILE_EXTENSION)
+}
+
+/// Saves settings as the named profile
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::profiles::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// proxy: String,
+/// }
+///
+/// let crate_name = "doctest_profiles";
+/// save_profile(crate_name, "work", &Settings{ proxy: "proxy.example.com".to_string() }).unwrap();
+/// save_profile(crate_name, "home", &Settings{ proxy: "".to_string() }).unwrap();
+/// assert_eq!(list_profiles(crate_name).unwrap(), vec!["home".to_string(), "work".to_string()]);
+///
+/// set_active_profile(crate_name, "work").unwrap();
+/// assert_eq!(get_active_profile(crate_name).unwrap(), Some("work".to_string()));
+///
+/// let settings = load_active_profile::<Settings>(crate_name).unwrap();
+/// assert_eq!(settings.proxy, "proxy.example.com");
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn save_profile<T>(
+    crate_name: &str,
+    profile: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    if !is_valid_profile_name(profile) {
+        return Err(SaveSettingsError::InvalidProfileName(profile.to_string()));
+    }
+    save_settings_in_dir(
+        &profiles_dir(crate_name),
+        &profile_file_name(profile),
+        settings,
+    )
+}
+
+/// Loads the named profile
+///
+/// For example usage, see `save_profile()` documentation.
+pub fn load_profile<T>(crate_name: &str, profile: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    if !is_valid_profile_name(profile) {
+        return Err(LoadSettingsError::InvalidProfileName(profile.to_string()));
+    }
+    load_settings_in_dir(&profiles_dir(crate_name), &profile_file_name(profile))
+}
+
+/// Applies `f` to every saved profile and saves it again, see `update_all_settings()`, e.g. to scrub a leaked token from every profile
+pub fn update_all_profiles<T>(
+    crate_name: &str,
+    f: impl FnMut(&mut T),
+) -> Vec<(PathBuf, Result<(), SettingsError>)>
+where
+    for<'a> T: Serialize + Deserialize<'a>,
+{
+    update_all_settings(&profiles_dir(crate_name).to_string_lossy(), f)
+}
+
+/// Lists the names of every saved profile, sorted by name
+pub fn list_profiles(crate_name: &str) -> io::Result<Vec<String>> {
+    let profiles_dir = profiles_dir(crate_name);
+    let profiles = list_settings_files(&profiles_dir.to_string_lossy())?
+        .into_iter()
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == PROFILE_EXTENSION)
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|profile| is_valid_profile_name(profile))
+        .collect();
+    Ok(profiles)
+}
+
+/// Deletes the named profile, if it is the active profile the active profile is cleared as well
+pub fn delete_profile(crate_name: &str, profile: &str) -> io::Result<()> {
+    if !is_valid_profile_name(profile) {
+        return Err(invalid_profile_name(profile));
+    }
+    delete_setting_file(profiles_dir(crate_name), profile_file_name(profile))?;
+    if matches!(get_active_profile(crate_name), Ok(Some(active)) if active == profile) {
+        delete_setting_file(crate_name, ACTIVE_PROFILE_FILE_NAME)?;
+    }
+    Ok(())
+}
+
+/// Records the named profile as the active profile, the profile does not need to be saved yet
+pub fn set_active_profile(crate_name: &str, profile: &str) -> Result<(), SaveSettingsError> {
+    if !is_valid_profile_name(profile) {
+        return Err(SaveSettingsError::InvalidProfileName(profile.to_string()));
+    }
+    save_settings_with_filename(
+        crate_name,
+        ACTIVE_PROFILE_FILE_NAME,
+        &ActiveProfile {
+            profile: profile.to_string(),
+        },
+    )
+}
+
+/// Returns the name of the active profile, or `None` if no profile is active
+pub fn get_active_profile(crate_name: &str) -> Result<Option<String>, LoadSettingsError> {
+    match load_settings_with_filename::<ActiveProfile>(crate_name, ACTIVE_PROFILE_FILE_NAME) {
+        Ok(active) if is_valid_profile_name(&active.profile) => Ok(Some(active.profile)),
+        Ok(active) => Err(LoadSettingsError::InvalidProfileName(active.profile)),
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Loads the active profile, returning `NoActiveProfile` if no profile is active
+pub fn load_active_profile<T>(crate_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match get_active_profile(crate_name)? {
+        None => Err(LoadSettingsError::NoActiveProfile),
+        Some(profile) => load_profile(crate_name, &profile),
+    }
+}
+
+/// Returns an `InvalidInput` io error for an invalid profile name
+fn invalid_profile_name(profile: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{:?} is not a valid profile name", profile),
+    )
+}
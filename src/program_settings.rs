@@ -0,0 +1,88 @@
+//! `ProgramSettings` trait source file
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::{
+    delete_setting_file, load_settings_with_filename, save_settings_with_filename,
+    LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Alias of `ProgramSettings` for implementing the trait by hand without the `derive` feature.
+pub use self::ProgramSettings as Settings;
+
+/// Trait for a settings type that knows its own folder and file name, implemented by hand or with
+/// `#[derive(ProgramSettings)]` when the `derive` feature is enabled.
+///
+/// The folder is always explicit, unlike the macros which use `env!("CARGO_CRATE_NAME")`, so a settings type
+/// defined in a library crate still saves to the folder the application expects.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::program_settings::ProgramSettings;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct UiSettings{
+/// dark_mode: bool,
+/// scale: f32,
+/// }
+///
+/// impl ProgramSettings for UiSettings {
+///     const FOLDER: &'static str = "cr_program_settings_doctest";
+///     const FILE_NAME: &'static str = "ui.toml";
+/// }
+///
+/// let settings = UiSettings{ dark_mode: true, scale: 1.5 };
+///
+/// settings.save().expect("Unable to save ui settings");
+/// assert!(UiSettings::path().ends_with("cr_program_settings_doctest/ui.toml"));
+///
+/// let loaded_settings = UiSettings::load().expect("Unable to load ui settings");
+/// assert_eq!(settings,loaded_settings);
+///
+/// UiSettings::delete().expect("Unable to delete ui settings");
+/// ```
+pub trait ProgramSettings: Serialize + for<'a> Deserialize<'a> + Sized {
+    /// The name of the folder in the users home directory the settings are saved in
+    const FOLDER: &'static str;
+    /// The name of the file the settings are saved to
+    const FILE_NAME: &'static str;
+
+    /// Saves the settings to `USER_HOME/FOLDER/FILE_NAME`
+    fn save(&self) -> Result<(), SaveSettingsError> {
+        save_settings_with_filename(Self::FOLDER, Self::FILE_NAME, self)
+    }
+
+    /// Loads the settings from `USER_HOME/FOLDER/FILE_NAME`
+    fn load() -> Result<Self, LoadSettingsError> {
+        load_settings_with_filename(Self::FOLDER, Self::FILE_NAME)
+    }
+
+    /// Loads the settings from `USER_HOME/FOLDER/FILE_NAME`, returning `Self::default()` if loading fails for any reason
+    fn load_or_default() -> Self
+    where
+        Self: Default,
+    {
+        Self::load().unwrap_or_default()
+    }
+
+    /// Returns true if the settings file exists at `USER_HOME/FOLDER/FILE_NAME`
+    fn exists() -> bool {
+        get_backend().exists(Path::new(Self::FOLDER), Self::FILE_NAME)
+    }
+
+    /// Deletes the settings file at `USER_HOME/FOLDER/FILE_NAME`
+    fn delete() -> io::Result<()> {
+        delete_setting_file(Self::FOLDER, Self::FILE_NAME)
+    }
+
+    /// Returns the path the settings are saved to, this path is relative if the users home could not be found
+    fn path() -> PathBuf {
+        get_backend()
+            .resolve(Path::new(Self::FOLDER))
+            .unwrap_or_else(|| PathBuf::from(Self::FOLDER))
+            .join(Self::FILE_NAME)
+    }
+}
@@ -0,0 +1,107 @@
+//! Project-local settings source file, settings files found by walking up from the current directory
+#![warn(missing_docs)]
+
+use crate::layered::merge_values;
+use crate::{
+    deserialize_settings, load_settings_with_filename, read_settings_bytes, LoadSettingsError,
+};
+use serde::Deserialize;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Looks for `file_name` in the current directory and each of its parents, returning the path of the closest match,
+/// or `None` if the filesystem root is reached without finding the file or the current directory can not be read.
+pub fn find_project_settings(file_name: &str) -> Option<PathBuf> {
+    find_project_settings_from(&std::env::current_dir().ok()?, file_name)
+}
+
+/// Looks for `file_name` in `start_dir` and each of its parents, returning the path of the closest match.
+///
+/// `start_dir` is canonicalized first, so symlinked directories are walked through their real parents rather than
+/// the parents of the link, and the search always ends at the filesystem root.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::project::find_project_settings_from;
+///
+/// let project_dir = std::env::temp_dir().join("cr_program_settings_doctest_project");
+/// let nested_dir = project_dir.join("src").join("bin");
+/// std::fs::create_dir_all(&nested_dir).unwrap();
+/// std::fs::write(project_dir.join(".doctest_tool.toml"), "verbose = true\n").unwrap();
+///
+/// let found = find_project_settings_from(&nested_dir, ".doctest_tool.toml").unwrap();
+/// assert_eq!(found, project_dir.canonicalize().unwrap().join(".doctest_tool.toml"));
+///
+/// std::fs::remove_dir_all(project_dir).unwrap();
+/// ```
+pub fn find_project_settings_from(start_dir: &Path, file_name: &str) -> Option<PathBuf> {
+    let start_dir = start_dir
+        .canonicalize()
+        .unwrap_or_else(|_| start_dir.to_path_buf());
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(file_name))
+        .find(|path| path.is_file())
+}
+
+/// Loads the project settings file found by `find_project_settings()`, or the user settings at
+/// `USER_HOME/crate_name/file_name` if there is no project settings file.
+///
+/// The path of the loaded file is recorded in `SETTINGS_PATHS`. See `load_settings_project_merged()`
+/// to merge the project settings over the user settings instead of picking one of them.
+pub fn load_settings_project_or_user<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    match find_project_settings(file_name) {
+        Some(project_file_path) => load_project_file(project_file_path),
+        None => load_settings_with_filename(crate_name, file_name),
+    }
+}
+
+/// Loads settings by merging the project settings file found by `find_project_settings()` over the user settings
+/// at `USER_HOME/crate_name/file_name`, so a project only needs to override the settings it cares about.
+///
+/// Tables are merged recursively, while every other value, including arrays, in the project file replaces the user value.
+/// Either file may be missing, if both are missing the `NotFound` io error of the user settings is returned.
+pub fn load_settings_project_merged<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let project_value = find_project_settings(file_name)
+        .map(load_project_file::<Value>)
+        .transpose()?;
+
+    let mut value = match read_settings_bytes(Path::new(crate_name), file_name) {
+        Ok((settings_file_path, file_data)) => {
+            deserialize_settings::<Value>(settings_file_path, file_data)?
+        }
+        Err(LoadSettingsError::IOError(err))
+            if err.kind() == io::ErrorKind::NotFound && project_value.is_some() =>
+        {
+            Value::Table(Default::default())
+        }
+        Err(err) => return Err(err),
+    };
+    if let Some(project_value) = project_value {
+        merge_values(&mut value, project_value);
+    }
+    value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
+
+/// Loads a project settings file, recording its path in `SETTINGS_PATHS`
+fn load_project_file<T>(project_file_path: PathBuf) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let file_data = std::fs::read(&project_file_path).map_err(LoadSettingsError::IOError)?;
+    deserialize_settings(project_file_path, file_data)
+}
@@ -0,0 +1,179 @@
+//! Settings registry source file, lets plugins register their settings so a host program can save and load them all together
+#![warn(missing_docs)]
+
+use crate::{
+    load_settings_with_filename, save_settings_with_filename, LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// Settings registered with `register_settings_type()`, shared between the registry and the plugin that registered them
+pub type SharedSettings<T> = Arc<RwLock<T>>;
+
+/// Global settings registry, in registration order
+static REGISTRY: RwLock<Vec<Arc<dyn RegisteredSettings + Send + Sync>>> = RwLock::new(Vec::new());
+
+/// Type erased operations on a registered settings type
+trait RegisteredSettings {
+    /// Returns the name the settings are registered under, `crate_name/file_name`
+    fn name(&self) -> &str;
+    /// Saves the current settings to their file
+    fn save(&self) -> Result<(), SaveSettingsError>;
+    /// Replaces the current settings with the contents of their file
+    fn load(&self) -> Result<(), LoadSettingsError>;
+    /// Replaces the current settings with their default
+    fn reset(&self);
+}
+
+/// A registered settings type, along with where it is stored
+struct RegistryEntry<T> {
+    name: String,
+    crate_name: String,
+    file_name: String,
+    settings: SharedSettings<T>,
+}
+
+impl<T> RegisteredSettings for RegistryEntry<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Default,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn save(&self) -> Result<(), SaveSettingsError> {
+        let settings = self.settings.read().unwrap();
+        save_settings_with_filename(&self.crate_name, &self.file_name, &*settings)
+    }
+
+    fn load(&self) -> Result<(), LoadSettingsError> {
+        let loaded = load_settings_with_filename::<T>(&self.crate_name, &self.file_name)?;
+        *self.settings.write().unwrap() = loaded;
+        Ok(())
+    }
+
+    fn reset(&self) {
+        *self.settings.write().unwrap() = T::default();
+    }
+}
+
+/// Registers a settings type stored at `USER_HOME/crate_name/file_name` under the name `crate_name/file_name`,
+/// returning the shared settings, which start as `T::default()` until `load_all_registered()` is called.
+///
+/// The registry keeps its own handle to the settings, so the host program can save, load, and reset every registered
+/// type without knowing them, while each plugin reads and changes its settings through the returned handle.
+/// Registering the same name again replaces the previous registration.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::registry::{load_all_registered, register_settings_type, save_all_registered, unregister_settings};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Default)]
+/// struct AudioPluginSettings{
+/// volume: u32,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug, Default)]
+/// struct VideoPluginSettings{
+/// fullscreen: bool,
+/// }
+///
+/// // each plugin registers its own settings at startup
+/// let audio = register_settings_type::<AudioPluginSettings>(env!("CARGO_CRATE_NAME"), "audio.ser");
+/// let video = register_settings_type::<VideoPluginSettings>(env!("CARGO_CRATE_NAME"), "video.ser");
+///
+/// audio.write().unwrap().volume = 70;
+/// video.write().unwrap().fullscreen = true;
+///
+/// // the host saves every plugin at once
+/// save_all_registered().unwrap();
+///
+/// audio.write().unwrap().volume = 0;
+/// load_all_registered().unwrap();
+/// assert_eq!(audio.read().unwrap().volume, 70);
+///
+/// unregister_settings(&format!("{}/audio.ser", env!("CARGO_CRATE_NAME")));
+/// unregister_settings(&format!("{}/video.ser", env!("CARGO_CRATE_NAME")));
+/// ```
+pub fn register_settings_type<T>(crate_name: &str, file_name: &str) -> SharedSettings<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Default + Send + Sync + 'static,
+{
+    let settings = Arc::new(RwLock::new(T::default()));
+    let entry = Arc::new(RegistryEntry {
+        name: format!("{}/{}", crate_name, file_name),
+        crate_name: crate_name.to_string(),
+        file_name: file_name.to_string(),
+        settings: settings.clone(),
+    });
+    let mut registry = REGISTRY.write().unwrap();
+    match registry
+        .iter_mut()
+        .find(|registered| registered.name() == entry.name)
+    {
+        Some(registered) => *registered = entry,
+        None => registry.push(entry),
+    }
+    settings
+}
+
+/// Removes a settings type from the registry by its name, `crate_name/file_name`, returning true if it was registered.
+/// The settings file is left untouched.
+pub fn unregister_settings(name: &str) -> bool {
+    let mut registry = REGISTRY.write().unwrap();
+    let count = registry.len();
+    registry.retain(|registered| registered.name() != name);
+    registry.len() != count
+}
+
+/// Returns the names of every registered settings type, `crate_name/file_name`, in registration order
+pub fn registered_settings() -> Vec<String> {
+    registry_snapshot()
+        .iter()
+        .map(|registered| registered.name().to_string())
+        .collect()
+}
+
+/// Saves every registered settings type, continuing past failures.
+/// Returns the name and error of each settings type that could not be saved.
+pub fn save_all_registered() -> Result<(), Vec<(String, SaveSettingsError)>> {
+    collect_errors(|registered| registered.save())
+}
+
+/// Loads every registered settings type from its file, continuing past failures, e.g. a plugin that has never been saved.
+/// Settings that fail to load keep their current value, and the name and error of each are returned.
+pub fn load_all_registered() -> Result<(), Vec<(String, LoadSettingsError)>> {
+    collect_errors(|registered| registered.load())
+}
+
+/// Resets every registered settings type to its default, without saving
+pub fn reset_all_registered() {
+    for registered in registry_snapshot() {
+        registered.reset();
+    }
+}
+
+/// Returns the registered settings, so they can be used without holding the registry lock
+fn registry_snapshot() -> Vec<Arc<dyn RegisteredSettings + Send + Sync>> {
+    REGISTRY.read().unwrap().clone()
+}
+
+/// Runs an operation on every registered settings type, collecting the errors by name
+fn collect_errors<E>(
+    operation: impl Fn(&dyn RegisteredSettings) -> Result<(), E>,
+) -> Result<(), Vec<(String, E)>> {
+    let errors: Vec<(String, E)> = registry_snapshot()
+        .iter()
+        .filter_map(|registered| {
+            operation(registered.as_ref())
+                .err()
+                .map(|err| (registered.name().to_string(), err))
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,227 @@
+//! OS keyring secrets source file, stores `Secret` fields in the OS keyring instead of the settings file
+//!
+//! In `SecretStorage::Keyring` mode, each `Secret` is saved to the keyring with the crate name as the service and
+//! `file_name/field.path` as the user, and the settings file only contains a `"keyring:crate_name/file_name/field.path"` placeholder.
+#![warn(missing_docs)]
+
+use crate::{
+    is_read_only, read_settings_bytes, serialize_settings, track_settings_path,
+    write_settings_bytes, LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::RwLock;
+
+pub use keyring;
+
+/// Prefix of the placeholders written to the settings file in place of secrets
+pub const KEYRING_PLACEHOLDER_PREFIX: &str = "keyring:";
+
+/// Prefix `Secret` adds while being saved by `save_settings_with_secrets()`, so secrets can be found in the serialized settings
+const SECRET_MARKER: &str = "\u{1}cr_program_settings_secret:";
+
+thread_local! {
+    /// Whether `Secret` values are currently being serialized with `SECRET_MARKER`
+    static MARK_SECRETS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Global secret storage mode
+static SECRET_STORAGE: RwLock<SecretStorage> = RwLock::new(SecretStorage::Keyring);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where `save_settings_with_secrets()` stores `Secret` fields
+pub enum SecretStorage {
+    /// Secrets are stored in the OS keyring, e.g. Keychain, Credential Manager, or the kernel keyring on linux
+    Keyring,
+    /// Secrets are stored in plaintext in the settings file, for headless environments such as CI that have no keyring
+    Plaintext,
+}
+
+/// Sets where `save_settings_with_secrets()` stores `Secret` fields, `SecretStorage::Keyring` is the default.
+/// Placeholders already in a settings file are always loaded from the keyring.
+pub fn set_secret_storage(storage: SecretStorage) {
+    *SECRET_STORAGE.write().unwrap() = storage;
+}
+
+/// Returns where `save_settings_with_secrets()` currently stores `Secret` fields
+pub fn get_secret_storage() -> SecretStorage {
+    *SECRET_STORAGE.read().unwrap()
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
+/// A secret settings field, e.g. an API token.
+/// Saved with `save_settings_with_secrets()`, it is stored in the OS keyring, while with any other save function it is
+/// saved as a plain string.
+pub struct Secret(pub String);
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // never print the secret itself
+        f.write_str("Secret(..)")
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if MARK_SECRETS.with(|mark_secrets| mark_secrets.get()) {
+            serializer.serialize_str(&format!("{}{}", SECRET_MARKER, self.0))
+        } else {
+            serializer.serialize_str(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let secret = String::deserialize(deserializer)?;
+        match secret.strip_prefix(SECRET_MARKER) {
+            Some(secret) => Ok(Secret(secret.to_string())),
+            None => Ok(Secret(secret)),
+        }
+    }
+}
+
+/// Enables `SECRET_MARKER` on the current thread until dropped
+struct MarkSecretsGuard;
+
+impl MarkSecretsGuard {
+    fn new() -> Self {
+        MARK_SECRETS.with(|mark_secrets| mark_secrets.set(true));
+        Self
+    }
+}
+
+impl Drop for MarkSecretsGuard {
+    fn drop(&mut self) {
+        MARK_SECRETS.with(|mark_secrets| mark_secrets.set(false));
+    }
+}
+
+/// Calls `f` with the dot separated path and value of every string in the toml value
+fn for_each_string<E>(
+    value: &mut toml::Value,
+    path: &mut Vec<String>,
+    f: &mut impl FnMut(&str, &mut String) -> Result<(), E>,
+) -> Result<(), E> {
+    match value {
+        toml::Value::String(string) => f(&path.join("."), string),
+        toml::Value::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                path.push(index.to_string());
+                let result = for_each_string(value, path, f);
+                path.pop();
+                result?;
+            }
+            Ok(())
+        }
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                path.push(key.clone());
+                let result = for_each_string(value, path, f);
+                path.pop();
+                result?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Saves settings to `USER_HOME/crate_name/file_name`, storing every `Secret` field according to `get_secret_storage()`
+/// ```no_run
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::secrets::{load_settings_with_secrets, save_settings_with_secrets, Secret};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct ApiSettings{
+/// endpoint: String,
+/// token: Secret,
+/// }
+///
+/// let settings = ApiSettings{ endpoint: "https://example.com".to_string(), token: Secret("hunter2".to_string()) };
+///
+/// // the settings file contains `token = "keyring:my_app/api.ser/token"`
+/// save_settings_with_secrets("my_app", "api.ser", &settings).expect("Unable to save settings");
+///
+/// let loaded = load_settings_with_secrets::<ApiSettings>("my_app", "api.ser").expect("Unable to load settings");
+/// assert_eq!(settings, loaded);
+/// ```
+pub fn save_settings_with_secrets<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    // the keyring is written before the settings file, so check up front
+    if is_read_only() {
+        return Err(SaveSettingsError::ReadOnlyMode);
+    }
+    let mut value = {
+        let _mark_secrets = MarkSecretsGuard::new();
+        toml::Value::try_from(settings).map_err(SaveSettingsError::SerializationError)?
+    };
+    let storage = get_secret_storage();
+    for_each_string::<SaveSettingsError>(&mut value, &mut vec![], &mut |path, string| {
+        if let Some(secret) = string.strip_prefix(SECRET_MARKER) {
+            *string = match storage {
+                SecretStorage::Keyring => {
+                    let user = format!("{}/{}", file_name, path);
+                    keyring::Entry::new(crate_name, &user)
+                        .and_then(|entry| entry.set_password(secret))
+                        .map_err(SaveSettingsError::KeyringError)?;
+                    format!("{}{}/{}", KEYRING_PLACEHOLDER_PREFIX, crate_name, user)
+                }
+                SecretStorage::Plaintext => secret.to_string(),
+            };
+        }
+        Ok(())
+    })?;
+    let serialized_data = serialize_settings(&value)?;
+    write_settings_bytes(Path::new(crate_name), file_name, serialized_data.as_bytes())
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, fetching every keyring placeholder from the OS keyring.
+/// A missing keyring entry, or a locked or unavailable keyring, is reported as `LoadSettingsError::KeyringError`.
+///
+/// For example usage, see `save_settings_with_secrets()` documentation.
+pub fn load_settings_with_secrets<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let file_data = String::from_utf8(file_data).map_err(|err| {
+        LoadSettingsError::IOError(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    })?;
+    let mut value = toml::from_str::<toml::Value>(&file_data)
+        .map_err(LoadSettingsError::DeserializationError)?;
+    for_each_string::<LoadSettingsError>(&mut value, &mut vec![], &mut |_path, string| {
+        if let Some(placeholder) = string.strip_prefix(KEYRING_PLACEHOLDER_PREFIX) {
+            let (service, user) = placeholder.split_once('/').unwrap_or((placeholder, ""));
+            let secret = keyring::Entry::new(service, user)
+                .and_then(|entry| entry.get_password())
+                .map_err(LoadSettingsError::KeyringError)?;
+            *string = format!("{}{}", SECRET_MARKER, secret);
+        }
+        Ok(())
+    })?;
+    let settings = value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)?;
+    track_settings_path(settings_file_path);
+    Ok(settings)
+}
@@ -0,0 +1,135 @@
+//! Settings sections source file, stores several settings as the top level tables of one file, e.g. `[database]` and `[ui]`
+#![warn(missing_docs)]
+
+use crate::{
+    load_settings_raw, save_settings_raw, LoadSettingsError, SaveSettingsError, SettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use toml::value::Table;
+use toml::Value;
+
+/// Saves settings as the section `section_name` of the settings file `USER_HOME/crate_name/file_name`,
+/// replacing the section if it exists and keeping every other section of the file. The file is created if it does not exist.
+/// The settings must be a struct or map, as a section is a TOML table.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::sections::{delete_section, load_section, save_section};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Database{
+/// host: String,
+/// port: u16,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Ui{
+/// dark_mode: bool,
+/// }
+///
+/// let database = Database{ host: "localhost".to_string(), port: 5432 };
+/// save_section(env!("CARGO_CRATE_NAME"), "sections.toml", "database", &database).unwrap();
+/// save_section(env!("CARGO_CRATE_NAME"), "sections.toml", "ui", &Ui{ dark_mode: true }).unwrap();
+///
+/// assert_eq!(
+///     read_settings_string(env!("CARGO_CRATE_NAME"), "sections.toml").unwrap(),
+///     "[database]\nhost = \"localhost\"\nport = 5432\n\n[ui]\ndark_mode = true\n"
+/// );
+/// assert_eq!(load_section::<Database>(env!("CARGO_CRATE_NAME"), "sections.toml", "database").unwrap(), database);
+///
+/// assert!(delete_section(env!("CARGO_CRATE_NAME"), "sections.toml", "ui").unwrap());
+/// assert!(matches!(
+///     load_section::<Ui>(env!("CARGO_CRATE_NAME"), "sections.toml", "ui"),
+///     Err(LoadSettingsError::SectionNotFound(_))
+/// ));
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "sections.toml").unwrap();
+/// ```
+pub fn save_section<T>(
+    crate_name: &str,
+    file_name: &str,
+    section_name: &str,
+    settings: &T,
+) -> Result<(), SettingsError>
+where
+    T: Serialize,
+{
+    let section = Value::try_from(settings).map_err(SaveSettingsError::SerializationError)?;
+    if !section.is_table() {
+        return Err(SaveSettingsError::RootNotATable(section.type_str().to_string()).into());
+    }
+    let mut root = match load_settings_raw(crate_name, file_name) {
+        Ok(root) => root,
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+            Value::Table(Table::new())
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if let Value::Table(table) = &mut root {
+        table.insert(section_name.to_string(), section);
+    }
+    save_settings_raw(crate_name, file_name, &root)?;
+    Ok(())
+}
+
+/// Loads the section `section_name` of the settings file `USER_HOME/crate_name/file_name`,
+/// returning `SectionNotFound` if the file has no such section.
+///
+/// For example usage, see `save_section()` documentation.
+pub fn load_section<T>(
+    crate_name: &str,
+    file_name: &str,
+    section_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let mut root = load_settings_raw(crate_name, file_name)?;
+    let section = root
+        .as_table_mut()
+        .and_then(|table| table.remove(section_name))
+        .ok_or_else(|| LoadSettingsError::SectionNotFound(section_name.to_string()))?;
+    section
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
+
+/// Removes the section `section_name` from the settings file `USER_HOME/crate_name/file_name`, keeping every other section.
+/// Returns true if the section existed, the file is only written when it did.
+///
+/// For example usage, see `save_section()` documentation.
+pub fn delete_section(
+    crate_name: &str,
+    file_name: &str,
+    section_name: &str,
+) -> Result<bool, SettingsError> {
+    let mut root = load_settings_raw(crate_name, file_name)?;
+    let removed = root
+        .as_table_mut()
+        .and_then(|table| table.remove(section_name))
+        .is_some();
+    if removed {
+        save_settings_raw(crate_name, file_name, &root)?;
+    }
+    Ok(removed)
+}
+
+/// Lists the names of every section of the settings file `USER_HOME/crate_name/file_name`, sorted by name.
+/// Keys of the file that are not tables, e.g. `version = 2`, are not sections.
+pub fn list_sections(crate_name: &str, file_name: &str) -> Result<Vec<String>, LoadSettingsError> {
+    let root = load_settings_raw(crate_name, file_name)?;
+    let mut sections: Vec<String> = root
+        .as_table()
+        .map(|table| {
+            table
+                .iter()
+                .filter(|(_, value)| value.is_table())
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    sections.sort();
+    Ok(sections)
+}
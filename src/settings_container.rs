@@ -1,13 +1,26 @@
 //! `SettingsContainer` source file
 #![warn(missing_docs)]
 
+use crate::backend::{get_backend, BackendError};
+use crate::options::{
+    read_settings_with_options, write_settings_with_options, LoadOptions, SaveOptions,
+};
+use crate::validation::Validate;
 use crate::{
-    load_settings_with_filename, save_settings_with_filename, LoadSettingsError, SaveSettingsError,
+    delete_setting_file, deserialize_settings, quarantine_settings_file, settings_modified_time,
+    LoadSettingsError, SaveSettingsError,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 /// Struct that handles saving and loading.
-#[derive(Serialize, Deserialize, PartialEq, Debug, Eq)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct SettingsContainer<T> {
     /// Generic settings inner field.
     settings: Option<T>,
@@ -15,38 +28,168 @@ pub struct SettingsContainer<T> {
     crate_name: String,
     /// The filename to save this struct
     file_name: String,
+    /// Hash of the file contents as of the last load or save, used by `save_checked()` to detect external edits
+    #[serde(skip)]
+    disk_hash: Mutex<Option<u64>>,
+    /// Whether the settings were changed since the container was last loaded or saved
+    #[serde(skip)]
+    dirty: AtomicBool,
+}
+
+impl<T: PartialEq> PartialEq for SettingsContainer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.settings == other.settings
+            && self.crate_name == other.crate_name
+            && self.file_name == other.file_name
+    }
+}
+
+impl<T: Eq> Eq for SettingsContainer<T> {}
+
+/// Hashes the contents of a settings file
+fn content_hash(file_data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    file_data.hash(&mut hasher);
+    hasher.finish()
 }
 
 impl<T> SettingsContainer<T>
 where
     for<'a> T: Serialize + Deserialize<'a>,
 {
-    /// Creates a new `SettingsContainer`
-    pub fn new(content: T, crate_name: &str, file_name: &str) -> Self {
+    /// Creates a new `SettingsContainer`, the names can be given as `&str`, `String`, or `&String`.
+    /// The new settings have not been saved yet, so the container starts out dirty.
+    pub fn new(content: T, crate_name: impl AsRef<str>, file_name: impl AsRef<str>) -> Self {
         Self {
             settings: Some(content),
-            crate_name: crate_name.to_string(),
-            file_name: file_name.to_string(),
+            crate_name: crate_name.as_ref().to_string(),
+            file_name: file_name.as_ref().to_string(),
+            disk_hash: Mutex::new(None),
+            dirty: AtomicBool::new(true),
         }
     }
 
+    /// Creates a new `SettingsContainer` with the same names `save_settings!()` uses, saved as `<folder>.ser`
+    /// in the folder set with `set_default_folder()`, or `crate_name` if none was set.
+    /// Use the `settings_container!()` macro to fill in the name of the calling crate.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::with_default_names(1u32, "my_crate");
+    /// assert_eq!(settings.crate_name(), "my_crate");
+    /// assert_eq!(settings.file_name(), "my_crate.ser");
+    /// ```
+    pub fn with_default_names(content: T, crate_name: &str) -> Self {
+        let folder = crate::default_folder(crate_name);
+        let file_name = format!("{}.ser", folder);
+        Self::new(content, folder, file_name)
+    }
+
     /// Gets the settings optional within the struct
     pub fn get_settings(&self) -> &Option<T> {
         &self.settings
     }
 
-    /// Gets the mutable settings optional
+    /// Gets the mutable settings optional, marking the container dirty since the settings may be changed through it
     pub fn get_mut_settings(&mut self) -> Option<&mut T> {
+        self.mark_dirty();
         self.settings.as_mut()
     }
 
-    /// Sets the settings optional within the struct
+    /// Sets the settings optional within the struct, marking the container dirty
     pub fn set_settings(&mut self, settings: T) {
         self.settings = Some(settings);
+        self.mark_dirty();
+    }
+
+    /// Modifies the inner settings if present, marking the container dirty
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::new(1u32,env!("CARGO_CRATE_NAME"),"doctest_dirty.ser");
+    /// settings.save().unwrap();
+    /// let mut settings = SettingsContainer::<u32>::load(env!("CARGO_CRATE_NAME"),"doctest_dirty.ser").unwrap();
+    /// assert!(!settings.is_dirty());
+    ///
+    /// settings.modify(|value| *value += 1);
+    /// // e.g. ask the user "save before exit?"
+    /// assert!(settings.is_dirty());
+    ///
+    /// settings.save().unwrap();
+    /// assert!(!settings.is_dirty());
+    /// settings.delete().unwrap();
+    /// ```
+    pub fn modify(&mut self, f: impl FnOnce(&mut T)) {
+        if let Some(settings) = self.settings.as_mut() {
+            f(settings);
+            self.mark_dirty();
+        }
+    }
+
+    /// Takes the settings out of the struct, leaving `None` in its place and marking the container dirty
+    pub fn take_settings(&mut self) -> Option<T> {
+        self.mark_dirty();
+        self.settings.take()
+    }
+
+    /// Returns true if the settings were changed, or may have been changed through `get_mut_settings()`,
+    /// since the container was last loaded or saved
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    /// Marks the container as having unsaved changes, e.g. after changing the settings through interior mutability
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the name of the folder the container is saved in
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::<u32>::try_load_or_default(env!("CARGO_CRATE_NAME"),"doctest_getters.ser");
+    /// assert_eq!(settings.crate_name(), env!("CARGO_CRATE_NAME"));
+    /// assert_eq!(settings.file_name(), "doctest_getters.ser");
+    /// ```
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Returns the name of the file the container is saved to
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Transforms the settings within the struct if present, keeping the same `crate_name` and `file_name`.
+    /// The returned container is dirty.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::new(1u32,env!("CARGO_CRATE_NAME"),"doctest_map_settings.ser");
+    ///
+    /// let mut migrated = settings.map_settings(|version| version + 1);
+    /// assert_eq!(migrated.get_settings(), &Some(2));
+    ///
+    /// assert_eq!(migrated.take_settings(), Some(2));
+    /// assert_eq!(migrated.get_settings(), &None);
+    /// ```
+    pub fn map_settings(self, f: impl FnOnce(T) -> T) -> Self {
+        Self {
+            settings: self.settings.map(f),
+            crate_name: self.crate_name,
+            file_name: self.file_name,
+            disk_hash: self.disk_hash,
+            dirty: AtomicBool::new(true),
+        }
     }
 
     /// Attempts to load a settings container, if it fails, it will return a default `SettingsContainer`
     /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
     /// use serde::{Deserialize, Serialize};
     /// use cr_program_settings::settings_container::SettingsContainer;
     ///
@@ -79,12 +222,121 @@ where
         }
     }
 
+    /// Attempts to load a settings container like `try_load_or_default()`, also returning the error that caused the fallback, if any.
+    /// Use `LoadSettingsError::is_not_found()` and `LoadSettingsError::is_corrupt()` to tell a first run from a corrupt file.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let (settings, reason) = SettingsContainer::<u32>::try_load_or_default_with_reason(env!("CARGO_CRATE_NAME"),"not_a_settings_file.ser");
+    /// assert_eq!(settings.get_settings(), &None);
+    /// assert!(reason.unwrap().is_not_found());
+    /// ```
+    pub fn try_load_or_default_with_reason(
+        crate_name: &str,
+        file_name: &str,
+    ) -> (Self, Option<LoadSettingsError>) {
+        match SettingsContainer::<T>::load(crate_name, file_name) {
+            Ok(settings_container) => (settings_container, None),
+            Err(err) => (Self::default(crate_name, file_name), Some(err)),
+        }
+    }
+
+    /// Attempts to load a settings container like `try_load_or_default()`, moving a corrupt file out of the way
+    /// with `quarantine_settings_file()` before falling back, so saving the default container does not destroy the user's data.
+    /// Returns the path of the quarantined copy, if the file was corrupt and could be moved.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let (settings, quarantined) = SettingsContainer::<u32>::try_load_or_default_quarantined(env!("CARGO_CRATE_NAME"),"not_a_settings_file.ser");
+    /// assert_eq!(settings.get_settings(), &None);
+    /// // a missing file is not corrupt, so there is nothing to quarantine
+    /// assert_eq!(quarantined, None);
+    /// ```
+    pub fn try_load_or_default_quarantined(
+        crate_name: &str,
+        file_name: &str,
+    ) -> (Self, Option<PathBuf>) {
+        let (settings_container, reason) =
+            Self::try_load_or_default_with_reason(crate_name, file_name);
+        let quarantined = match reason {
+            Some(err) if err.is_corrupt() => quarantine_settings_file(crate_name, file_name).ok(),
+            _ => None,
+        };
+        (settings_container, quarantined)
+    }
+
+    /// Attempts to load a settings container, if it fails, it will return a container holding the settings returned by `f`.
+    /// The fallback settings are not saved, so the container is dirty.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::<String>::try_load_or_else(env!("CARGO_CRATE_NAME"),"not_a_settings_file.ser", || "en-US".to_string());
+    /// assert_eq!(settings.get_settings(), &Some("en-US".to_string()));
+    /// assert!(settings.is_dirty());
+    /// ```
+    pub fn try_load_or_else(crate_name: &str, file_name: &str, f: impl FnOnce() -> T) -> Self {
+        Self::try_load_or_else_with_error(crate_name, file_name, |_| f())
+    }
+
+    /// Attempts to load a settings container like `try_load_or_else()`, handing `f` the error that caused the fallback
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::<u32>::try_load_or_else_with_error(env!("CARGO_CRATE_NAME"),"not_a_settings_file.ser", |err| {
+    ///     assert!(matches!(err, LoadSettingsError::IOError(_)));
+    ///     1
+    /// });
+    /// assert_eq!(settings.get_settings(), &Some(1));
+    /// ```
+    pub fn try_load_or_else_with_error(
+        crate_name: &str,
+        file_name: &str,
+        f: impl FnOnce(LoadSettingsError) -> T,
+    ) -> Self {
+        match SettingsContainer::<T>::load(crate_name, file_name) {
+            Ok(settings_container) => settings_container,
+            Err(err) => Self::new(f(err), crate_name, file_name),
+        }
+    }
+
+    /// Attempts to load a settings container like `try_load_or_else()`, saving the fallback settings right away
+    /// so the file exists for the user to edit. A file that failed to load is overwritten by the fallback settings.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::<u32>::try_load_or_else_and_save(env!("CARGO_CRATE_NAME"),"doctest_or_else_and_save.ser", || 1)
+    ///     .expect("Failed to save fallback settings");
+    /// assert!(!settings.is_dirty());
+    /// assert!(settings.exists());
+    /// # settings.delete().unwrap();
+    /// ```
+    pub fn try_load_or_else_and_save(
+        crate_name: &str,
+        file_name: &str,
+        f: impl FnOnce() -> T,
+    ) -> Result<Self, SaveSettingsError> {
+        let settings_container = Self::try_load_or_else(crate_name, file_name, f);
+        if settings_container.is_dirty() {
+            settings_container.save()?;
+        }
+        Ok(settings_container)
+    }
+
     /// Returns a default `SettingsContainer`
     pub fn default(crate_name: &str, file_name: &str) -> Self {
         Self {
             settings: None,
             crate_name: crate_name.to_string(),
             file_name: file_name.to_string(),
+            disk_hash: Mutex::new(None),
+            dirty: AtomicBool::new(false),
         }
     }
 
@@ -92,11 +344,17 @@ where
     /// For a `unwrap_or_default` style, use try_load_or_default()
     /// For example usage, see save() or try_load_or_default() documentation
     pub fn load(crate_name: &str, file_name: &str) -> Result<Self, LoadSettingsError> {
-        load_settings_with_filename(crate_name, file_name)
+        let (settings_file_path, file_data) =
+            read_settings_with_options(Path::new(crate_name), file_name, &LoadOptions::default())?;
+        let hash = content_hash(&file_data);
+        let container = deserialize_settings::<Self>(settings_file_path, file_data)?;
+        *container.disk_hash.lock().unwrap() = Some(hash);
+        Ok(container)
     }
 
     /// Saves a settings container using its `crate_name` and `file_name` within the struct.
     /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
     /// use cr_program_settings::settings_container::SettingsContainer;
     ///
     /// let settings = SettingsContainer::new("some_cool_data".to_string(),env!("CARGO_CRATE_NAME"),"doctest_save_settings.ser");
@@ -108,6 +366,212 @@ where
     /// assert_eq!(settings,loaded_settings);
     /// ```
     pub fn save(&self) -> Result<(), SaveSettingsError> {
-        save_settings_with_filename(&self.crate_name, &self.file_name, self)
+        let mut disk_hash = self.disk_hash.lock().unwrap();
+        self.write(&mut disk_hash)
+    }
+
+    /// Saves the settings container like `save()`, overwriting the file even if it was modified outside the program,
+    /// for use after `save_checked()` reported a conflict that the user chose to discard.
+    pub fn force_save(&self) -> Result<(), SaveSettingsError> {
+        self.save()
+    }
+
+    /// Saves the settings container only if the file has not been modified outside the program since this container
+    /// was loaded or last saved, otherwise returns `SaveSettingsError::ConflictDetected` without writing.
+    /// A container that has never been loaded or saved conflicts with any existing file, while a file deleted
+    /// outside the program is simply recreated.
+    ///
+    /// On a conflict, use `load_theirs()` to fetch the file as it is on disk to merge with or ask the user about,
+    /// or `force_save()` to overwrite it.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let mut ours = SettingsContainer::new(1u32,env!("CARGO_CRATE_NAME"),"doctest_save_checked.ser");
+    /// ours.force_save().unwrap();
+    ///
+    /// // the user edits the file while the program is running
+    /// let theirs = SettingsContainer::new(2u32,env!("CARGO_CRATE_NAME"),"doctest_save_checked.ser");
+    /// theirs.save().unwrap();
+    ///
+    /// ours.set_settings(3);
+    /// assert!(matches!(ours.save_checked(), Err(SaveSettingsError::ConflictDetected { .. })));
+    ///
+    /// // merge their changes with ours, and save on top of the version that was merged
+    /// let mut merged = ours.load_theirs().unwrap();
+    /// let theirs_value = merged.take_settings().unwrap();
+    /// merged.set_settings(theirs_value + 3);
+    /// merged.save_checked().expect("Nothing changed on disk since load_theirs()");
+    /// ```
+    pub fn save_checked(&self) -> Result<(), SaveSettingsError> {
+        let mut disk_hash = self.disk_hash.lock().unwrap();
+        match get_backend().read(Path::new(&self.crate_name), &self.file_name) {
+            Ok(file_data) => {
+                if *disk_hash != Some(content_hash(&file_data)) {
+                    return Err(SaveSettingsError::ConflictDetected {
+                        on_disk_modified: self.on_disk_modified(),
+                    });
+                }
+            }
+            Err(BackendError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        self.write(&mut disk_hash)
+    }
+
+    /// Loads the settings container from disk as it currently is, e.g. after `save_checked()` reported a conflict.
+    /// Saving the returned container with `save_checked()` succeeds as long as the file is not modified again.
+    pub fn load_theirs(&self) -> Result<Self, LoadSettingsError> {
+        Self::load(&self.crate_name, &self.file_name)
+    }
+
+    /// Returns true if the settings file of this container exists
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::new(5u32,env!("CARGO_CRATE_NAME"),"doctest_container_exists.ser");
+    /// assert!(!settings.exists());
+    ///
+    /// settings.save().unwrap();
+    /// assert!(settings.exists());
+    ///
+    /// settings.delete().unwrap();
+    /// assert!(!settings.exists());
+    /// ```
+    pub fn exists(&self) -> bool {
+        get_backend().exists(Path::new(&self.crate_name), &self.file_name)
+    }
+
+    /// Deletes the settings file of this container, removing it from `SETTINGS_PATHS` like `delete_setting_file()`.
+    /// The settings held by the container are kept, so it can be saved again.
+    pub fn delete(&self) -> io::Result<()> {
+        let mut disk_hash = self.disk_hash.lock().unwrap();
+        delete_setting_file(&self.crate_name, &self.file_name)?;
+        *disk_hash = None;
+        // the settings held by the container are no longer saved anywhere
+        self.mark_dirty();
+        Ok(())
+    }
+
+    /// Serializes and writes the settings container, recording the hash of what was written
+    fn write(&self, disk_hash: &mut Option<u64>) -> Result<(), SaveSettingsError> {
+        let options = SaveOptions::default();
+        let file_data = options.render(self)?;
+        write_settings_with_options(
+            Path::new(&self.crate_name),
+            &self.file_name,
+            &file_data,
+            &options,
+        )?;
+        *disk_hash = Some(content_hash(file_data.as_bytes()));
+        self.dirty.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the modification time of the settings file, if the backend stores it on the filesystem
+    fn on_disk_modified(&self) -> Option<SystemTime> {
+        settings_modified_time(&self.crate_name, &self.file_name).ok()
+    }
+
+    #[cfg(feature = "async")]
+    /// Async version of `load()`, see `asynchronous` module documentation for how the IO is performed
+    pub async fn load_async(crate_name: &str, file_name: &str) -> Result<Self, LoadSettingsError> {
+        crate::asynchronous::load_settings_with_filename_async(crate_name, file_name).await
+    }
+
+    #[cfg(feature = "async")]
+    /// Async version of `save()`, see `asynchronous` module documentation for how the IO is performed
+    pub async fn save_async(&self) -> Result<(), SaveSettingsError> {
+        crate::asynchronous::save_settings_with_filename_async(
+            &self.crate_name,
+            &self.file_name,
+            self,
+        )
+        .await?;
+        self.dirty.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl<T> SettingsContainer<T> {
+    /// Consumes the container, returning the settings within it if present
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// let settings = SettingsContainer::new(1u32,env!("CARGO_CRATE_NAME"),"doctest_into_inner.ser");
+    /// assert_eq!(settings.into_inner(), Some(1));
+    ///
+    /// let settings = SettingsContainer::new(2u32,env!("CARGO_CRATE_NAME"),"doctest_into_inner.ser");
+    /// let inner: Option<u32> = settings.into();
+    /// assert_eq!(inner, Some(2));
+    /// ```
+    pub fn into_inner(self) -> Option<T> {
+        self.settings
+    }
+}
+
+impl<T> From<SettingsContainer<T>> for Option<T> {
+    fn from(container: SettingsContainer<T>) -> Self {
+        container.into_inner()
+    }
+}
+
+impl<T> SettingsContainer<T>
+where
+    for<'a> T: Serialize + Deserialize<'a> + Validate,
+{
+    /// Loads a settings container like `load()`, then validates the inner settings if present.
+    /// An invalid file is left untouched on disk so the user can fix it.
+    pub fn load_validated(crate_name: &str, file_name: &str) -> Result<Self, LoadSettingsError> {
+        let container = Self::load(crate_name, file_name)?;
+        match &container.settings {
+            Some(settings) => match settings.validate() {
+                Ok(_) => Ok(container),
+                Err(err) => Err(LoadSettingsError::ValidationFailed(err)),
+            },
+            None => Ok(container),
+        }
+    }
+
+    /// Validates the inner settings if present, then saves the container like `save()`.
+    /// ```
+    /// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+    /// use serde::{Deserialize, Serialize};
+    /// use cr_program_settings::prelude::*;
+    /// use cr_program_settings::settings_container::SettingsContainer;
+    ///
+    /// #[derive(Serialize,Deserialize,PartialEq,Debug)]
+    /// struct PathSettings {
+    /// output_path: String,
+    /// }
+    ///
+    /// impl Validate for PathSettings {
+    ///     fn validate(&self) -> Result<(), ValidationError> {
+    ///         if self.output_path.is_empty() {
+    ///             return Err(ValidationError::new("output_path must not be empty"));
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let invalid = SettingsContainer::new(PathSettings{ output_path: "".to_string() },env!("CARGO_CRATE_NAME"),"doctest_validated_container.ser");
+    /// assert!(invalid.save_validated().is_err());
+    ///
+    /// let valid = SettingsContainer::new(PathSettings{ output_path: "/tmp".to_string() },env!("CARGO_CRATE_NAME"),"doctest_validated_container.ser");
+    /// valid.save_validated().expect("Failed to save valid settings container");
+    ///
+    /// let loaded = SettingsContainer::<PathSettings>::load_validated(env!("CARGO_CRATE_NAME"),"doctest_validated_container.ser").unwrap();
+    /// assert_eq!(valid,loaded);
+    /// ```
+    pub fn save_validated(&self) -> Result<(), SaveSettingsError> {
+        if let Some(settings) = &self.settings {
+            if let Err(err) = settings.validate() {
+                return Err(SaveSettingsError::ValidationFailed(err));
+            }
+        }
+        self.save()
     }
 }
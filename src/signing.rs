@@ -0,0 +1,120 @@
+//! Signed settings source file, detects settings files modified outside the program using HMAC-SHA256
+//!
+//! The signature is stored next to the settings file in `file_name.sig`, as a lowercase hex string.
+#![warn(missing_docs)]
+
+use crate::backend::{get_backend, BackendError};
+use crate::{
+    deserialize_settings, read_settings_bytes, serialize_settings, write_settings_bytes,
+    LoadSettingsError, SaveSettingsError,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::io;
+use std::path::Path;
+
+/// Returns the name of the signature file for a settings file
+pub fn signature_file_name(file_name: &str) -> String {
+    format!("{}.sig", file_name)
+}
+
+/// Creates the HMAC-SHA256 of the settings file contents
+fn settings_mac(file_data: &[u8], key: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(file_data);
+    mac
+}
+
+/// Decodes a lowercase or uppercase hex string, returning `None` if it is not valid hex
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Saves settings to `USER_HOME/crate_name/file_name`, and an HMAC-SHA256 signature of the file to `file_name.sig`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::signing::{load_settings_signed, save_settings_signed};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct KioskSettings{
+/// start_page: String,
+/// }
+///
+/// let key = b"a key only the kiosk application knows";
+/// let settings = KioskSettings{ start_page: "https://example.com".to_string() };
+/// save_settings_signed(env!("CARGO_CRATE_NAME"), "kiosk.ser", &settings, key).expect("Unable to save signed settings");
+///
+/// let loaded = load_settings_signed::<KioskSettings>(env!("CARGO_CRATE_NAME"), "kiosk.ser", key).expect("Settings were modified");
+/// assert_eq!(settings, loaded);
+///
+/// // a different key does not match the signature
+/// assert!(matches!(load_settings_signed::<KioskSettings>(env!("CARGO_CRATE_NAME"), "kiosk.ser", b"wrong key"), Err(LoadSettingsError::SignatureInvalid)));
+///
+/// // recovery tooling can still load the settings without verifying them
+/// assert_eq!(load_settings_with_filename::<KioskSettings>(env!("CARGO_CRATE_NAME"), "kiosk.ser").unwrap(), settings);
+/// ```
+pub fn save_settings_signed<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+    key: &[u8],
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    let serialized_data = serialize_settings(settings)?;
+    let signature: String = settings_mac(serialized_data.as_bytes(), key)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    write_settings_bytes(Path::new(crate_name), file_name, serialized_data.as_bytes())?;
+    get_backend().write(
+        Path::new(crate_name),
+        &signature_file_name(file_name),
+        signature.as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name` after verifying the signature saved by `save_settings_signed()`.
+/// Returns `SignatureMissing` if `file_name.sig` does not exist, and `SignatureInvalid` if the settings file was modified
+/// or signed with a different key. The file is left untouched, so it can still be loaded with `load_settings_with_filename()`.
+///
+/// For example usage, see `save_settings_signed()` documentation.
+pub fn load_settings_signed<T>(
+    crate_name: &str,
+    file_name: &str,
+    key: &[u8],
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let signature = match get_backend().read(Path::new(crate_name), &signature_file_name(file_name))
+    {
+        Ok(signature) => signature,
+        Err(BackendError::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(LoadSettingsError::SignatureMissing)
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let signature = std::str::from_utf8(&signature)
+        .ok()
+        .and_then(|signature| decode_hex(signature.trim()))
+        .ok_or(LoadSettingsError::SignatureInvalid)?;
+    settings_mac(&file_data, key)
+        .verify_slice(&signature)
+        .map_err(|_| LoadSettingsError::SignatureInvalid)?;
+    deserialize_settings(settings_file_path, file_data)
+}
@@ -0,0 +1,103 @@
+//! Settings statistics source file, e.g. for showing how much space the settings of a crate use
+#![warn(missing_docs)]
+
+use crate::{get_settings_dir, list_settings_files};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::{fs, io};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// The number of settings files of a crate and their combined size, returned by `settings_stats()`
+pub struct SettingsStats {
+    /// Number of settings files
+    pub file_count: u64,
+    /// Combined size of the settings files in bytes
+    pub total_bytes: u64,
+}
+
+impl Display for SettingsStats {
+    /// Formats the stats for users, e.g. `12.0 KB across 4 files`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        let files = if self.file_count == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        if self.total_bytes < 1024 {
+            return write!(
+                f,
+                "{} B across {} {}",
+                self.total_bytes, self.file_count, files
+            );
+        }
+        let mut size = self.total_bytes as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(
+            f,
+            "{:.1} {} across {} {}",
+            size, UNITS[unit], self.file_count, files
+        )
+    }
+}
+
+/// Returns the number and combined size of the settings files directly inside `USER_HOME/crate_name`,
+/// subfolders such as the `profiles` folder are excluded, see `settings_stats_recursive()` to include them.
+/// Only settings stored by the default `FileSystemBackend` can be measured.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::stats::settings_stats;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_doctest_stats";
+/// save_settings_with_filename(crate_name, "first.ser", &Settings{ setting1: 1 }).unwrap();
+/// save_settings_with_filename(crate_name, "second.ser", &Settings{ setting1: 2 }).unwrap();
+///
+/// let stats = settings_stats(crate_name).unwrap();
+/// assert_eq!(stats.file_count, 2);
+/// assert_eq!(stats.to_string(), "26 B across 2 files");
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn settings_stats(crate_name: &str) -> io::Result<SettingsStats> {
+    let mut stats = SettingsStats::default();
+    for settings_file in list_settings_files(crate_name)? {
+        stats.file_count += 1;
+        stats.total_bytes += fs::metadata(settings_file)?.len();
+    }
+    Ok(stats)
+}
+
+/// Returns the number and combined size of every settings file inside `USER_HOME/crate_name`, including subfolders.
+/// Symlinked folders are not followed, so a link back to a parent folder is not counted twice.
+pub fn settings_stats_recursive(crate_name: &str) -> io::Result<SettingsStats> {
+    let settings_path = get_settings_dir(Path::new(crate_name)).map_err(io::Error::from)?;
+    let mut stats = SettingsStats::default();
+    add_folder_stats(&settings_path, &mut stats)?;
+    Ok(stats)
+}
+
+/// Adds the files in `folder` and its subfolders to `stats`
+fn add_folder_stats(folder: &Path, stats: &mut SettingsStats) -> io::Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            add_folder_stats(&entry.path(), stats)?;
+        } else if file_type.is_file() || (file_type.is_symlink() && entry.path().is_file()) {
+            stats.file_count += 1;
+            stats.total_bytes += fs::metadata(entry.path())?.len();
+        }
+    }
+    Ok(())
+}
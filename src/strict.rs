@@ -0,0 +1,84 @@
+//! Strict loading source file, reports keys in a settings file that the settings struct does not use
+#![warn(missing_docs)]
+
+use crate::format::Format;
+use crate::{deserialize_settings, read_settings_bytes, LoadSettingsError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use toml::Value;
+
+/// Loads settings from `USER_HOME/crate_name/file_name` like `load_settings_with_filename()`, additionally returning
+/// the dotted path of every key in the file that is not part of `T`, e.g. a typo such as `window.widht`.
+/// Unknown keys are not an error, so the caller can decide whether to warn the user, log them, or refuse to start.
+///
+/// Keys are found by serializing the loaded settings again and comparing the result with the file,
+/// so fields that are skipped while serializing, e.g. with `#[serde(skip_serializing_if)]`, can be reported as unknown.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::strict::load_settings_strict;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Window{
+/// width: u32,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// window: Window,
+/// }
+///
+/// let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("strict.ser");
+/// std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+/// std::fs::write(&path, "[window]\nwidth = 800\nwidht = 1024\n").unwrap();
+///
+/// let (settings, unknown_keys) = load_settings_strict::<Settings>(env!("CARGO_CRATE_NAME"), "strict.ser").unwrap();
+/// assert_eq!(settings.window.width, 800);
+/// assert_eq!(unknown_keys, vec!["window.widht".to_string()]);
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "strict.ser").unwrap();
+/// ```
+pub fn load_settings_strict<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<(T, Vec<String>), LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a> + Serialize,
+{
+    let (settings_file_path, file_data) = read_settings_bytes(Path::new(crate_name), file_name)?;
+    let file_value = Format::Toml.deserialize::<Value>(&file_data);
+    let settings = deserialize_settings::<T>(settings_file_path, file_data)?;
+    let file_value = file_value?;
+    let mut unknown_keys = Vec::new();
+    // settings that can not be serialized back into a table have nothing to compare against
+    if let Ok(known_value) = Value::try_from(&settings) {
+        collect_unknown_keys(&file_value, &known_value, "", &mut unknown_keys);
+    }
+    Ok((settings, unknown_keys))
+}
+
+/// Pushes the path of every key in `value` that is missing from `known`, recursing into tables and arrays of tables
+fn collect_unknown_keys(value: &Value, known: &Value, path: &str, unknown_keys: &mut Vec<String>) {
+    match (value, known) {
+        (Value::Table(table), Value::Table(known_table)) => {
+            for (key, value) in table {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match known_table.get(key) {
+                    Some(known) => collect_unknown_keys(value, known, &key_path, unknown_keys),
+                    None => unknown_keys.push(key_path),
+                }
+            }
+        }
+        (Value::Array(array), Value::Array(known_array)) => {
+            for (index, (value, known)) in array.iter().zip(known_array).enumerate() {
+                collect_unknown_keys(value, known, &format!("{}[{}]", path, index), unknown_keys);
+            }
+        }
+        _ => {}
+    }
+}
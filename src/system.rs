@@ -0,0 +1,127 @@
+//! System-wide settings source file, machine-wide defaults that each user can override
+#![warn(missing_docs)]
+
+use crate::format::Format;
+use crate::layered::merge_values;
+use crate::{deserialize_settings, read_settings_bytes, LoadSettingsError};
+use serde::Deserialize;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use toml::value::Table;
+use toml::Value;
+
+/// Global override of the folder system-wide settings folders are stored in
+static SYSTEM_SETTINGS_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Overrides the folder system-wide settings folders are stored in, e.g. for tests or for installs to a custom prefix
+pub fn set_system_settings_root(root: impl Into<PathBuf>) {
+    *SYSTEM_SETTINGS_ROOT.write().unwrap() = Some(root.into());
+}
+
+/// Removes the override set with `set_system_settings_root()`, so the platform default is used again
+pub fn reset_system_settings_root() {
+    *SYSTEM_SETTINGS_ROOT.write().unwrap() = None;
+}
+
+/// Returns the folder that administrators should place system-wide settings for the crate in:
+/// `%ProgramData%\crate_name` on Windows, `/Library/Application Support/crate_name` on macOS,
+/// and `/etc/crate_name` on other platforms, unless overridden with `set_system_settings_root()`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use cr_program_settings::system::get_system_settings_dir;
+///
+/// #[cfg(target_os = "linux")]
+/// assert_eq!(get_system_settings_dir("my_app"), std::path::Path::new("/etc/my_app"));
+/// ```
+pub fn get_system_settings_dir(crate_name: &str) -> PathBuf {
+    let root = SYSTEM_SETTINGS_ROOT
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(default_system_settings_root);
+    root.join(crate_name)
+}
+
+#[cfg(windows)]
+/// Returns the platform default folder for system-wide settings
+fn default_system_settings_root() -> PathBuf {
+    std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+}
+
+#[cfg(target_os = "macos")]
+/// Returns the platform default folder for system-wide settings
+fn default_system_settings_root() -> PathBuf {
+    PathBuf::from("/Library/Application Support")
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+/// Returns the platform default folder for system-wide settings
+fn default_system_settings_root() -> PathBuf {
+    PathBuf::from("/etc")
+}
+
+/// Loads settings by merging the users settings file at `USER_HOME/crate_name/file_name` over the system-wide file at
+/// `get_system_settings_dir(crate_name)/file_name`, so users only need to store the settings they changed.
+///
+/// Tables are merged recursively, while every other value, including arrays, in the users file replaces the system-wide value.
+/// Either file may be missing, if both are missing the `NotFound` io error is returned.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::system::{load_settings_with_system_defaults, reset_system_settings_root, set_system_settings_root, get_system_settings_dir};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// update_server: String,
+/// volume: u32,
+/// }
+///
+/// let system_root = std::env::temp_dir().join("cr_program_settings_doctest_system");
+/// set_system_settings_root(&system_root);
+/// std::fs::create_dir_all(get_system_settings_dir(env!("CARGO_CRATE_NAME"))).unwrap();
+/// std::fs::write(get_system_settings_dir(env!("CARGO_CRATE_NAME")).join("system.ser"), "update_server = \"internal.example.com\"\nvolume = 50\n").unwrap();
+///
+/// #[derive(Serialize,Deserialize)]
+/// struct UserSettings{
+/// volume: u32,
+/// }
+/// save_settings!(UserSettings{ volume: 80 }, "system.ser").unwrap();
+///
+/// let settings = load_settings_with_system_defaults::<Settings>(env!("CARGO_CRATE_NAME"), "system.ser").unwrap();
+/// assert_eq!(settings, Settings{ update_server: "internal.example.com".to_string(), volume: 80 });
+///
+/// reset_system_settings_root();
+/// std::fs::remove_dir_all(system_root).unwrap();
+/// ```
+pub fn load_settings_with_system_defaults<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let system_file_path = get_system_settings_dir(crate_name).join(file_name);
+    let (mut value, system_file_found) = match std::fs::read(system_file_path) {
+        Ok(file_data) => (Format::Toml.deserialize::<Value>(&file_data)?, true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => (Value::Table(Table::new()), false),
+        Err(err) => return Err(LoadSettingsError::IOError(err)),
+    };
+
+    match read_settings_bytes(Path::new(crate_name), file_name) {
+        Ok((settings_file_path, file_data)) => {
+            let user_value = deserialize_settings::<Value>(settings_file_path, file_data)?;
+            merge_values(&mut value, user_value);
+        }
+        Err(LoadSettingsError::IOError(err))
+            if err.kind() == io::ErrorKind::NotFound && system_file_found => {}
+        Err(err) => return Err(err),
+    }
+
+    value
+        .try_into::<T>()
+        .map_err(LoadSettingsError::DeserializationError)
+}
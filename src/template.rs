@@ -0,0 +1,180 @@
+//! Settings template source file, writes an example settings file from the defaults of a settings struct
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::{serialize_settings, write_settings_bytes_with, SaveSettingsError};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Writes `T::default()` to `USER_HOME/crate_name/file_name` as a template for the user to edit, e.g. on the first run of a program,
+/// only if the file does not exist yet. Returns the path of the written file, or `SaveSettingsError::AlreadyExists`
+/// if the file exists, in which case it is left untouched.
+///
+/// For a template with a comment above each field, see `write_template_settings_with_docs()`.
+pub fn write_template_settings<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<PathBuf, SaveSettingsError>
+where
+    T: Default + Serialize,
+{
+    write_template_settings_with_docs::<T>(crate_name, file_name, &[])
+}
+
+/// Writes a template like `write_template_settings()`, with a `# comment` above each key found in `field_docs`.
+/// Keys are matched by their dotted path, e.g. `window.width` for the `width` key of the `[window]` table,
+/// and a table is documented by its own path, e.g. `window`. Comments can span several lines.
+/// Each key is only documented where it first appears, e.g. in the first entry of an array of tables.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::template::write_template_settings_with_docs;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Window{
+/// width: u32,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// volume: u32,
+/// window: Window,
+/// }
+///
+/// impl Default for Settings {
+///     fn default() -> Self {
+///         Self{ volume: 50, window: Window{ width: 800 } }
+///     }
+/// }
+///
+/// let field_docs = [
+///     ("volume", "Volume of the sound effects, from 0 to 100"),
+///     ("window", "Size of the main window"),
+///     ("window.width", "Width in pixels"),
+/// ];
+///
+/// // on the first run, give the user a documented file to start from
+/// match write_template_settings_with_docs::<Settings>(env!("CARGO_CRATE_NAME"), "template.toml", &field_docs) {
+///     Ok(path) => println!("wrote example settings to {}", path.display()),
+///     Err(SaveSettingsError::AlreadyExists(_)) => {}
+///     Err(err) => panic!("unable to write example settings: {:?}", err),
+/// }
+///
+/// assert_eq!(
+///     read_settings_string(env!("CARGO_CRATE_NAME"), "template.toml").unwrap(),
+///     "# Volume of the sound effects, from 0 to 100\nvolume = 50\n\n# Size of the main window\n[window]\n# Width in pixels\nwidth = 800\n"
+/// );
+/// assert_eq!(load_settings!(Settings, "template.toml").unwrap(), Settings::default());
+///
+/// // the users edits are never replaced
+/// assert!(matches!(
+///     write_template_settings_with_docs::<Settings>(env!("CARGO_CRATE_NAME"), "template.toml", &field_docs),
+///     Err(SaveSettingsError::AlreadyExists(_))
+/// ));
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "template.toml").unwrap();
+/// ```
+pub fn write_template_settings_with_docs<T>(
+    crate_name: &str,
+    file_name: &str,
+    field_docs: &[(&str, &str)],
+) -> Result<PathBuf, SaveSettingsError>
+where
+    T: Default + Serialize,
+{
+    let serialized_data = serialize_settings(&T::default())?;
+    let file_data = add_field_docs(&serialized_data, field_docs);
+    let folder = Path::new(crate_name);
+    write_settings_bytes_with(
+        folder,
+        file_name,
+        file_data.as_bytes(),
+        |backend, relative_dir, file_name, data| backend.write_new(relative_dir, file_name, data),
+    )?;
+    Ok(get_backend()
+        .resolve(folder)
+        .unwrap_or_else(|| folder.to_path_buf())
+        .join(file_name))
+}
+
+/// Inserts the comments of `field_docs` above the keys and table headers of a serialized TOML document they document
+fn add_field_docs(serialized_data: &str, field_docs: &[(&str, &str)]) -> String {
+    if field_docs.is_empty() {
+        return serialized_data.to_string();
+    }
+    let mut documented = HashSet::new();
+    let mut file_data = String::with_capacity(serialized_data.len() * 2);
+    let mut table_path = String::new();
+    // the delimiter of a multi-line string the current line is inside of
+    let mut open_string: Option<&str> = None;
+    for line in serialized_data.lines() {
+        if let Some(delimiter) = open_string {
+            if line.matches(delimiter).count() % 2 == 1 {
+                open_string = None;
+            }
+            file_data.push_str(line);
+            file_data.push('\n');
+            continue;
+        }
+        let key_path = if let Some(header) = table_header(line) {
+            table_path = header.to_string();
+            Some(table_path.clone())
+        } else if line.starts_with(char::is_whitespace) || line.starts_with(']') {
+            // elements of a multi-line array
+            None
+        } else {
+            line.split_once(" = ").map(|(key, _)| {
+                let key = unquote(key);
+                if table_path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", table_path, key)
+                }
+            })
+        };
+        if let Some(key_path) = key_path {
+            if let Some((_, doc)) = field_docs.iter().find(|(path, _)| *path == key_path) {
+                if documented.insert(key_path) {
+                    for doc_line in doc.lines() {
+                        if doc_line.is_empty() {
+                            file_data.push_str("#\n");
+                        } else {
+                            file_data.push_str("# ");
+                            file_data.push_str(doc_line);
+                            file_data.push('\n');
+                        }
+                    }
+                }
+            }
+        }
+        for delimiter in ["\"\"\"", "'''"] {
+            if line.matches(delimiter).count() % 2 == 1 {
+                open_string = Some(delimiter);
+            }
+        }
+        file_data.push_str(line);
+        file_data.push('\n');
+    }
+    file_data
+}
+
+/// Returns the dotted path of a table header line, e.g. `window.size` for `[window.size]` or `[[window.size]]`
+fn table_header(line: &str) -> Option<&str> {
+    let header = line
+        .strip_prefix("[[")
+        .and_then(|line| line.strip_suffix("]]"))
+        .or_else(|| {
+            line.strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+        })?;
+    Some(header.trim())
+}
+
+/// Removes the quotes around a quoted key, e.g. `"my key"`
+fn unquote(key: &str) -> &str {
+    key.strip_prefix('"')
+        .and_then(|key| key.strip_suffix('"'))
+        .unwrap_or(key)
+}
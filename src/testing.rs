@@ -0,0 +1,162 @@
+//! Test fixture source file
+#![warn(missing_docs)]
+
+use crate::{serialize_settings, set_home_override, SETTINGS_PATHS};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counter used to give every temporary settings directory in this process a unique name
+static TEMP_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary settings directory that replaces the users home on the current thread until it is dropped.
+/// Every save, load, and delete made on this thread while it is alive goes into the temporary directory,
+/// which is removed when it is dropped, along with its entries in `SETTINGS_PATHS`, even during a panic.
+///
+/// The override is per thread, so tests running in parallel never see each others files, and the guard can not be sent
+/// to another thread. It only applies to the default `FileSystemBackend`.
+/// Use `with_temp_settings_dir()` to run a closure with a temporary directory instead.
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::testing::TempSettingsDir;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let temp_dir = TempSettingsDir::new();
+/// save_settings!(Settings{ setting1: 3 }).expect("Unable to save settings in temporary directory");
+/// assert_eq!(get_user_home().unwrap(), temp_dir.path());
+///
+/// let settings_dir = temp_dir.path().join(env!("CARGO_CRATE_NAME"));
+/// assert!(settings_dir.exists());
+///
+/// drop(temp_dir);
+/// assert!(!settings_dir.exists());
+/// ```
+pub struct TempSettingsDir {
+    /// The temporary directory used as the users home
+    temp_dir: PathBuf,
+    /// The home override to restore when dropped
+    previous: Option<PathBuf>,
+    /// The override belongs to the thread that created it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl TempSettingsDir {
+    /// Creates a fresh, empty temporary directory and uses it as the users home on the current thread
+    pub fn new() -> Self {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "cr_program_settings_{}_{}",
+            std::process::id(),
+            TEMP_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).expect("Unable to create temporary settings directory");
+
+        let previous = set_home_override(Some(temp_dir.clone()));
+        Self {
+            temp_dir,
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the temporary directory used as the users home
+    pub fn path(&self) -> &Path {
+        &self.temp_dir
+    }
+}
+
+impl Default for TempSettingsDir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TempSettingsDir {
+    fn drop(&mut self) {
+        set_home_override(self.previous.take());
+        SETTINGS_PATHS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|path| !path.starts_with(&self.temp_dir));
+        let _ = fs::remove_dir_all(&self.temp_dir);
+    }
+}
+
+/// Runs `f` with the users home replaced by a fresh temporary directory on the current thread.
+/// Every save, load, and delete made by `f` on this thread goes into the temporary directory, which is removed
+/// afterwards along with its entries in `SETTINGS_PATHS`, even if `f` panics.
+///
+/// The override is per thread, so fixtures running in parallel tests never see each others files.
+/// It only applies to the default `FileSystemBackend`.
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::testing::with_temp_settings_dir;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let settings_file = with_temp_settings_dir(|| {
+///     save_settings!(Settings{ setting1: 3 }).expect("Unable to save settings in temporary directory");
+///     assert_eq!(load_settings!(Settings).unwrap(), Settings{ setting1: 3 });
+///
+///     let settings_file = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join(format!("{}.ser", env!("CARGO_CRATE_NAME")));
+///     assert!(settings_file.exists());
+///     assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+///     settings_file
+/// });
+///
+/// assert!(!settings_file.exists());
+/// assert!(!SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+/// ```
+pub fn with_temp_settings_dir<R>(f: impl FnOnce() -> R) -> R {
+    let _temp_dir = TempSettingsDir::new();
+    f()
+}
+
+/// Asserts that settings survive being saved and loaded again unchanged, for use in tests.
+/// The settings are serialized and deserialized in memory, so nothing is written to the filesystem.
+/// Panics with the serialized TOML if the settings can not be serialized, can not be deserialized, or come back different,
+/// e.g. because of a `#[serde(skip)]` field without a matching default.
+/// This costs a full serialization and deserialization pass.
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::testing::assert_settings_roundtrip;
+///
+/// #[derive(Serialize,Deserialize, PartialEq)]
+/// struct Settings{
+/// setting1: u32,
+/// name: String,
+/// }
+///
+/// assert_settings_roundtrip(&Settings{ setting1: 3, name: "roundtrip".to_string() });
+/// ```
+pub fn assert_settings_roundtrip<T>(settings: &T)
+where
+    for<'a> T: Serialize + Deserialize<'a> + PartialEq,
+{
+    let serialized_data = match serialize_settings(settings) {
+        Ok(serialized_data) => serialized_data,
+        Err(err) => panic!("settings can not be serialized: {:?}", err),
+    };
+    match toml::from_str::<T>(&serialized_data) {
+        Ok(loaded) => assert!(
+            loaded == *settings,
+            "settings changed after saving and loading them again, saved as:\n{}",
+            serialized_data
+        ),
+        Err(err) => panic!(
+            "saved settings can not be loaded again: {}\nsaved as:\n{}",
+            err, serialized_data
+        ),
+    }
+}
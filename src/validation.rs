@@ -0,0 +1,166 @@
+//! Settings validation source file
+#![warn(missing_docs)]
+
+use crate::{
+    load_settings_with_filename, save_settings_with_filename, LoadSettingsError, SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Trait for settings structs that have invariants which must hold before saving and after loading.
+pub trait Validate {
+    /// Checks the invariants of the settings, returning a `ValidationError` describing every problem found.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned by a `Validate` implementation, containing human-readable messages for each failed check.
+pub struct ValidationError {
+    messages: Vec<String>,
+}
+
+impl ValidationError {
+    /// Creates a new `ValidationError` with a single message
+    pub fn new(message: &str) -> Self {
+        Self {
+            messages: vec![message.to_string()],
+        }
+    }
+
+    /// Creates a new `ValidationError` from a list of messages
+    pub fn from_messages(messages: Vec<String>) -> Self {
+        Self { messages }
+    }
+
+    /// Adds another message to the error
+    pub fn push(&mut self, message: &str) {
+        self.messages.push(message.to_string());
+    }
+
+    /// Returns the human-readable messages describing why validation failed
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "settings failed validation: {}",
+            self.messages.join("; ")
+        )
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Validates the settings, then saves them to `USER_HOME/crate_name/file_name`.
+/// Nothing is written if validation fails.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct ServerSettings{
+/// port: u16,
+/// }
+///
+/// impl Validate for ServerSettings {
+///     fn validate(&self) -> Result<(), ValidationError> {
+///         if self.port == 0 {
+///             return Err(ValidationError::new("port must not be 0"));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let file_name = "validated_settings.ser";
+///
+/// let invalid = ServerSettings{ port: 0 };
+/// assert!(matches!(save_settings_validated(env!("CARGO_CRATE_NAME"), file_name, &invalid), Err(SaveSettingsError::ValidationFailed(_))));
+///
+/// let valid = ServerSettings{ port: 8080 };
+/// save_settings_validated(env!("CARGO_CRATE_NAME"), file_name, &valid).expect("Unable to save valid settings");
+///
+/// let loaded = load_settings_validated::<ServerSettings>(env!("CARGO_CRATE_NAME"), file_name).expect("Unable to load valid settings");
+/// assert_eq!(valid, loaded);
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), file_name).unwrap();
+/// ```
+pub fn save_settings_validated<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize + Validate,
+{
+    match settings.validate() {
+        Ok(_) => save_settings_with_filename(crate_name, file_name, settings),
+        Err(err) => Err(SaveSettingsError::ValidationFailed(err)),
+    }
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, then validates them.
+/// An invalid file is left untouched on disk so the user can fix it.
+///
+/// For example usage, see `save_settings_validated()` documentation.
+pub fn load_settings_validated<T>(crate_name: &str, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a> + Validate,
+{
+    let settings = load_settings_with_filename::<T>(crate_name, file_name)?;
+    match settings.validate() {
+        Ok(_) => Ok(settings),
+        Err(err) => Err(LoadSettingsError::ValidationFailed(err)),
+    }
+}
+
+/// Loads settings from `USER_HOME/crate_name/file_name`, then checks them with the `validate` closure,
+/// for one-off rules or types that do not implement `Validate`.
+/// An error message returned by the closure is reported as `LoadSettingsError::ValidationFailed`.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct ServerSettings{
+/// port: u16,
+/// }
+///
+/// let file_name = "validator_settings.ser";
+/// save_settings_with_filename(env!("CARGO_CRATE_NAME"), file_name, &ServerSettings{ port: 0 }).unwrap();
+///
+/// let result = load_settings_with_validator::<ServerSettings, _>(env!("CARGO_CRATE_NAME"), file_name, |settings| {
+///     if settings.port == 0 {
+///         return Err("port must not be 0".to_string());
+///     }
+///     Ok(())
+/// });
+/// match result {
+///     Err(LoadSettingsError::ValidationFailed(err)) => assert_eq!(err.messages(), ["port must not be 0"]),
+///     _ => panic!("expected the settings to fail validation"),
+/// }
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), file_name).unwrap();
+/// ```
+pub fn load_settings_with_validator<T, F>(
+    crate_name: &str,
+    file_name: &str,
+    validate: F,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+    F: Fn(&T) -> Result<(), String>,
+{
+    let settings = load_settings_with_filename::<T>(crate_name, file_name)?;
+    match validate(&settings) {
+        Ok(_) => Ok(settings),
+        Err(message) => Err(LoadSettingsError::ValidationFailed(ValidationError::new(
+            &message,
+        ))),
+    }
+}
@@ -0,0 +1,168 @@
+//! Versioned settings source file, keeps the settings of each major app version in its own `crate_name/vN` folder
+#![warn(missing_docs)]
+
+use crate::{
+    get_settings_dir, load_settings_in_dir, save_settings_in_dir, LoadSettingsError,
+    SaveSettingsError,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::{fs, io};
+
+/// The settings version used by `save_settings_current_version()` and `load_settings_current_version()`
+static SETTINGS_VERSION: RwLock<Option<u32>> = RwLock::new(None);
+
+/// Sets the settings version of the running app, so `save_settings_current_version()` and `load_settings_current_version()`
+/// use `USER_HOME/crate_name/vN/file_name`. Usually set once at startup to the major version of the app,
+/// so an upgrade starts from a clean folder and a rollback finds its old settings untouched.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::versioned::{load_settings_current_version, reset_settings_version, save_settings_current_version, set_settings_version};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// set_settings_version(2);
+/// save_settings_current_version("cr_program_settings_versioned_doc", "settings.ser", &Settings{ setting1: 2 }).unwrap();
+/// assert_eq!(load_settings_current_version::<Settings>("cr_program_settings_versioned_doc", "settings.ser").unwrap(), Settings{ setting1: 2 });
+///
+/// reset_settings_version();
+/// cr_program_settings::delete_settings_folder("cr_program_settings_versioned_doc").unwrap();
+/// ```
+pub fn set_settings_version(version: u32) {
+    *SETTINGS_VERSION.write().unwrap() = Some(version);
+}
+
+/// Removes the version set with `set_settings_version()`, so settings are stored directly in `USER_HOME/crate_name` again
+pub fn reset_settings_version() {
+    *SETTINGS_VERSION.write().unwrap() = None;
+}
+
+/// Returns the version set with `set_settings_version()`, if any
+pub fn settings_version() -> Option<u32> {
+    *SETTINGS_VERSION.read().unwrap()
+}
+
+/// Returns the folder of a settings version relative to the settings root, `crate_name/vN`
+pub fn versioned_dir(crate_name: &str, version: u32) -> PathBuf {
+    Path::new(crate_name).join(format!("v{}", version))
+}
+
+/// Returns the folder the configured version uses, `crate_name/vN`, or `crate_name` if no version is set
+fn current_version_dir(crate_name: &str) -> PathBuf {
+    match settings_version() {
+        Some(version) => versioned_dir(crate_name, version),
+        None => PathBuf::from(crate_name),
+    }
+}
+
+/// Saves a serializable settings object to `USER_HOME/crate_name/vN/file_name`, where `N` is `version`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::versioned::{list_settings_versions, load_settings_versioned, save_settings_versioned};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_versions_doc";
+/// save_settings_versioned(crate_name, 1, "settings.ser", &Settings{ setting1: 1 }).unwrap();
+/// save_settings_versioned(crate_name, 2, "settings.ser", &Settings{ setting1: 2 }).unwrap();
+///
+/// // both versions are kept side by side
+/// assert_eq!(load_settings_versioned::<Settings>(crate_name, 1, "settings.ser").unwrap(), Settings{ setting1: 1 });
+/// assert_eq!(list_settings_versions(crate_name).unwrap(), vec![1, 2]);
+///
+/// delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn save_settings_versioned<T>(
+    crate_name: &str,
+    version: u32,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_in_dir(&versioned_dir(crate_name, version), file_name, settings)
+}
+
+/// Loads settings from `USER_HOME/crate_name/vN/file_name`, where `N` is `version`
+///
+/// For example usage, see `save_settings_versioned()` documentation.
+pub fn load_settings_versioned<T>(
+    crate_name: &str,
+    version: u32,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_in_dir(&versioned_dir(crate_name, version), file_name)
+}
+
+/// Saves settings to the folder of the version set with `set_settings_version()`,
+/// or to `USER_HOME/crate_name/file_name` if no version is set
+///
+/// For example usage, see `set_settings_version()` documentation.
+pub fn save_settings_current_version<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_in_dir(&current_version_dir(crate_name), file_name, settings)
+}
+
+/// Loads settings from the folder of the version set with `set_settings_version()`,
+/// or from `USER_HOME/crate_name/file_name` if no version is set
+///
+/// For example usage, see `set_settings_version()` documentation.
+pub fn load_settings_current_version<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_in_dir(&current_version_dir(crate_name), file_name)
+}
+
+/// Lists the versions that have a folder in `USER_HOME/crate_name`, sorted from oldest to newest,
+/// e.g. to migrate from the newest older version on first start after an upgrade.
+/// Only folders named `v` followed by a number are counted, and a missing crate folder has no versions.
+pub fn list_settings_versions(crate_name: &str) -> io::Result<Vec<u32>> {
+    let settings_path = get_settings_dir(Path::new(crate_name)).map_err(io::Error::from)?;
+    let entries = match fs::read_dir(settings_path) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+    let mut versions = vec![];
+    for entry in entries {
+        let entry = entry?;
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let version = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix('v'))
+            .filter(|number| number.bytes().all(|byte| byte.is_ascii_digit()))
+            .and_then(|number| number.parse::<u32>().ok());
+        if let Some(version) = version {
+            versions.push(version);
+        }
+    }
+    versions.sort_unstable();
+    Ok(versions)
+}
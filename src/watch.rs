@@ -0,0 +1,208 @@
+//! File watching source file, reloads settings when the file is changed outside the program
+#![warn(missing_docs)]
+
+use crate::{
+    get_home_override, get_settings_dir, load_settings_with_filename, set_home_override,
+    LoadSettingsError,
+};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::{fs, io};
+
+/// How long the settings file must be left alone before it is reloaded, editors often write several times per save
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Handle to a running settings watcher, watching stops when this is dropped or `stop()` is called
+pub struct WatchHandle {
+    watcher: Option<RecommendedWatcher>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    /// Stops watching the settings file
+    pub fn stop(self) {}
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        // dropping the watcher closes the event channel, which ends the worker thread
+        self.watcher.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Watches `USER_HOME/crate_name/file_name` and calls `callback` with the freshly loaded settings every time
+/// the file changes. Bursts of changes are debounced into a single reload, changes to other files in the
+/// folder are ignored, and a file that fails to load, e.g. a half written or malformed file, produces an `Err`.
+/// Deleting the file produces an `Err` with the `NotFound` io error.
+///
+/// The settings folder is created if it does not exist yet. Only the default `FileSystemBackend` is watched.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::watch::watch_settings;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let handle = watch_settings::<Settings>(env!("CARGO_CRATE_NAME"), "watched.ser", move |settings| {
+///     let _ = sender.send(settings.map(|settings| settings.setting1).ok());
+/// }).expect("Unable to watch settings");
+///
+/// // an edit made by another program
+/// save_settings!(Settings{ setting1: 99 }, "watched.ser").unwrap();
+/// assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), Some(99));
+///
+/// handle.stop();
+/// ```
+pub fn watch_settings<T>(
+    crate_name: &str,
+    file_name: &str,
+    callback: impl Fn(Result<T, LoadSettingsError>) + Send + 'static,
+) -> io::Result<WatchHandle>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let crate_name_owned = crate_name.to_string();
+    let file_name_owned = file_name.to_string();
+    watch_file(crate_name, file_name, move |_exists| {
+        callback(load_settings_with_filename::<T>(
+            &crate_name_owned,
+            &file_name_owned,
+        ))
+    })
+}
+
+#[derive(Debug)]
+/// A change to a watched settings file, sent by `subscribe_settings_changes()`
+pub enum SettingsEvent<T> {
+    /// The settings file changed and was reloaded successfully
+    Reloaded(T),
+    /// The settings file changed, but could not be loaded, e.g. a half written or malformed file
+    ParseError(LoadSettingsError),
+    /// The settings file was deleted
+    Deleted,
+}
+
+/// Watches `USER_HOME/crate_name/file_name` and sends a `SettingsEvent` on the returned receiver every time the file changes,
+/// which is easier to integrate into an event loop that already polls receivers each frame than a callback.
+/// Changes are debounced and filtered the same way as `watch_settings()`, and each call creates an independent subscriber,
+/// so the same file can be subscribed to any number of times.
+///
+/// Events stop when the `WatchHandle` is dropped or stopped, after which the receiver disconnects.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use std::time::Duration;
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+/// use cr_program_settings::watch::{subscribe_settings_changes, SettingsEvent};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let (handle, receiver) = subscribe_settings_changes::<Settings>(env!("CARGO_CRATE_NAME"), "subscribed.ser").expect("Unable to watch settings");
+///
+/// save_settings!(Settings{ setting1: 42 }, "subscribed.ser").unwrap();
+///
+/// // e.g. once per frame, use `try_recv()` to poll without blocking
+/// match receiver.recv_timeout(Duration::from_secs(5)).unwrap() {
+///     SettingsEvent::Reloaded(settings) => assert_eq!(settings.setting1, 42),
+///     event => panic!("unexpected event {:?}", event),
+/// }
+///
+/// handle.stop();
+/// ```
+pub fn subscribe_settings_changes<T>(
+    crate_name: &str,
+    file_name: &str,
+) -> io::Result<(WatchHandle, Receiver<SettingsEvent<T>>)>
+where
+    for<'a> T: Deserialize<'a> + Send + 'static,
+{
+    let crate_name_owned = crate_name.to_string();
+    let file_name_owned = file_name.to_string();
+    let (sender, receiver) = channel();
+    let handle = watch_file(crate_name, file_name, move |exists| {
+        let event = if exists {
+            match load_settings_with_filename::<T>(&crate_name_owned, &file_name_owned) {
+                Ok(settings) => SettingsEvent::Reloaded(settings),
+                Err(err) => SettingsEvent::ParseError(err),
+            }
+        } else {
+            SettingsEvent::Deleted
+        };
+        // the subscriber may have dropped its receiver, in which case there is nobody left to notify
+        let _ = sender.send(event);
+    })?;
+    Ok((handle, receiver))
+}
+
+/// Watches `USER_HOME/crate_name/file_name`, calling `on_change` with whether the file exists after each debounced burst of changes
+pub(crate) fn watch_file(
+    crate_name: &str,
+    file_name: &str,
+    on_change: impl Fn(bool) + Send + 'static,
+) -> io::Result<WatchHandle> {
+    let settings_path = get_settings_dir(Path::new(crate_name)).map_err(io::Error::from)?;
+    fs::create_dir_all(&settings_path)?;
+    let settings_file_path = settings_path.join(file_name);
+    let watched_name = OsString::from(file_name);
+
+    let (sender, receiver) = channel::<Event>();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            let _ = sender.send(event);
+        }
+    })
+    .map_err(io::Error::other)?;
+    watcher
+        .watch(&settings_path, RecursiveMode::NonRecursive)
+        .map_err(io::Error::other)?;
+
+    // access events are ignored, otherwise reloading the file would trigger another reload
+    let is_watched_file = move |event: &Event| {
+        !matches!(event.kind, EventKind::Access(_))
+            && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == Some(watched_name.as_os_str()))
+    };
+
+    let home_override = get_home_override();
+    let worker = std::thread::spawn(move || {
+        set_home_override(home_override);
+        while let Ok(event) = receiver.recv() {
+            let mut changed = is_watched_file(&event);
+            // wait until the folder has been quiet for the debounce window
+            loop {
+                match receiver.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(event) => changed |= is_watched_file(&event),
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            if changed {
+                on_change(settings_file_path.is_file());
+            }
+        }
+    });
+
+    Ok(WatchHandle {
+        watcher: Some(watcher),
+        worker: Some(worker),
+    })
+}
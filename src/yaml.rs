@@ -0,0 +1,127 @@
+//! YAML settings format source file, uses `serde_yaml` for settings files that are edited by hand, e.g. infrastructure configs
+#![warn(missing_docs)]
+
+use crate::backend::get_backend;
+use crate::format::{load_settings_with_format, save_settings_with_format, Format};
+use crate::{LoadSettingsError, SaveSettingsError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Saves a serializable settings object as YAML to `USER_HOME/crate_name/file_name`
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Server{
+/// host: String,
+/// port: u16,
+/// }
+///
+/// let server = Server{ host: "localhost".to_string(), port: 8080 };
+///
+/// save_settings_yaml(env!("CARGO_CRATE_NAME"), "server.yaml", &server).expect("Unable to save yaml settings");
+///
+/// let loaded_server = load_settings_yaml::<Server>(env!("CARGO_CRATE_NAME"), "server.yaml").expect("Unable to load yaml settings");
+///
+/// assert_eq!(server,loaded_server);
+/// ```
+pub fn save_settings_yaml<T>(
+    crate_name: &str,
+    file_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_with_format(crate_name, file_name, settings, Format::Yaml)
+}
+
+/// Loads a YAML settings file from `USER_HOME/crate_name/file_name`.
+/// Anchors and aliases are resolved, along with `<<` merge keys, so repeated sections can share their values.
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::prelude::*;
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Server{
+/// host: String,
+/// port: u16,
+/// }
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Servers{
+/// primary: Server,
+/// backup: Server,
+/// }
+///
+/// let file_contents = "
+/// primary: &server
+///   host: example.com
+///   port: 443
+/// backup:
+///   <<: *server
+///   host: backup.example.com
+/// ";
+/// let path = get_user_home().unwrap().join(env!("CARGO_CRATE_NAME")).join("servers.yml");
+/// std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+/// std::fs::write(&path, file_contents).unwrap();
+///
+/// let servers = load_settings_yaml::<Servers>(env!("CARGO_CRATE_NAME"), "servers.yml").unwrap();
+/// assert_eq!(servers.backup, Server{ host: "backup.example.com".to_string(), port: 443 });
+///
+/// delete_setting_file(env!("CARGO_CRATE_NAME"), "servers.yml").unwrap();
+/// ```
+pub fn load_settings_yaml<T>(crate_name: &str, file_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings_with_format(crate_name, file_name, Format::Yaml)
+}
+
+/// Saves a serializable settings object as YAML to `USER_HOME/crate_name/crate_name.yaml`
+pub fn save_default_settings_yaml<T>(
+    crate_name: &str,
+    settings: &T,
+) -> Result<(), SaveSettingsError>
+where
+    T: Serialize,
+{
+    save_settings_yaml(crate_name, &format!("{}.yaml", crate_name), settings)
+}
+
+/// Loads the YAML settings file `USER_HOME/crate_name/crate_name.yaml`, or `crate_name.yml` if there is no `.yaml` file,
+/// so files named either way by hand are found
+/// ```
+/// # let _temp_dir = cr_program_settings::testing::TempSettingsDir::new();
+/// use serde::{Deserialize, Serialize};
+/// use cr_program_settings::yaml::{load_default_settings_yaml, save_settings_yaml};
+///
+/// #[derive(Serialize,Deserialize, PartialEq, Debug)]
+/// struct Settings{
+/// setting1: u32,
+/// }
+///
+/// let crate_name = "cr_program_settings_yaml_doc";
+/// save_settings_yaml(crate_name, "cr_program_settings_yaml_doc.yml", &Settings{ setting1: 4 }).unwrap();
+/// assert_eq!(load_default_settings_yaml::<Settings>(crate_name).unwrap(), Settings{ setting1: 4 });
+///
+/// cr_program_settings::delete_settings_folder(crate_name).unwrap();
+/// ```
+pub fn load_default_settings_yaml<T>(crate_name: &str) -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let yaml_file_name = format!("{}.yaml", crate_name);
+    let yml_file_name = format!("{}.yml", crate_name);
+    let file_name = if !get_backend().exists(Path::new(crate_name), &yaml_file_name)
+        && get_backend().exists(Path::new(crate_name), &yml_file_name)
+    {
+        yml_file_name
+    } else {
+        yaml_file_name
+    };
+    load_settings_yaml(crate_name, &file_name)
+}
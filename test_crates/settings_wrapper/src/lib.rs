@@ -0,0 +1,23 @@
+//! An intermediary library that wraps the `cr_program_settings` macros, the way a framework might save settings
+//! on behalf of the application using it. The macros expand here, so `CARGO_CRATE_NAME` is `settings_wrapper`.
+
+use cr_program_settings::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Saves settings with `save_settings!`, using the default folder
+pub fn save_wrapped<T: Serialize>(settings: &T) -> Result<(), SaveSettingsError> {
+    save_settings!(settings, "wrapped.ser")
+}
+
+/// Loads settings with `load_settings!`, using the default folder
+pub fn load_wrapped<T>() -> Result<T, LoadSettingsError>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    load_settings!(T, "wrapped.ser")
+}
+
+/// Deletes settings with `delete_settings!`, using the default folder
+pub fn delete_wrapped() -> std::io::Result<()> {
+    delete_settings!("wrapped.ser")
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "async")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::settings_container::SettingsContainer;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+    b: Vec<String>,
+}
+
+#[tokio::test]
+async fn test_async_functions() {
+    let crate_name = "cr_program_settings_async";
+    let t = TestStruct {
+        a: 7,
+        b: vec!["async".to_string(), "settings".to_string()],
+    };
+
+    save_settings_async(crate_name, &t).await.unwrap();
+    let settings_file = get_user_home()
+        .unwrap()
+        .join(crate_name)
+        .join("cr_program_settings_async.ser");
+    assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+    assert_eq!(
+        load_settings_async::<TestStruct>(crate_name).await.unwrap(),
+        t
+    );
+
+    let container = SettingsContainer::new(t, crate_name, "container.ser");
+    container.save_async().await.unwrap();
+    let loaded = SettingsContainer::<TestStruct>::load_async(crate_name, "container.ser")
+        .await
+        .unwrap();
+    assert_eq!(loaded, container);
+
+    assert!(
+        load_settings_with_filename_async::<TestStruct>(crate_name, "missing.ser")
+            .await
+            .is_err()
+    );
+
+    delete_settings_folder(crate_name).unwrap();
+}
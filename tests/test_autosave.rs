@@ -0,0 +1,68 @@
+#![cfg(feature = "autosave")]
+
+use cr_program_settings::autosave::AutoSaver;
+use cr_program_settings::prelude::*;
+use cr_program_settings::settings_container::SettingsContainer;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct SliderSettings {
+    value: u32,
+}
+
+#[test]
+fn test_autosave_debounce_and_drop() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_autosave";
+        let file_name = "slider.ser";
+        let container = SettingsContainer::new(SliderSettings { value: 0 }, crate_name, file_name);
+        let auto_saver = AutoSaver::new(container, Duration::from_millis(50));
+
+        for value in 1..=1000 {
+            auto_saver.modify(|settings| settings.value = value);
+        }
+        assert!(auto_saver.is_dirty());
+
+        std::thread::sleep(Duration::from_millis(300));
+        assert!(!auto_saver.is_dirty());
+        assert!(auto_saver.take_last_error().is_none());
+        let loaded = SettingsContainer::<SliderSettings>::load(crate_name, file_name).unwrap();
+        assert_eq!(loaded.get_settings(), &Some(SliderSettings { value: 1000 }));
+
+        // changes made right before dropping are saved by the final save
+        auto_saver.modify(|settings| settings.value = 7);
+        drop(auto_saver);
+        let loaded = SettingsContainer::<SliderSettings>::load(crate_name, file_name).unwrap();
+        assert_eq!(loaded.get_settings(), &Some(SliderSettings { value: 7 }));
+    });
+}
+
+#[test]
+fn test_autosave_error_callback() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_autosave_error";
+        let file_name = "collision.ser";
+        // a directory in place of the settings file makes every save fail
+        std::fs::create_dir_all(get_user_home().unwrap().join(crate_name).join(file_name)).unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = std::sync::Mutex::new(sender);
+        let container = SettingsContainer::new(SliderSettings { value: 0 }, crate_name, file_name);
+        let auto_saver =
+            AutoSaver::new(container, Duration::from_millis(10)).on_error(move |err| {
+                let _ = sender
+                    .lock()
+                    .unwrap()
+                    .send(matches!(err, SaveSettingsError::PathIsDirectory(_)));
+            });
+
+        auto_saver.modify(|settings| settings.value = 1);
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).unwrap());
+        assert!(matches!(
+            auto_saver.take_last_error(),
+            Some(SaveSettingsError::PathIsDirectory(_))
+        ));
+    });
+}
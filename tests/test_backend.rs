@@ -0,0 +1,46 @@
+use cr_program_settings::backend::{set_backend, MemoryBackend};
+use cr_program_settings::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+    b: String,
+}
+
+#[test]
+fn test_memory_backend() {
+    // the backend is global, so every assertion about it lives in this one test
+    set_backend(Box::new(MemoryBackend::new()));
+    let crate_name = "cr_program_settings_memory";
+    let t = TestStruct {
+        a: 5,
+        b: "in memory".to_string(),
+    };
+
+    save_settings(crate_name, &t).unwrap();
+    save_settings_with_filename(crate_name, "second.ser", &t).unwrap();
+    assert!(!get_user_home().unwrap().join(crate_name).exists());
+
+    assert_eq!(load_settings::<TestStruct>(crate_name).unwrap(), t);
+    assert_eq!(
+        list_settings_files(crate_name).unwrap(),
+        vec![
+            PathBuf::from(crate_name).join("cr_program_settings_memory.ser"),
+            PathBuf::from(crate_name).join("second.ser"),
+        ]
+    );
+    assert!(SETTINGS_PATHS
+        .read()
+        .unwrap()
+        .contains(&PathBuf::from(crate_name).join("second.ser")));
+
+    delete_setting_file(crate_name, "second.ser").unwrap();
+    assert!(load_settings_with_filename::<TestStruct>(crate_name, "second.ser").is_err());
+    assert!(delete_setting_file(crate_name, "second.ser").is_err());
+
+    delete_settings_folder(crate_name).unwrap();
+    assert!(load_settings::<TestStruct>(crate_name).is_err());
+    assert!(SETTINGS_PATHS.read().unwrap().is_empty());
+}
@@ -0,0 +1,102 @@
+use cr_program_settings::batch::{save_settings_batch, BatchItem};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_save_settings_batch() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_batch";
+        let other_crate_name = "cr_program_settings_batch_other";
+        save_settings_batch(vec![
+            BatchItem::new(crate_name, "first.ser", &TestStruct { a: 1 }),
+            BatchItem::new(other_crate_name, "second.ser", &TestStruct { a: 2 }),
+        ])
+        .unwrap();
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "first.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(other_crate_name, "second.ser").unwrap(),
+            TestStruct { a: 2 }
+        );
+
+        // the second item can not be moved into place, so the first is restored
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir(settings_dir.join("directory.ser")).unwrap();
+        let err = save_settings_batch(vec![
+            BatchItem::new(crate_name, "first.ser", &TestStruct { a: 10 }),
+            BatchItem::new(crate_name, "new.ser", &TestStruct { a: 11 }),
+            BatchItem::new(crate_name, "directory.ser", &TestStruct { a: 12 }),
+        ])
+        .unwrap_err();
+        assert_eq!(err.index, 2);
+        assert_eq!(err.file_name, "directory.ser");
+        assert!(matches!(err.error, SaveSettingsError::PathIsDirectory(_)));
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "first.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        assert_eq!(
+            list_settings_files(crate_name).unwrap(),
+            vec![settings_dir.join("first.ser")]
+        );
+
+        // a serialization failure writes nothing at all
+        let err = save_settings_batch(vec![
+            BatchItem::new(crate_name, "first.ser", &TestStruct { a: 20 }),
+            BatchItem::new(crate_name, "array.ser", &vec![1, 2]),
+        ])
+        .unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(matches!(err.error, SaveSettingsError::RootNotATable(_)));
+        assert_eq!(
+            list_settings_files(crate_name).unwrap(),
+            vec![settings_dir.join("first.ser")]
+        );
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_settings_batch_unreadable_file() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_batch_unreadable";
+        save_settings_with_filename(crate_name, "first.ser", &TestStruct { a: 1 }).unwrap();
+        // a link to itself exists but can never be read, so its contents could not be restored
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        let looped = settings_dir.join("looped.ser");
+        std::os::unix::fs::symlink(&looped, &looped).unwrap();
+
+        let err = save_settings_batch(vec![
+            BatchItem::new(crate_name, "first.ser", &TestStruct { a: 10 }),
+            BatchItem::new(crate_name, "looped.ser", &TestStruct { a: 11 }),
+        ])
+        .unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.file_name, "looped.ser");
+        assert!(matches!(err.error, SaveSettingsError::IOError(_)));
+
+        // nothing was moved into place, and the temporary files are gone
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "first.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        assert!(std::fs::symlink_metadata(&looped)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        let mut names: Vec<String> = std::fs::read_dir(&settings_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["first.ser", "looped.ser"]);
+    });
+}
@@ -0,0 +1,59 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct BorrowedSettings<'a> {
+    #[serde(borrow)]
+    title: Cow<'a, str>,
+    #[serde(borrow)]
+    tags: Vec<Cow<'a, str>>,
+    count: u32,
+}
+
+#[test]
+fn test_load_settings_borrowed() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_borrowed";
+        let settings = BorrowedSettings {
+            title: Cow::Borrowed("a \"quoted\" title"),
+            tags: vec![Cow::Borrowed("one"), Cow::Borrowed("two")],
+            count: 2,
+        };
+        save_settings_with_filename(crate_name, "borrowed.ser", &settings).unwrap();
+
+        let buffer = read_settings_string(crate_name, "borrowed.ser").unwrap();
+        let loaded = load_settings_borrowed::<BorrowedSettings>(&buffer).unwrap();
+        assert_eq!(loaded, settings);
+
+        // the same buffer can be parsed more than once
+        let raw = load_settings_borrowed::<toml::Value>(&buffer).unwrap();
+        assert_eq!(raw.get("count"), Some(&toml::Value::Integer(2)));
+
+        assert!(matches!(
+            load_settings_borrowed::<BorrowedSettings>("title = [not toml"),
+            Err(LoadSettingsError::DeserializationError(_))
+        ));
+    });
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_deserialize_borrowed_json() {
+    use cr_program_settings::format::Format;
+
+    #[derive(Deserialize)]
+    struct JsonSettings<'a> {
+        name: &'a str,
+    }
+
+    let file_data = br#"{ "name": "zero copy" }"#.to_vec();
+    let settings = Format::Json
+        .deserialize_borrowed::<JsonSettings>(&file_data)
+        .unwrap();
+    assert_eq!(settings.name, "zero copy");
+    // the field points into the buffer rather than a copy of it
+    let buffer_range = file_data.as_ptr_range();
+    assert!(buffer_range.contains(&settings.name.as_ptr()));
+}
@@ -0,0 +1,58 @@
+use cr_program_settings::backend::{reset_backend, set_backend, MemoryBackend};
+use cr_program_settings::prelude::*;
+use cr_program_settings::profiles::{list_profiles, save_profile};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+/// Saves two settings files and two profiles in `crate_name`
+fn save_files(crate_name: &str) {
+    save_settings_with_filename(crate_name, "a.ser", &TestStruct { a: 1 }).unwrap();
+    save_settings_with_filename(crate_name, "b.ser", &TestStruct { a: 2 }).unwrap();
+    save_profile(crate_name, "work", &TestStruct { a: 3 }).unwrap();
+    save_profile(crate_name, "home", &TestStruct { a: 4 }).unwrap();
+}
+
+/// Clears the files of `crate_name`, for whichever backend is active
+fn assert_clear(crate_name: &str) {
+    save_files(crate_name);
+    assert_eq!(clear_settings(crate_name, false).unwrap(), 2);
+    assert!(list_settings_files(crate_name).unwrap().is_empty());
+    assert_eq!(list_profiles(crate_name).unwrap(), vec!["home", "work"]);
+    assert!(!SETTINGS_PATHS
+        .read()
+        .unwrap()
+        .iter()
+        .any(|path| path.ends_with("a.ser") || path.ends_with("b.ser")));
+
+    save_files(crate_name);
+    assert_eq!(clear_settings(crate_name, true).unwrap(), 4);
+    assert!(list_profiles(crate_name).unwrap_or_default().is_empty());
+    assert!(load_settings_with_filename::<TestStruct>(crate_name, "a.ser").is_err());
+}
+
+// swaps the global backend, so both backends are checked in a single test
+#[test]
+fn test_clear_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_clear";
+        assert!(clear_settings(crate_name, true).is_err());
+
+        assert_clear(crate_name);
+        // the folder and its subfolders are kept
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        assert!(settings_dir.is_dir());
+        assert!(settings_dir.join("profiles").is_dir());
+
+        // clearing an empty folder deletes nothing
+        assert_eq!(clear_settings(crate_name, true).unwrap(), 0);
+    });
+
+    set_backend(Box::new(MemoryBackend::new()));
+    assert_clear("cr_program_settings_clear_memory");
+    reset_backend();
+}
@@ -0,0 +1,58 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+// the current directory is process wide, so every case lives in a single test
+#[test]
+fn test_custom_path() {
+    with_temp_settings_dir(|| {
+        let export_dir = get_user_home().unwrap().join("exports");
+        let export_path = export_dir.join("nested").join("export.toml");
+
+        // missing parents are created
+        save_settings_to_path(&export_path, &TestStruct { a: 1 }).unwrap();
+        assert!(export_path.is_file());
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&export_path));
+        assert_eq!(
+            load_settings_from_path::<TestStruct>(&export_path).unwrap(),
+            TestStruct { a: 1 }
+        );
+
+        save_settings_at!(TestStruct { a: 2 }, &export_path).unwrap();
+        assert_eq!(
+            load_settings_at!(TestStruct, &export_path).unwrap(),
+            TestStruct { a: 2 }
+        );
+
+        // relative paths resolve against the current directory
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&export_dir).unwrap();
+        save_settings_at!(TestStruct { a: 3 }, "relative.toml").unwrap();
+        assert_eq!(
+            load_settings_at!(TestStruct, "relative.toml").unwrap(),
+            TestStruct { a: 3 }
+        );
+        std::env::set_current_dir(original_dir).unwrap();
+        let relative_path = export_dir.join("relative.toml");
+        assert!(relative_path.is_file());
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&relative_path));
+
+        assert!(matches!(
+            save_settings_to_path(&export_dir, &TestStruct { a: 4 }),
+            Err(SaveSettingsError::PathIsDirectory(_))
+        ));
+        assert!(matches!(
+            load_settings_from_path::<TestStruct>(&export_dir),
+            Err(LoadSettingsError::PathIsDirectory(_))
+        ));
+        assert!(matches!(
+            load_settings_from_path::<TestStruct>(&export_dir.join("missing.toml")),
+            Err(LoadSettingsError::IOError(_))
+        ));
+    });
+}
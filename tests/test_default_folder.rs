@@ -0,0 +1,43 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{default_folder, reset_default_folder, set_default_folder};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_default_folder() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        assert_eq!(default_folder("crate_name"), "crate_name");
+
+        set_default_folder("ProductName");
+        assert_eq!(default_folder("crate_name"), "ProductName");
+
+        save_settings!(TestStruct { a: 1 }).unwrap();
+        assert!(home.join("ProductName").join("ProductName.ser").is_file());
+        assert_eq!(load_settings!(TestStruct).unwrap(), TestStruct { a: 1 });
+
+        // explicit arguments take priority over the default folder
+        save_settings!(TestStruct { a: 2 }, "explicit.ser", "ExplicitFolder").unwrap();
+        assert!(home.join("ExplicitFolder").join("explicit.ser").is_file());
+        assert!(load_settings!(TestStruct, "explicit.ser").is_err());
+
+        // only the default file is deleted, not the rest of the folder
+        save_settings!(TestStruct { a: 1 }, "other.ser").unwrap();
+        delete_settings!().unwrap();
+        assert!(!home.join("ProductName").join("ProductName.ser").exists());
+        assert!(home.join("ProductName").join("other.ser").is_file());
+
+        // the crate name is used again after resetting
+        reset_default_folder();
+        save_settings!(TestStruct { a: 3 }).unwrap();
+        assert!(home
+            .join(env!("CARGO_CRATE_NAME"))
+            .join(format!("{}.ser", env!("CARGO_CRATE_NAME")))
+            .is_file());
+    });
+}
@@ -0,0 +1,73 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_delete_setting_file_if_exists() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_delete_if_exists";
+        let settings_file = get_user_home().unwrap().join(crate_name).join("file.ser");
+        assert!(!delete_setting_file_if_exists(crate_name, "file.ser").unwrap());
+
+        save_settings_with_filename(crate_name, "file.ser", &TestStruct { a: 1 }).unwrap();
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+        assert!(delete_setting_file_if_exists(crate_name, "file.ser").unwrap());
+        assert!(!settings_file.exists());
+        assert!(!SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+
+        // a file removed behind the libraries back is still forgotten
+        save_settings_with_filename(crate_name, "file.ser", &TestStruct { a: 1 }).unwrap();
+        std::fs::remove_file(&settings_file).unwrap();
+        assert!(!delete_setting_file_if_exists(crate_name, "file.ser").unwrap());
+        assert!(!SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+
+        // other errors are still reported
+        std::fs::create_dir_all(&settings_file).unwrap();
+        assert!(delete_setting_file_if_exists(crate_name, "file.ser").is_err());
+    });
+}
+
+#[test]
+fn test_delete_settings_folder_if_exists() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_delete_folder_if_exists";
+        assert!(!delete_settings_folder_if_exists(crate_name).unwrap());
+        save_settings_with_filename(crate_name, "file.ser", &TestStruct { a: 1 }).unwrap();
+        assert!(delete_settings_folder_if_exists(crate_name).unwrap());
+        assert!(!get_user_home().unwrap().join(crate_name).exists());
+        assert!(!delete_settings_folder_if_exists(crate_name).unwrap());
+    });
+}
+
+#[test]
+fn test_delete_settings_if_exists_macro() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let folder = env!("CARGO_CRATE_NAME");
+        assert!(!delete_settings_if_exists!().unwrap());
+        save_settings!(TestStruct { a: 1 }).unwrap();
+        save_settings!(TestStruct { a: 1 }, "other.ser").unwrap();
+        save_settings!(
+            TestStruct { a: 1 },
+            "elsewhere.ser",
+            "cr_program_settings_elsewhere"
+        )
+        .unwrap();
+
+        assert!(delete_settings_if_exists!().unwrap());
+        assert!(!home.join(folder).join(format!("{}.ser", folder)).exists());
+        assert!(delete_settings_if_exists!("other.ser").unwrap());
+        assert!(!delete_settings_if_exists!("other.ser").unwrap());
+        assert!(
+            delete_settings_if_exists!("elsewhere.ser", "cr_program_settings_elsewhere").unwrap()
+        );
+        assert!(
+            !delete_settings_if_exists!("elsewhere.ser", "cr_program_settings_elsewhere").unwrap()
+        );
+    });
+}
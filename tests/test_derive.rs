@@ -0,0 +1,43 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings_derive::ProgramSettings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, ProgramSettings, PartialEq, Debug)]
+#[settings(folder = "cr_program_settings_derive_test", file = "ui.toml")]
+struct UiSettings {
+    dark_mode: bool,
+    font: String,
+}
+
+#[derive(Serialize, Deserialize, ProgramSettings, PartialEq, Debug)]
+#[settings(folder = "cr_program_settings_derive_test")]
+struct NetworkSettings {
+    port: u16,
+}
+
+#[test]
+fn test_derive_program_settings() {
+    let ui = UiSettings {
+        dark_mode: true,
+        font: "monospace".to_string(),
+    };
+    let network = NetworkSettings { port: 8080 };
+
+    assert_eq!(UiSettings::FILE_NAME, "ui.toml");
+    assert_eq!(NetworkSettings::FILE_NAME, "NetworkSettings.ser");
+    assert!(
+        NetworkSettings::path().ends_with("cr_program_settings_derive_test/NetworkSettings.ser")
+    );
+
+    ui.save().unwrap();
+    network.save().unwrap();
+
+    assert_eq!(UiSettings::load().unwrap(), ui);
+    assert_eq!(NetworkSettings::load().unwrap(), network);
+
+    UiSettings::delete().unwrap();
+    assert!(UiSettings::load().is_err());
+    assert_eq!(NetworkSettings::load().unwrap(), network);
+
+    delete_settings_folder(NetworkSettings::FOLDER).unwrap();
+}
@@ -0,0 +1,102 @@
+use cr_program_settings::diff::{diff_settings, SettingsDiff};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct DiffSettings {
+    name: String,
+    tags: Vec<String>,
+    servers: Vec<Server>,
+}
+
+fn server(host: &str, port: u16) -> Server {
+    Server {
+        host: host.to_string(),
+        port,
+    }
+}
+
+#[test]
+fn test_diff_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_diff";
+        let old = DiffSettings {
+            name: "old".to_string(),
+            tags: vec!["a".to_string()],
+            servers: vec![server("a.example.com", 80), server("b.example.com", 80)],
+        };
+
+        // a missing file lists every key as added
+        let diff = diff_settings(crate_name, "diff.ser", &old).unwrap();
+        let added: Vec<&str> = diff.added().iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(added, vec!["name", "servers", "tags"]);
+
+        save_settings_with_filename(crate_name, "diff.ser", &old).unwrap();
+        let diff = diff_settings(crate_name, "diff.ser", &old).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes\n");
+        assert!(diff.to_lines().is_empty());
+
+        let new = DiffSettings {
+            name: "new".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+            servers: vec![server("a.example.com", 8080)],
+        };
+        let diff = diff_settings(crate_name, "diff.ser", &new).unwrap();
+        assert!(diff.added().is_empty());
+        assert_eq!(diff.removed().len(), 1);
+        assert_eq!(diff.removed()[0].0, "servers[1]");
+        let changed: Vec<&str> = diff
+            .changed()
+            .iter()
+            .map(|(path, _, _)| path.as_str())
+            .collect();
+        assert_eq!(changed, vec!["name", "servers[0].port", "tags"]);
+        assert_eq!(
+            diff.to_string(),
+            "- servers[1] = { host = \"b.example.com\", port = 80 }\n\
+             ~ name: \"old\" -> \"new\"\n\
+             ~ servers[0].port: 80 -> 8080\n\
+             ~ tags: [\"a\"] -> [\"a\", \"b\"]\n"
+        );
+        assert_eq!(
+            diff.to_lines(),
+            vec![
+                "- servers[1] = { host = \"b.example.com\", port = 80 }",
+                "~ name: \"old\" -> \"new\"",
+                "~ servers[0].port: 80 -> 8080",
+                "~ tags: [\"a\"] -> [\"a\", \"b\"]",
+            ]
+        );
+
+        // the file is not modified
+        assert_eq!(
+            load_settings_with_filename::<DiffSettings>(crate_name, "diff.ser").unwrap(),
+            old
+        );
+    });
+}
+
+#[test]
+fn test_settings_diff_between() {
+    let old: Value = toml::from_str("a = 1\n[nested]\nb = 2\nc = 3\n").unwrap();
+    let new: Value = toml::from_str("a = 1\nd = 4\n[nested]\nb = 5\n").unwrap();
+    let diff = SettingsDiff::between(&old, &new);
+    assert_eq!(diff.added(), &[("d".to_string(), Value::Integer(4))]);
+    assert_eq!(
+        diff.removed(),
+        &[("nested.c".to_string(), Value::Integer(3))]
+    );
+    assert_eq!(
+        diff.changed(),
+        &[("nested.b".to_string(), Value::Integer(2), Value::Integer(5))]
+    );
+}
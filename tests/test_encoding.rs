@@ -0,0 +1,98 @@
+use cr_program_settings::format::{load_settings_with_format, Format};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct EncodedSettings {
+    city: String,
+}
+
+const CRATE_NAME: &str = "cr_program_settings_encoding";
+
+/// Writes a settings file with exactly the given bytes
+fn write_file(file_name: &str, file_data: &[u8]) {
+    let settings_dir = get_user_home().unwrap().join(CRATE_NAME);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join(file_name), file_data).unwrap();
+}
+
+#[test]
+fn test_byte_order_mark() {
+    with_temp_settings_dir(|| {
+        // as saved by Notepad
+        write_file("bom.ser", "\u{feff}city = \"Zürich\"\n".as_bytes());
+        let expected = EncodedSettings {
+            city: "Zürich".to_string(),
+        };
+        assert_eq!(
+            load_settings_with_filename::<EncodedSettings>(CRATE_NAME, "bom.ser").unwrap(),
+            expected
+        );
+        assert_eq!(
+            load_settings_with_format::<EncodedSettings>(CRATE_NAME, "bom.ser", Format::Toml)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            Format::Toml
+                .deserialize::<EncodedSettings>("\u{feff}city = \"Zürich\"".as_bytes())
+                .unwrap(),
+            expected
+        );
+
+        // the raw text is returned without the byte order mark
+        assert_eq!(
+            read_settings_string(CRATE_NAME, "bom.ser").unwrap(),
+            "city = \"Zürich\"\n"
+        );
+
+        // error locations line up with the file as the editor shows it
+        let file_contents = "\u{feff}city = 5\n";
+        write_file("bom_error.ser", file_contents.as_bytes());
+        let err = load_settings_with_filename::<EncodedSettings>(CRATE_NAME, "bom_error.ser")
+            .unwrap_err();
+        let location = err.location(file_contents).unwrap();
+        assert_eq!((location.line, location.column), (1, 8));
+    });
+}
+
+#[test]
+fn test_invalid_encoding() {
+    with_temp_settings_dir(|| {
+        // "Zürich" encoded as Latin-1, the ü is the single byte 0xFC
+        write_file("latin1.ser", b"city = \"Z\xfcrich\"\n");
+        for result in [
+            load_settings_with_filename::<EncodedSettings>(CRATE_NAME, "latin1.ser"),
+            load_settings_with_format::<EncodedSettings>(CRATE_NAME, "latin1.ser", Format::Toml),
+        ] {
+            match result {
+                Err(LoadSettingsError::InvalidEncoding { path, position }) => {
+                    assert_eq!(
+                        path,
+                        get_user_home().unwrap().join(CRATE_NAME).join("latin1.ser")
+                    );
+                    assert_eq!(position, 9);
+                }
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+        assert!(matches!(
+            read_settings_string(CRATE_NAME, "latin1.ser"),
+            Err(LoadSettingsError::InvalidEncoding { position: 9, .. })
+        ));
+        assert!(matches!(
+            Format::Toml.deserialize_borrowed::<EncodedSettings>(b"city = \"Z\xfcrich\"\n"),
+            Err(LoadSettingsError::InvalidEncoding { position: 9, .. })
+        ));
+
+        // positions count the byte order mark
+        let mut file_data = "\u{feff}".as_bytes().to_vec();
+        file_data.extend_from_slice(b"city = \"\xff\"\n");
+        write_file("bom_latin1.ser", &file_data);
+        assert!(matches!(
+            load_settings_with_filename::<EncodedSettings>(CRATE_NAME, "bom_latin1.ser"),
+            Err(LoadSettingsError::InvalidEncoding { position: 11, .. })
+        ));
+    });
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "encryption")]
+
+use cr_program_settings::encryption::{is_encrypted, SecretKey};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Credentials {
+    api_token: String,
+}
+
+#[test]
+fn test_encrypted_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_encryption";
+        let credentials = Credentials {
+            api_token: "super secret token".to_string(),
+        };
+        let key = SecretKey::from_password("password", b"test salt").unwrap();
+        assert_eq!(
+            key,
+            SecretKey::from_password("password", b"test salt").unwrap()
+        );
+        assert_ne!(
+            key,
+            SecretKey::from_password("password", b"other salt").unwrap()
+        );
+        assert_eq!(format!("{:?}", key), "SecretKey(..)");
+
+        save_settings_encrypted(crate_name, "credentials.enc", &credentials, &key).unwrap();
+        let settings_file = get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("credentials.enc");
+        let file_data = std::fs::read(&settings_file).unwrap();
+        assert!(is_encrypted(&file_data));
+        assert!(!String::from_utf8_lossy(&file_data).contains("super secret token"));
+        assert_eq!(
+            load_settings_encrypted::<Credentials>(crate_name, "credentials.enc", &key).unwrap(),
+            credentials
+        );
+
+        // a tampered file fails to decrypt instead of failing to deserialize
+        let mut tampered = file_data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        std::fs::write(&settings_file, tampered).unwrap();
+        assert!(matches!(
+            load_settings_encrypted::<Credentials>(crate_name, "credentials.enc", &key),
+            Err(LoadSettingsError::DecryptionFailed)
+        ));
+
+        // plaintext settings are detected
+        save_settings_with_filename(crate_name, "plain.ser", &credentials).unwrap();
+        assert!(matches!(
+            load_settings_encrypted::<Credentials>(crate_name, "plain.ser", &key),
+            Err(LoadSettingsError::NotEncrypted)
+        ));
+    });
+}
@@ -0,0 +1,97 @@
+use cr_program_settings::options::{load_settings_with_options, LoadOptions, UnknownEnvVar};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Paths {
+    cache: String,
+    plugins: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Settings {
+    name: String,
+    count: u32,
+    paths: Paths,
+    aliases: BTreeMap<String, String>,
+}
+
+#[test]
+fn test_env_expansion() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_env_expansion";
+        let file_name = "expand.toml";
+        std::env::set_var("CR_ENV_EXPANSION_ROOT", "/opt/app");
+        std::env::set_var("CR_ENV_EXPANSION_USER", "ferris");
+        std::env::remove_var("CR_ENV_EXPANSION_MISSING");
+
+        let settings = Settings {
+            name: "$CR_ENV_EXPANSION_USER-${CR_ENV_EXPANSION_USER}!".to_string(),
+            count: 3,
+            paths: Paths {
+                cache: "${CR_ENV_EXPANSION_ROOT}/cache".to_string(),
+                plugins: vec![
+                    "$CR_ENV_EXPANSION_ROOT/plugins".to_string(),
+                    "costs $$5, not $ 5 or ${} or $1".to_string(),
+                ],
+            },
+            aliases: BTreeMap::from([(
+                "$CR_ENV_EXPANSION_USER".to_string(),
+                "${CR_ENV_EXPANSION_MISSING}/home".to_string(),
+            )]),
+        };
+        save_settings_with_filename(crate_name, file_name, &settings).unwrap();
+
+        // expansion is opt-in
+        assert_eq!(
+            load_settings_with_options::<Settings>(crate_name, file_name, &LoadOptions::new())
+                .unwrap(),
+            settings
+        );
+
+        let options = LoadOptions::new().expand_env(true);
+        assert!(options.get_expand_env());
+        assert_eq!(options.get_unknown_env(), UnknownEnvVar::Keep);
+        let expanded =
+            load_settings_with_options::<Settings>(crate_name, file_name, &options).unwrap();
+        assert_eq!(
+            expanded,
+            Settings {
+                name: "ferris-ferris!".to_string(),
+                count: 3,
+                paths: Paths {
+                    cache: "/opt/app/cache".to_string(),
+                    plugins: vec![
+                        "/opt/app/plugins".to_string(),
+                        "costs $5, not $ 5 or ${} or $1".to_string(),
+                    ],
+                },
+                // keys are never expanded, and unknown variables are kept as written
+                aliases: BTreeMap::from([(
+                    "$CR_ENV_EXPANSION_USER".to_string(),
+                    "${CR_ENV_EXPANSION_MISSING}/home".to_string(),
+                )]),
+            }
+        );
+
+        let options = options.unknown_env(UnknownEnvVar::Error);
+        assert!(matches!(
+            load_settings_with_options::<Settings>(crate_name, file_name, &options),
+            Err(LoadSettingsError::UnknownEnvVar(name)) if name == "CR_ENV_EXPANSION_MISSING"
+        ));
+
+        // an expanded value that does not fit the field is reported with the file
+        let mut wrong_type = toml::Table::try_from(&settings).unwrap();
+        wrong_type.insert("count".to_string(), "$CR_ENV_EXPANSION_ROOT".into());
+        save_settings_with_filename(crate_name, file_name, &wrong_type).unwrap();
+        let options = LoadOptions::new().expand_env(true);
+        match load_settings_with_options::<Settings>(crate_name, file_name, &options) {
+            Err(LoadSettingsError::ParseError(err)) => {
+                assert!(err.path().ends_with(file_name));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    });
+}
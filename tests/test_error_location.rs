@@ -0,0 +1,178 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct EditedSettings {
+    name: String,
+    volume: u32,
+}
+
+/// Writes `file_contents` as the settings file and returns the error of loading it
+fn load_error(file_contents: &str) -> LoadSettingsError {
+    let crate_name = "cr_program_settings_error_location";
+    let settings_dir = get_user_home().unwrap().join(crate_name);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join("edited.ser"), file_contents).unwrap();
+    load_settings_with_filename::<EditedSettings>(crate_name, "edited.ser").unwrap_err()
+}
+
+#[test]
+fn test_error_location() {
+    with_temp_settings_dir(|| {
+        // a syntax error on the third line
+        let file_contents = "name = \"ü\"\n\nvolume = = 3\n";
+        let err = load_error(file_contents);
+        let span = err.span().unwrap();
+        assert_eq!(&file_contents[span.start..span.start + 1], "=");
+        let location = err.location(file_contents).unwrap();
+        assert_eq!((location.line, location.column), (3, 10));
+        assert!(!location.message.is_empty());
+        assert!(!location.message.contains("line"));
+
+        // a type error on the second line
+        let file_contents = "name = \"x\"\nvolume = \"loud\"\n";
+        let location = load_error(file_contents).location(file_contents).unwrap();
+        assert_eq!((location.line, location.column), (2, 10));
+
+        // columns count characters rather than bytes
+        let file_contents = "name = \"ü\" = 1\n";
+        let location = load_error(file_contents).location(file_contents).unwrap();
+        assert_eq!((location.line, location.column), (1, 12));
+
+        // a missing field points at the table it is missing from
+        let file_contents = "name = \"x\"\n";
+        let err = load_error(file_contents);
+        assert!(matches!(err, LoadSettingsError::ParseError(_)));
+        let location = err.location(file_contents).unwrap();
+        assert_eq!((location.line, location.column), (1, 1));
+        assert!(location.message.contains("volume"));
+
+        // errors that are not parse errors have no location
+        let err = load_settings_with_filename::<EditedSettings>(
+            "cr_program_settings_error_location",
+            "missing.ser",
+        )
+        .unwrap_err();
+        assert_eq!(err.span(), None);
+        assert_eq!(err.location(""), None);
+    });
+}
+
+#[test]
+fn test_parse_error_context() {
+    with_temp_settings_dir(|| {
+        let file_contents = "name = \"x\"\n\nvolume = = 3\n";
+        let err = match load_error(file_contents) {
+            LoadSettingsError::ParseError(err) => err,
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+        let path = get_user_home()
+            .unwrap()
+            .join("cr_program_settings_error_location")
+            .join("edited.ser");
+        assert_eq!(err.path(), path);
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.column(), Some(10));
+        assert_eq!(err.line_text(), Some("volume = = 3"));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "failed to parse {} at line 3: {}",
+                path.display(),
+                err.message()
+            )
+        );
+        assert!(!err.message().contains("line"));
+
+        // the toml error stays available for programmatic handling
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.to_string(), err.toml_error().to_string());
+        assert_eq!(err.toml_error().span(), Some(21..22));
+
+        // the line is found after a byte order mark
+        let err = load_error("\u{feff}volume = \"loud\"\nname = \"x\"\n");
+        match err {
+            LoadSettingsError::ParseError(err) => {
+                assert_eq!(err.line(), Some(1));
+                assert_eq!(err.line_text(), Some("volume = \"loud\""));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    });
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_error_location() {
+    use cr_program_settings::format::{load_settings_with_format, Format};
+
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_json_error_location";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        let file_contents = "{\n  \"name\": \"x\",\n  \"volume\": loud\n}";
+        std::fs::write(settings_dir.join("edited.json"), file_contents).unwrap();
+
+        let err =
+            load_settings_with_format::<EditedSettings>(crate_name, "edited.json", Format::Json)
+                .unwrap_err();
+        assert_eq!(err.span(), None);
+        let location = err.location(file_contents).unwrap();
+        assert_eq!(location.line, 3);
+        assert!(!location.message.contains("line"));
+    });
+}
+
+#[test]
+fn test_parse_error_context_with_options() {
+    use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_error_location_options";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(
+            settings_dir.join("edited.toml"),
+            "name = \"${HOME}\"\n\nvolume = \"loud\"\n",
+        )
+        .unwrap();
+        std::fs::write(settings_dir.join("included.toml"), "volume = \"quiet\"\n").unwrap();
+        std::fs::write(
+            settings_dir.join("including.toml"),
+            "include = [\"included.toml\"]\nname = \"x\"\n",
+        )
+        .unwrap();
+
+        // the offending value is in the loaded file, so its line is reported
+        for options in [
+            LoadOptions::new().expand_env(true),
+            LoadOptions::new().includes(true),
+        ] {
+            match load_settings_with_options::<EditedSettings>(crate_name, "edited.toml", &options)
+            {
+                Err(LoadSettingsError::ParseError(err)) => {
+                    assert_eq!(err.path(), settings_dir.join("edited.toml"));
+                    assert_eq!(err.line(), Some(3));
+                    assert_eq!(err.column(), Some(10));
+                    assert_eq!(err.line_text(), Some("volume = \"loud\""));
+                }
+                other => panic!("expected a parse error, got {:?}", other),
+            }
+        }
+
+        // the offending value came from an included file, which has no position in the loaded file
+        match load_settings_with_options::<EditedSettings>(
+            crate_name,
+            "including.toml",
+            &LoadOptions::new().includes(true),
+        ) {
+            Err(LoadSettingsError::ParseError(err)) => {
+                assert_eq!(err.path(), settings_dir.join("including.toml"));
+                assert_eq!(err.line(), None);
+                assert!(err.message().contains("quiet"));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    });
+}
@@ -0,0 +1,102 @@
+use cr_program_settings::export::{export_settings, import_settings, import_settings_validated};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ExportedSettings {
+    name: String,
+    volume: u32,
+}
+
+#[test]
+fn test_export_import_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_export";
+        let file_name = "exported.ser";
+        let backup_dir = get_user_home().unwrap().join("backups").join("nested");
+        let backup = backup_dir.join("settings.toml");
+
+        let settings = ExportedSettings {
+            name: "export".to_string(),
+            volume: 7,
+        };
+        save_settings_with_filename(crate_name, file_name, &settings).unwrap();
+
+        // missing parent folders of the destination are created
+        export_settings(crate_name, file_name, &backup).unwrap();
+        let live_path = get_user_home().unwrap().join(crate_name).join(file_name);
+        assert_eq!(
+            std::fs::read(&backup).unwrap(),
+            std::fs::read(&live_path).unwrap()
+        );
+
+        // exporting a missing settings file fails without creating the destination
+        let missing_backup = backup_dir.join("missing.toml");
+        let err = export_settings(crate_name, "missing.ser", &missing_backup).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!missing_backup.exists());
+
+        // importing replaces the live file, into a folder that may not exist yet
+        save_settings_with_filename(
+            crate_name,
+            file_name,
+            &ExportedSettings {
+                name: "changed".to_string(),
+                volume: 0,
+            },
+        )
+        .unwrap();
+        import_settings(&backup, crate_name, file_name).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<ExportedSettings>(crate_name, file_name).unwrap(),
+            settings
+        );
+        import_settings(&backup, "cr_program_settings_import", file_name).unwrap();
+        let imported_path = get_user_home()
+            .unwrap()
+            .join("cr_program_settings_import")
+            .join(file_name);
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&imported_path));
+
+        let err =
+            import_settings(&backup_dir.join("missing.toml"), crate_name, file_name).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // a validated import leaves the live file alone if the source does not load
+        let malformed = backup_dir.join("malformed.toml");
+        std::fs::write(&malformed, "name = [not toml").unwrap();
+        assert!(matches!(
+            import_settings_validated::<ExportedSettings>(&malformed, crate_name, file_name),
+            Err(SettingsError::Load(
+                LoadSettingsError::DeserializationError(_)
+            ))
+        ));
+        let wrong_type = backup_dir.join("wrong_type.toml");
+        std::fs::write(&wrong_type, "other = true\n").unwrap();
+        assert!(
+            import_settings_validated::<ExportedSettings>(&wrong_type, crate_name, file_name)
+                .is_err()
+        );
+        assert_eq!(
+            load_settings_with_filename::<ExportedSettings>(crate_name, file_name).unwrap(),
+            settings
+        );
+
+        let imported = ExportedSettings {
+            name: "imported".to_string(),
+            volume: 3,
+        };
+        let valid = backup_dir.join("valid.toml");
+        std::fs::write(&valid, toml::to_string(&imported).unwrap()).unwrap();
+        assert_eq!(
+            import_settings_validated::<ExportedSettings>(&valid, crate_name, file_name).unwrap(),
+            imported
+        );
+        assert_eq!(
+            load_settings_with_filename::<ExportedSettings>(crate_name, file_name).unwrap(),
+            imported
+        );
+    });
+}
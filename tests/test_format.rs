@@ -0,0 +1,73 @@
+#![cfg(all(feature = "json", feature = "bincode"))]
+
+use cr_program_settings::format::{convert_settings, load_settings_with_format, Format};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+    b: Vec<String>,
+}
+
+#[test]
+fn test_convert_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_convert";
+        let t = TestStruct {
+            a: 3,
+            b: vec!["converted".to_string()],
+        };
+        save_settings_with_filename(crate_name, "settings.ser", &t).unwrap();
+
+        let json_name = convert_settings::<TestStruct>(
+            crate_name,
+            "settings.ser",
+            Format::Toml,
+            Format::Json,
+            true,
+        )
+        .unwrap();
+        assert_eq!(json_name, "settings.json");
+        assert!(load_settings_with_filename::<TestStruct>(crate_name, "settings.ser").is_err());
+        let json =
+            std::fs::read_to_string(get_user_home().unwrap().join(crate_name).join(&json_name))
+                .unwrap();
+        assert!(json.trim_start().starts_with('{'));
+        assert_eq!(
+            load_settings_with_format::<TestStruct>(crate_name, &json_name, Format::Json).unwrap(),
+            t
+        );
+
+        let bin_name = convert_settings::<TestStruct>(
+            crate_name,
+            &json_name,
+            Format::Json,
+            Format::Bincode,
+            false,
+        )
+        .unwrap();
+        assert_eq!(bin_name, "settings.bin");
+        assert_eq!(
+            load_settings_bin::<TestStruct>(crate_name, &bin_name).unwrap(),
+            t
+        );
+        assert!(
+            load_settings_with_format::<TestStruct>(crate_name, &json_name, Format::Json).is_ok()
+        );
+
+        assert!(matches!(
+            convert_settings::<TestStruct>(
+                crate_name,
+                &bin_name,
+                Format::Json,
+                Format::Toml,
+                false
+            ),
+            Err(SettingsError::Load(
+                LoadSettingsError::JsonDeserializationError(_)
+            ))
+        ));
+    });
+}
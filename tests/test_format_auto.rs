@@ -0,0 +1,119 @@
+use cr_program_settings::format::{
+    load_settings_any, load_settings_auto, save_settings_auto, Format,
+};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_format_from_file_name() {
+    assert_eq!(Format::from_file_name("a.toml"), Some(Format::Toml));
+    assert_eq!(Format::from_file_name("a.ser"), Some(Format::Toml));
+    assert_eq!(Format::from_file_name("a.TOML"), Some(Format::Toml));
+    assert_eq!(Format::from_file_name("a.unknown"), Some(Format::Toml));
+    assert_eq!(Format::from_file_name(".hidden"), Some(Format::Toml));
+    assert_eq!(Format::from_file_name("a.ron"), None);
+    #[cfg(feature = "yaml")]
+    assert_eq!(Format::from_file_name("a.yml"), Some(Format::Yaml));
+    #[cfg(not(feature = "yaml"))]
+    assert_eq!(Format::from_file_name("a.yaml"), None);
+    #[cfg(feature = "json")]
+    assert_eq!(Format::from_file_name("a.JSON"), Some(Format::Json));
+    #[cfg(not(feature = "json"))]
+    assert_eq!(Format::from_file_name("a.json"), None);
+    #[cfg(feature = "bincode")]
+    assert_eq!(Format::from_file_name("a.bin"), Some(Format::Bincode));
+    #[cfg(not(feature = "bincode"))]
+    assert_eq!(Format::from_file_name("a.bin"), None);
+}
+
+#[test]
+fn test_load_settings_auto() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_format_auto";
+        let settings = TestStruct { a: 4 };
+        save_settings_auto(crate_name, "auto.ser", &settings).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "auto.ser").unwrap(),
+            settings
+        );
+        assert_eq!(
+            load_settings_auto::<TestStruct>(crate_name, "auto.ser").unwrap(),
+            settings
+        );
+
+        #[cfg(feature = "json")]
+        {
+            save_settings_auto(crate_name, "auto.json", &settings).unwrap();
+            let path = get_user_home().unwrap().join(crate_name).join("auto.json");
+            assert!(std::fs::read_to_string(path).unwrap().starts_with('{'));
+            assert_eq!(
+                load_settings_auto::<TestStruct>(crate_name, "auto.json").unwrap(),
+                settings
+            );
+        }
+
+        assert!(matches!(
+            save_settings_auto(crate_name, "auto.ron", &settings),
+            Err(SaveSettingsError::FormatNotEnabled(_))
+        ));
+        assert!(matches!(
+            load_settings_auto::<TestStruct>(crate_name, "auto.ron"),
+            Err(LoadSettingsError::FormatNotEnabled(_))
+        ));
+    });
+}
+
+#[test]
+fn test_load_settings_any() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_load_any";
+        let settings = TestStruct { a: 5 };
+
+        save_settings_with_filename(crate_name, "any.ser", &settings).unwrap();
+        let (loaded, format) = load_settings_any::<TestStruct>(crate_name, "any.ser").unwrap();
+        assert_eq!(loaded, settings);
+        assert_eq!(format, Format::Toml);
+
+        #[cfg(feature = "json")]
+        {
+            cr_program_settings::format::save_settings_with_format(
+                crate_name,
+                "any.ser",
+                &settings,
+                Format::Json,
+            )
+            .unwrap();
+            let (loaded, format) = load_settings_any::<TestStruct>(crate_name, "any.ser").unwrap();
+            assert_eq!(loaded, settings);
+            assert_eq!(format, Format::Json);
+        }
+
+        #[cfg(feature = "yaml")]
+        {
+            cr_program_settings::format::save_settings_with_format(
+                crate_name,
+                "any.ser",
+                &settings,
+                Format::Yaml,
+            )
+            .unwrap();
+            let (loaded, format) = load_settings_any::<TestStruct>(crate_name, "any.ser").unwrap();
+            assert_eq!(loaded, settings);
+            assert_eq!(format, Format::Yaml);
+        }
+
+        // nothing parses, so the error of the format the extension names is returned, with the path of the file
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::write(settings_dir.join("any.ser"), "a = [").unwrap();
+        assert!(matches!(
+            load_settings_any::<TestStruct>(crate_name, "any.ser"),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+    });
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 struct TestStruct {
@@ -22,5 +23,109 @@ fn test_functions() {
 
     assert_eq!(t, loaded_settings);
 
-    delete_settings(crate_name).unwrap();
+    delete_settings_folder(crate_name).unwrap();
+}
+
+#[test]
+fn test_settings_path_is_directory() {
+    let t = TestStruct {
+        a: 1.0,
+        b: 2,
+        c: "this should never be written".to_string(),
+    };
+    let crate_name = "cr_program_settings_dir_collision";
+    let file_name = "settings.ser";
+    let collision = get_user_home().unwrap().join(crate_name).join(file_name);
+    std::fs::create_dir_all(&collision).unwrap();
+
+    match save_settings_with_filename(crate_name, file_name, &t) {
+        Err(SaveSettingsError::PathIsDirectory(path)) => assert_eq!(path, collision),
+        _ => panic!("expected save to fail with PathIsDirectory"),
+    }
+
+    match load_settings_with_filename::<TestStruct>(crate_name, file_name) {
+        Err(LoadSettingsError::PathIsDirectory(path)) => assert_eq!(path, collision),
+        other => panic!(
+            "expected load to fail with PathIsDirectory, got {:?}",
+            other
+        ),
+    }
+
+    delete_settings_folder(crate_name).unwrap();
+}
+
+#[test]
+fn test_root_not_a_table() {
+    let crate_name = "cr_program_settings_root_not_table";
+    match save_settings(crate_name, &vec![1, 2, 3]) {
+        Err(SaveSettingsError::RootNotATable(type_str)) => assert_eq!(type_str, "array"),
+        _ => panic!("expected save to fail with RootNotATable"),
+    }
+    match save_settings(crate_name, &5) {
+        Err(SaveSettingsError::RootNotATable(type_str)) => assert_eq!(type_str, "integer"),
+        _ => panic!("expected save to fail with RootNotATable"),
+    }
+    assert!(!get_user_home().unwrap().join(crate_name).exists());
+}
+
+#[test]
+fn test_save_settings_to_writer() {
+    let t = TestStruct {
+        a: 1.5,
+        b: 2,
+        c: "written to a buffer".to_string(),
+    };
+    let mut buffer = Vec::new();
+    save_settings_to_writer(&mut buffer, &t).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert_eq!(toml::from_str::<TestStruct>(&written).unwrap(), t);
+
+    // the same error mapping as the file based functions
+    assert!(matches!(
+        save_settings_to_writer(Vec::new(), &vec![1, 2, 3]),
+        Err(SaveSettingsError::RootNotATable(_))
+    ));
+
+    struct FailingWriter;
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("sink closed"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    assert!(matches!(
+        save_settings_to_writer(FailingWriter, &t),
+        Err(SaveSettingsError::IOError(_))
+    ));
+}
+
+#[test]
+fn test_save_settings_with_header() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_header";
+        let t = TestStruct {
+            a: 5.0,
+            b: 5,
+            c: "header".to_string(),
+        };
+        save_settings_with_header(crate_name, "header.ser", &t, "line one\n\nline three\n")
+            .unwrap();
+        assert_eq!(
+            read_settings_string(crate_name, "header.ser").unwrap(),
+            "# line one\n#\n# line three\n\na = 5.0\nb = 5\nc = \"header\"\n"
+        );
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "header.ser").unwrap(),
+            t
+        );
+
+        // an empty header adds nothing
+        save_settings_with_header(crate_name, "header.ser", &t, "").unwrap();
+        assert_eq!(
+            read_settings_string(crate_name, "header.ser").unwrap(),
+            "a = 5.0\nb = 5\nc = \"header\"\n"
+        );
+    });
 }
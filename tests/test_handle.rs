@@ -0,0 +1,86 @@
+#![cfg(feature = "arc-swap")]
+
+use cr_program_settings::handle::SettingsHandle;
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+struct FrameSettings {
+    fov: u32,
+    fov_squared: u32,
+}
+
+#[test]
+fn test_settings_handle() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_handle";
+        let file_name = "frame.toml";
+
+        let handle = SettingsHandle::<FrameSettings>::load_or_default(crate_name, file_name);
+        assert_eq!(*handle.get(), FrameSettings::default());
+        assert!(matches!(
+            SettingsHandle::<FrameSettings>::load(crate_name, file_name),
+            Err(LoadSettingsError::IOError(_))
+        ));
+
+        // readers always see a consistent snapshot while the settings are updated
+        let done = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    while !done.load(Ordering::SeqCst) {
+                        let settings = handle.get();
+                        assert_eq!(settings.fov * settings.fov, settings.fov_squared);
+                    }
+                });
+            }
+            for fov in 1..=50 {
+                handle
+                    .modify(|settings| {
+                        settings.fov = fov;
+                        settings.fov_squared = fov * fov;
+                    })
+                    .unwrap();
+            }
+            done.store(true, Ordering::SeqCst);
+        });
+
+        let expected = FrameSettings {
+            fov: 50,
+            fov_squared: 2500,
+        };
+        assert_eq!(*handle.get(), expected);
+        assert_eq!(
+            load_settings_with_filename::<FrameSettings>(crate_name, file_name).unwrap(),
+            expected
+        );
+        assert_eq!(
+            *SettingsHandle::<FrameSettings>::load(crate_name, file_name)
+                .unwrap()
+                .get(),
+            expected
+        );
+
+        // an edit outside the program is only seen after a reload
+        let edited = FrameSettings {
+            fov: 3,
+            fov_squared: 9,
+        };
+        save_settings_with_filename(crate_name, file_name, &edited).unwrap();
+        assert_eq!(*handle.get(), expected);
+        handle.reload().unwrap();
+        assert_eq!(*handle.get(), edited);
+
+        // a failed reload keeps the snapshot
+        delete_setting_file(crate_name, file_name).unwrap();
+        assert!(handle.reload().is_err());
+        assert_eq!(*handle.get(), edited);
+
+        handle.store(expected.clone()).unwrap();
+        assert_eq!(*handle.get(), expected);
+        assert_eq!(handle.crate_name(), crate_name);
+        assert_eq!(handle.file_name(), file_name);
+    });
+}
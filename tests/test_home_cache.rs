@@ -0,0 +1,28 @@
+#![cfg(unix)]
+
+use cr_program_settings::prelude::*;
+
+// changes the HOME environment variable of the test process, so this is the only test in this file
+#[test]
+fn test_user_home_is_cached() {
+    let original_home = std::env::var_os("HOME");
+    let first_home = std::env::temp_dir().join("cr_program_settings_home_cache_first");
+    let second_home = std::env::temp_dir().join("cr_program_settings_home_cache_second");
+
+    std::env::set_var("HOME", &first_home);
+    assert_eq!(refresh_user_home(), Some(first_home.clone()));
+
+    // the operating system is not asked again, so a changed environment goes unnoticed
+    std::env::set_var("HOME", &second_home);
+    assert_eq!(get_user_home(), Some(first_home.clone()));
+    assert_eq!(get_user_home(), Some(first_home));
+
+    assert_eq!(refresh_user_home(), Some(second_home.clone()));
+    assert_eq!(get_user_home(), Some(second_home));
+
+    match original_home {
+        Some(home) => std::env::set_var("HOME", home),
+        None => std::env::remove_var("HOME"),
+    }
+    refresh_user_home();
+}
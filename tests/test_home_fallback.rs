@@ -0,0 +1,67 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::{
+    reset_home_fallback, reset_home_resolver, set_home_fallback, set_home_resolver,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct FallbackSettings {
+    value: u32,
+}
+
+// changes the global home resolver, fallback, and current directory, so everything is checked in a single test
+#[test]
+fn test_home_fallback() {
+    let crate_name = "cr_program_settings_home_fallback";
+    let fallback_dir = std::env::temp_dir().join("cr_program_settings_home_fallback_dir");
+    let _ = std::fs::remove_dir_all(&fallback_dir);
+    std::fs::create_dir_all(&fallback_dir).unwrap();
+
+    // an environment without a home directory
+    set_home_resolver(|| None);
+    assert_eq!(get_user_home(), None);
+    assert!(matches!(
+        save_settings_with_filename(crate_name, "settings.ser", &FallbackSettings { value: 1 }),
+        Err(SaveSettingsError::FailedToGetUserHome)
+    ));
+
+    set_home_fallback(&fallback_dir);
+    assert_eq!(get_user_home(), Some(fallback_dir.clone()));
+    save_settings_with_filename(crate_name, "settings.ser", &FallbackSettings { value: 1 })
+        .unwrap();
+    assert!(fallback_dir.join(crate_name).join("settings.ser").is_file());
+    assert_eq!(
+        load_settings_with_filename::<FallbackSettings>(crate_name, "settings.ser").unwrap(),
+        FallbackSettings { value: 1 }
+    );
+
+    // a relative fallback follows the current directory
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&fallback_dir).unwrap();
+    set_home_fallback(".");
+    let home = get_user_home().unwrap();
+    assert!(home.is_absolute());
+    assert_eq!(
+        home.canonicalize().unwrap(),
+        fallback_dir.canonicalize().unwrap()
+    );
+    assert_eq!(
+        load_settings_with_filename::<FallbackSettings>(crate_name, "settings.ser").unwrap(),
+        FallbackSettings { value: 1 }
+    );
+    std::env::set_current_dir(original_dir).unwrap();
+
+    // the fallback is not used while a home directory is found
+    let home_dir = fallback_dir.join("home");
+    let resolver_dir = home_dir.clone();
+    set_home_resolver(move || Some(resolver_dir.clone()));
+    assert_eq!(get_user_home(), Some(home_dir));
+
+    reset_home_resolver();
+    reset_home_fallback();
+    set_home_resolver(|| None);
+    assert_eq!(get_user_home(), None);
+    reset_home_resolver();
+
+    std::fs::remove_dir_all(fallback_dir).unwrap();
+}
@@ -0,0 +1,46 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{reset_home_resolver, set_home_resolver};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_home_resolver() {
+    let system_home = get_user_home();
+    let resolved_home = std::env::temp_dir().join("cr_program_settings_test_home_resolver");
+    let resolver_home = resolved_home.clone();
+    set_home_resolver(move || Some(resolver_home.clone()));
+    assert_eq!(get_user_home(), Some(resolved_home.clone()));
+
+    let crate_name = "cr_program_settings_resolver";
+    save_settings(crate_name, &TestStruct { a: 1 }).unwrap();
+    assert!(resolved_home
+        .join(crate_name)
+        .join(format!("{}.ser", crate_name))
+        .is_file());
+    assert_eq!(
+        load_settings::<TestStruct>(crate_name).unwrap(),
+        TestStruct { a: 1 }
+    );
+
+    // the test fixture takes priority over the resolver
+    with_temp_settings_dir(|| {
+        assert_ne!(get_user_home(), Some(resolved_home.clone()));
+        assert!(load_settings::<TestStruct>(crate_name).is_err());
+    });
+
+    // a resolver that cannot find a home reports it instead of falling back
+    set_home_resolver(|| None);
+    assert!(matches!(
+        save_settings(crate_name, &TestStruct { a: 2 }),
+        Err(SaveSettingsError::FailedToGetUserHome)
+    ));
+
+    reset_home_resolver();
+    assert_eq!(get_user_home(), system_home);
+    std::fs::remove_dir_all(resolved_home).unwrap();
+}
@@ -0,0 +1,142 @@
+use cr_program_settings::includes::MissingInclude;
+use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct Keybindings {
+    jump: String,
+    crouch: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct IncludeSettings {
+    theme: String,
+    volume: u32,
+    keybindings: Keybindings,
+}
+
+/// Writes `contents` to `file_name` in the settings folder of `crate_name`
+fn write_file(crate_name: &str, file_name: &str, contents: &str) {
+    let settings_dir = get_user_home().unwrap().join(crate_name);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join(file_name), contents).unwrap();
+}
+
+#[test]
+fn test_includes() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_includes";
+        let options = LoadOptions::new().includes(true);
+        write_file(
+            crate_name,
+            "main.toml",
+            "include = [\"keybindings.toml\", \"theme.toml\"]\nvolume = 80\n[keybindings]\ncrouch = \"ctrl\"",
+        );
+        write_file(
+            crate_name,
+            "keybindings.toml",
+            "include = [\"defaults.toml\"]\n[keybindings]\njump = \"space\"",
+        );
+        write_file(
+            crate_name,
+            "defaults.toml",
+            "theme = \"light\"\nvolume = 10\n[keybindings]\njump = \"w\"\ncrouch = \"c\"",
+        );
+        write_file(crate_name, "theme.toml", "theme = \"dark\"\nvolume = 20");
+
+        // later includes win over earlier ones, and the main file wins over all of them
+        assert_eq!(
+            load_settings_with_options::<IncludeSettings>(crate_name, "main.toml", &options)
+                .unwrap(),
+            IncludeSettings {
+                theme: "dark".to_string(),
+                volume: 80,
+                keybindings: Keybindings {
+                    jump: "space".to_string(),
+                    crouch: "ctrl".to_string(),
+                },
+            }
+        );
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        for file_name in [
+            "main.toml",
+            "keybindings.toml",
+            "defaults.toml",
+            "theme.toml",
+        ] {
+            assert!(SETTINGS_PATHS
+                .read()
+                .unwrap()
+                .contains(&settings_dir.join(file_name)));
+        }
+
+        // without the option, the include key is a regular field
+        assert!(load_settings_with_options::<IncludeSettings>(
+            crate_name,
+            "main.toml",
+            &LoadOptions::new()
+        )
+        .is_err());
+    });
+}
+
+#[test]
+fn test_include_errors() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_include_errors";
+        let options = LoadOptions::new().includes(true);
+        write_file(crate_name, "a.toml", "include = [\"b.toml\"]");
+        write_file(crate_name, "b.toml", "include = [\"a.toml\"]");
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        match load_settings_with_options::<toml::Table>(crate_name, "a.toml", &options) {
+            Err(LoadSettingsError::IncludeCycle(cycle)) => assert_eq!(
+                cycle,
+                vec![
+                    settings_dir.join("a.toml"),
+                    settings_dir.join("b.toml"),
+                    settings_dir.join("a.toml")
+                ]
+            ),
+            other => panic!("expected an include cycle, got {:?}", other),
+        }
+
+        write_file(crate_name, "self.toml", "include = [\"self.toml\"]");
+        assert!(matches!(
+            load_settings_with_options::<toml::Table>(crate_name, "self.toml", &options),
+            Err(LoadSettingsError::IncludeCycle(_))
+        ));
+
+        // missing includes fail by default, or are skipped
+        write_file(
+            crate_name,
+            "missing.toml",
+            "include = [\"nowhere.toml\"]\nvolume = 1",
+        );
+        assert!(matches!(
+            load_settings_with_options::<toml::Table>(crate_name, "missing.toml", &options),
+            Err(LoadSettingsError::IOError(err)) if err.kind() == std::io::ErrorKind::NotFound
+        ));
+        let skipped = load_settings_with_options::<toml::Table>(
+            crate_name,
+            "missing.toml",
+            &options.clone().missing_include(MissingInclude::Skip),
+        )
+        .unwrap();
+        assert_eq!(skipped.get("volume"), Some(&toml::Value::Integer(1)));
+        assert!(!skipped.contains_key("include"));
+
+        // includes must be plain file names in the settings folder
+        write_file(crate_name, "outside.toml", "include = [\"../other.toml\"]");
+        write_file(crate_name, "not_array.toml", "include = \"a.toml\"");
+        for file_name in ["outside.toml", "not_array.toml"] {
+            assert!(matches!(
+                load_settings_with_options::<toml::Table>(crate_name, file_name, &options),
+                Err(LoadSettingsError::InvalidInclude(_))
+            ));
+        }
+    });
+}
@@ -0,0 +1,102 @@
+use cr_program_settings::keys::{get_settings_value, set_settings_value};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use toml::Value;
+
+const FILE: &str = "ui = { dark_mode = false, scale = 1.5 }\n[[servers]]\nhost = \"a.example.com\"\n[[servers]]\nhost = \"b.example.com\"\n";
+
+fn write_settings(crate_name: &str) {
+    let settings_dir = get_user_home().unwrap().join(crate_name);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join("keys.ser"), FILE).unwrap();
+}
+
+#[test]
+fn test_get_settings_value() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_keys_get";
+        write_settings(crate_name);
+        let get = |key_path| get_settings_value(crate_name, "keys.ser", key_path);
+
+        assert_eq!(get("ui.dark_mode").unwrap(), Some(Value::Boolean(false)));
+        assert_eq!(get("ui.scale").unwrap(), Some(Value::Float(1.5)));
+        assert_eq!(
+            get("servers.1.host").unwrap(),
+            Some(Value::String("b.example.com".to_string()))
+        );
+        assert!(get("ui").unwrap().unwrap().is_table());
+
+        // missing keys and elements are not errors
+        assert_eq!(get("ui.missing").unwrap(), None);
+        assert_eq!(get("missing.key").unwrap(), None);
+        assert_eq!(get("servers.5.host").unwrap(), None);
+
+        for key_path in ["ui.dark_mode.x", "servers.first", "", "ui..scale"] {
+            assert!(matches!(
+                get(key_path),
+                Err(LoadSettingsError::InvalidKeyPath(_))
+            ));
+        }
+        assert!(matches!(
+            get_settings_value(crate_name, "missing.ser", "ui"),
+            Err(LoadSettingsError::IOError(_))
+        ));
+    });
+}
+
+#[test]
+fn test_set_settings_value() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_keys_set";
+        write_settings(crate_name);
+        let set = |key_path, value| set_settings_value(crate_name, "keys.ser", key_path, value);
+        let get = |key_path| get_settings_value(crate_name, "keys.ser", key_path).unwrap();
+
+        set("ui.dark_mode", Value::Boolean(true)).unwrap();
+        assert_eq!(get("ui.dark_mode"), Some(Value::Boolean(true)));
+        // the rest of the file is kept
+        assert_eq!(get("ui.scale"), Some(Value::Float(1.5)));
+
+        set("servers.0.host", Value::String("c.example.com".to_string())).unwrap();
+        assert_eq!(
+            get("servers.0.host"),
+            Some(Value::String("c.example.com".to_string()))
+        );
+        assert_eq!(
+            get("servers.1.host"),
+            Some(Value::String("b.example.com".to_string()))
+        );
+
+        // missing tables are created
+        set("window.size.width", Value::Integer(800)).unwrap();
+        assert_eq!(get("window.size.width"), Some(Value::Integer(800)));
+
+        for key_path in [
+            "ui.dark_mode.x",
+            "servers.2.host",
+            "servers.2",
+            "servers.first",
+            "",
+        ] {
+            assert!(matches!(
+                set(key_path, Value::Integer(1)),
+                Err(SettingsError::Save(SaveSettingsError::InvalidKeyPath(_)))
+            ));
+        }
+        assert_eq!(get("ui.dark_mode"), Some(Value::Boolean(true)));
+
+        // a missing file is created, a malformed file is left alone
+        set_settings_value(crate_name, "new.ser", "a.b", Value::Integer(1)).unwrap();
+        assert_eq!(
+            get_settings_value(crate_name, "new.ser", "a.b").unwrap(),
+            Some(Value::Integer(1))
+        );
+        let broken = get_user_home().unwrap().join(crate_name).join("broken.ser");
+        std::fs::write(&broken, "a = [").unwrap();
+        assert!(matches!(
+            set_settings_value(crate_name, "broken.ser", "a", Value::Integer(1)),
+            Err(SettingsError::Load(LoadSettingsError::ParseError(_)))
+        ));
+        assert_eq!(std::fs::read_to_string(broken).unwrap(), "a = [");
+    });
+}
@@ -0,0 +1,107 @@
+use cr_program_settings::format::{load_settings_with_format, save_settings_with_format, Format};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct LargeSettings {
+    entries: Vec<LargeEntry>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct LargeEntry {
+    name: String,
+    value: u64,
+}
+
+/// Settings that serialize to a few megabytes in any format
+fn large_settings() -> LargeSettings {
+    LargeSettings {
+        entries: (0..50_000)
+            .map(|value| LargeEntry {
+                name: format!("entry number {} with some padding text", value),
+                value,
+            })
+            .collect(),
+    }
+}
+
+/// Saves and loads large settings in `format`, asserting the file is large and loading it is not unreasonably slow
+fn assert_large_roundtrip(format: Format) {
+    let crate_name = "cr_program_settings_large";
+    let file_name = format!("large.{}", format.extension());
+    let settings = large_settings();
+    save_settings_with_format(crate_name, &file_name, &settings, format).unwrap();
+    let file_size = std::fs::metadata(get_user_home().unwrap().join(crate_name).join(&file_name))
+        .unwrap()
+        .len();
+    assert!(file_size > 2 * 1024 * 1024, "{} bytes", file_size);
+
+    let start = Instant::now();
+    let loaded =
+        load_settings_with_format::<LargeSettings>(crate_name, &file_name, format).unwrap();
+    // generous enough for unoptimized builds on slow machines, a quadratic read would blow well past it
+    assert!(
+        start.elapsed() < Duration::from_secs(30),
+        "{:?}",
+        start.elapsed()
+    );
+    assert_eq!(loaded, settings);
+}
+
+#[test]
+fn test_large_settings_toml() {
+    with_temp_settings_dir(|| assert_large_roundtrip(Format::Toml));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_large_settings_json() {
+    with_temp_settings_dir(|| assert_large_roundtrip(Format::Json));
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_large_settings_bincode() {
+    with_temp_settings_dir(|| assert_large_roundtrip(Format::Bincode));
+}
+
+#[test]
+fn test_streamed_load_errors() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_streamed";
+        // a missing file is reported the same way by every format
+        #[allow(clippy::single_element_loop)]
+        for format in [
+            Format::Toml,
+            #[cfg(feature = "json")]
+            Format::Json,
+            #[cfg(feature = "bincode")]
+            Format::Bincode,
+        ] {
+            let err = load_settings_with_format::<LargeSettings>(crate_name, "missing", format)
+                .unwrap_err();
+            assert!(
+                matches!(&err, LoadSettingsError::IOError(err) if err.kind() == std::io::ErrorKind::NotFound),
+                "{:?}",
+                err
+            );
+        }
+
+        #[cfg(feature = "json")]
+        {
+            let settings_dir = get_user_home().unwrap().join(crate_name);
+            std::fs::create_dir_all(&settings_dir).unwrap();
+            std::fs::write(settings_dir.join("truncated.json"), "{ \"entries\": [").unwrap();
+            assert!(matches!(
+                load_settings_with_format::<LargeSettings>(
+                    crate_name,
+                    "truncated.json",
+                    Format::Json
+                ),
+                Err(LoadSettingsError::JsonDeserializationError(_))
+            ));
+        }
+    });
+}
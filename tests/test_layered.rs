@@ -0,0 +1,160 @@
+use cr_program_settings::layered::{
+    load_settings_layered, load_settings_merged, load_settings_merged_and_save, LayerOptions,
+};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Window {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Settings {
+    name: String,
+    window: Window,
+    recent: Vec<String>,
+}
+
+fn defaults() -> Settings {
+    Settings {
+        name: "default".to_string(),
+        window: Window {
+            width: 800,
+            height: 600,
+        },
+        recent: vec!["a".to_string(), "b".to_string()],
+    }
+}
+
+#[test]
+fn test_layered_without_file() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_layered";
+        let options = LayerOptions {
+            env_prefix: None,
+            defaults: Some(defaults()),
+        };
+        assert_eq!(
+            load_settings_layered(crate_name, "missing.ser", options).unwrap(),
+            defaults()
+        );
+
+        // without any layers the settings can not be built
+        assert!(matches!(
+            load_settings_layered::<Settings>(crate_name, "missing.ser", LayerOptions::default()),
+            Err(LoadSettingsError::DeserializationError(_))
+        ));
+    });
+}
+
+#[test]
+fn test_layered_merge() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_layered";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        // tables merge key by key, arrays are replaced as a whole
+        std::fs::write(
+            settings_dir.join("partial.ser"),
+            "recent = [\"c\"]\n[window]\nwidth = 1024\n",
+        )
+        .unwrap();
+        std::env::set_var("CR_PROGRAM_SETTINGS_LAYERED_WINDOW__HEIGHT", "700");
+        std::env::set_var("CR_PROGRAM_SETTINGS_LAYERED_NAME", "from env");
+
+        let loaded = load_settings_layered(
+            crate_name,
+            "partial.ser",
+            LayerOptions {
+                env_prefix: Some("CR_PROGRAM_SETTINGS_LAYERED_"),
+                defaults: Some(defaults()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            loaded,
+            Settings {
+                name: "from env".to_string(),
+                window: Window {
+                    width: 1024,
+                    height: 700,
+                },
+                recent: vec!["c".to_string()],
+            }
+        );
+
+        // a malformed file is reported rather than skipped
+        std::fs::write(settings_dir.join("partial.ser"), "recent = [").unwrap();
+        assert!(matches!(
+            load_settings_layered(
+                crate_name,
+                "partial.ser",
+                LayerOptions {
+                    env_prefix: None,
+                    defaults: Some(defaults()),
+                },
+            ),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+    });
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct SettingsV1 {
+    name: String,
+    window: WindowV1,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct WindowV1 {
+    width: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        defaults()
+    }
+}
+
+#[test]
+fn test_load_settings_merged() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_merged";
+        assert!(matches!(
+            load_settings_merged::<Settings>(crate_name, "merged.ser"),
+            Err(LoadSettingsError::IOError(_))
+        ));
+
+        let old = SettingsV1 {
+            name: "saved".to_string(),
+            window: WindowV1 { width: 1280 },
+        };
+        save_settings_with_filename(crate_name, "merged.ser", &old).unwrap();
+        let expected = Settings {
+            name: "saved".to_string(),
+            window: Window {
+                width: 1280,
+                height: 600,
+            },
+            recent: vec!["a".to_string(), "b".to_string()],
+        };
+        assert_eq!(
+            load_settings_merged::<Settings>(crate_name, "merged.ser").unwrap(),
+            expected
+        );
+        // the file is left as it is
+        assert!(load_settings_with_filename::<Settings>(crate_name, "merged.ser").is_err());
+
+        assert_eq!(
+            load_settings_merged_and_save::<Settings>(crate_name, "merged.ser").unwrap(),
+            expected
+        );
+        assert_eq!(
+            load_settings_with_filename::<Settings>(crate_name, "merged.ser").unwrap(),
+            expected
+        );
+    });
+}
@@ -0,0 +1,171 @@
+use cr_program_settings::lenient::{load_settings_lenient, RecoveryIssue};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Window {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    volume: u32,
+    name: String,
+    window: Window,
+    recent: Vec<String>,
+}
+
+impl Default for TestStruct {
+    fn default() -> Self {
+        Self {
+            volume: 50,
+            name: "default".to_string(),
+            window: Window {
+                width: 800,
+                height: 600,
+            },
+            recent: vec![],
+        }
+    }
+}
+
+const CRATE_NAME: &str = "cr_program_settings_lenient";
+
+/// Writes `contents` to the settings file `file_name` and loads it leniently
+fn load_lenient(file_name: &str, contents: &str) -> (TestStruct, Vec<RecoveryIssue>) {
+    let settings_dir = get_user_home().unwrap().join(CRATE_NAME);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join(file_name), contents).unwrap();
+    load_settings_lenient::<TestStruct>(CRATE_NAME, file_name).unwrap()
+}
+
+#[test]
+fn test_lenient_valid_file() {
+    with_temp_settings_dir(|| {
+        let settings = TestStruct {
+            volume: 10,
+            name: "saved".to_string(),
+            window: Window {
+                width: 1,
+                height: 2,
+            },
+            recent: vec!["a".to_string()],
+        };
+        save_settings_with_filename(CRATE_NAME, "valid.ser", &settings).unwrap();
+        let (loaded, issues) =
+            load_settings_lenient::<TestStruct>(CRATE_NAME, "valid.ser").unwrap();
+        assert_eq!(loaded, settings);
+        assert!(issues.is_empty());
+
+        // missing fields are filled in from the defaults without being reported
+        let (loaded, issues) = load_lenient("partial.ser", "volume = 5\n");
+        assert_eq!(loaded.volume, 5);
+        assert_eq!(loaded.name, "default");
+        assert!(issues.is_empty());
+    });
+}
+
+#[test]
+fn test_lenient_syntax_errors() {
+    with_temp_settings_dir(|| {
+        // a broken value line is skipped, the rest of the file is kept
+        let (loaded, issues) = load_lenient(
+            "broken_line.ser",
+            "volume = 5\nname = \"unterminated\nrecent = [\"a\"]\n",
+        );
+        assert_eq!(loaded.volume, 5);
+        assert_eq!(loaded.name, "default");
+        assert_eq!(loaded.recent, vec!["a".to_string()]);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            RecoveryIssue::SyntaxError { line: 2, content, .. } if content == "name = \"unterminated"
+        ));
+
+        // a broken table header skips its whole table, rather than moving its keys into another table
+        let (loaded, issues) = load_lenient(
+            "broken_header.ser",
+            "volume = 5\n[window\nwidth = 1\nheight = 2\n",
+        );
+        assert_eq!(loaded.volume, 5);
+        assert_eq!(loaded.window, TestStruct::default().window);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            RecoveryIssue::SyntaxError { line: 2, .. }
+        ));
+
+        // a duplicate key is skipped, keeping the first value
+        let (loaded, issues) = load_lenient("duplicate.ser", "volume = 5\nvolume = 6\n");
+        assert_eq!(loaded.volume, 5);
+        assert!(matches!(
+            &issues[0],
+            RecoveryIssue::SyntaxError { line: 2, .. }
+        ));
+
+        // several errors are all reported, in the order they were found
+        let (loaded, issues) = load_lenient(
+            "several.ser",
+            "volume = \nname = \"kept\"\nrecent = [\"a\"\n",
+        );
+        assert_eq!(loaded.name, "kept");
+        assert_eq!(issues.len(), 2);
+    });
+}
+
+#[test]
+fn test_lenient_invalid_fields() {
+    with_temp_settings_dir(|| {
+        // a value of the wrong type is replaced by the default
+        let (loaded, issues) = load_lenient(
+            "wrong_type.ser",
+            "volume = \"loud\"\nname = \"kept\"\n[window]\nwidth = 1\nheight = -2\n",
+        );
+        assert_eq!(loaded.volume, 50);
+        assert_eq!(loaded.name, "kept");
+        // fields are salvaged per top level field, so the whole window is the default
+        assert_eq!(loaded.window, TestStruct::default().window);
+        let paths: Vec<&str> = issues
+            .iter()
+            .map(|issue| match issue {
+                RecoveryIssue::InvalidField { path, .. } => path.as_str(),
+                RecoveryIssue::SyntaxError { .. } => panic!("unexpected syntax error"),
+            })
+            .collect();
+        assert_eq!(paths, vec!["volume", "window"]);
+        assert!(issues[0]
+            .to_string()
+            .starts_with("used the default for volume"));
+
+        // a table missing some of its fields is filled in from the defaults
+        let (loaded, issues) = load_lenient("partial_table.ser", "[window]\nwidth = 1\n");
+        assert_eq!(
+            loaded.window,
+            Window {
+                width: 1,
+                height: 600
+            }
+        );
+        assert!(issues.is_empty());
+    });
+}
+
+#[test]
+fn test_lenient_errors() {
+    with_temp_settings_dir(|| {
+        assert!(matches!(
+            load_settings_lenient::<TestStruct>(CRATE_NAME, "missing.ser"),
+            Err(LoadSettingsError::IOError(_))
+        ));
+
+        let settings_dir = get_user_home().unwrap().join(CRATE_NAME);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(settings_dir.join("binary.ser"), [0xff, 0xfe, 0x00]).unwrap();
+        assert!(matches!(
+            load_settings_lenient::<TestStruct>(CRATE_NAME, "binary.ser"),
+            Err(LoadSettingsError::InvalidEncoding { .. })
+        ));
+    });
+}
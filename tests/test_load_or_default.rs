@@ -0,0 +1,153 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::CorruptPolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct TestStruct {
+    a: u32,
+}
+
+fn write_corrupt(folder: &str, file_name: &str) {
+    let settings_dir = get_user_home().unwrap().join(folder);
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(settings_dir.join(file_name), "a = [").unwrap();
+}
+
+#[test]
+fn test_load_or_default_missing_file() {
+    with_temp_settings_dir(|| {
+        assert_eq!(load_settings_or_default!(TestStruct), TestStruct::default());
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "missing.ser"),
+            TestStruct::default()
+        );
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "missing.ser", "cr_program_settings_missing"),
+            TestStruct::default()
+        );
+
+        save_settings!(TestStruct { a: 3 }, "saved.ser").unwrap();
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "saved.ser"),
+            TestStruct { a: 3 }
+        );
+        save_settings!(TestStruct { a: 4 }).unwrap();
+        assert_eq!(load_settings_or_default!(TestStruct), TestStruct { a: 4 });
+    });
+}
+
+#[test]
+fn test_load_or_default_corrupt_file_default() {
+    with_temp_settings_dir(|| {
+        let folder = "cr_program_settings_corrupt_default";
+        write_corrupt(folder, "corrupt.ser");
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "corrupt.ser", folder, on_corrupt = default),
+            TestStruct::default()
+        );
+        assert_eq!(
+            load_settings_or_default::<TestStruct>(folder, "corrupt.ser", CorruptPolicy::Default),
+            TestStruct::default()
+        );
+
+        write_corrupt(env!("CARGO_CRATE_NAME"), "corrupt.ser");
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "corrupt.ser", on_corrupt = default),
+            TestStruct::default()
+        );
+        // the corrupt file is left for the user to fix
+        assert!(get_user_home()
+            .unwrap()
+            .join(folder)
+            .join("corrupt.ser")
+            .is_file());
+    });
+}
+
+#[test]
+#[should_panic(expected = "unable to load settings")]
+fn test_load_or_default_corrupt_file_panics() {
+    with_temp_settings_dir(|| {
+        write_corrupt(env!("CARGO_CRATE_NAME"), "corrupt.ser");
+        load_settings_or_default!(TestStruct, "corrupt.ser");
+    });
+}
+
+#[test]
+#[should_panic(expected = "unable to load settings")]
+fn test_load_or_default_corrupt_file_explicit_panic() {
+    with_temp_settings_dir(|| {
+        let folder = "cr_program_settings_corrupt_panic";
+        write_corrupt(folder, "corrupt.ser");
+        load_settings_or_default!(TestStruct, "corrupt.ser", folder, on_corrupt = panic);
+    });
+}
+
+#[test]
+fn test_load_or_default_quarantine() {
+    with_temp_settings_dir(|| {
+        let folder = "cr_program_settings_quarantine";
+        write_corrupt(folder, "corrupt.ser");
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "corrupt.ser", folder, on_corrupt = quarantine),
+            TestStruct::default()
+        );
+
+        // the corrupt file was moved aside intact, freeing the name for new settings
+        let files = list_settings_files(folder).unwrap();
+        assert_eq!(files.len(), 1);
+        let quarantined = files[0].file_name().unwrap().to_str().unwrap();
+        assert!(quarantined.starts_with("corrupt.ser.corrupt-"));
+        assert_eq!(std::fs::read_to_string(&files[0]).unwrap(), "a = [");
+
+        // quarantining twice within a second keeps both copies
+        write_corrupt(folder, "corrupt.ser");
+        assert_eq!(
+            load_settings_or_default::<TestStruct>(
+                folder,
+                "corrupt.ser",
+                CorruptPolicy::Quarantine
+            ),
+            TestStruct::default()
+        );
+        write_corrupt(folder, "corrupt.ser");
+        quarantine_settings_file(folder, "corrupt.ser").unwrap();
+        assert_eq!(list_settings_files(folder).unwrap().len(), 3);
+
+        // a missing file is left alone
+        assert_eq!(
+            load_settings_or_default!(TestStruct, "missing.ser", folder, on_corrupt = quarantine),
+            TestStruct::default()
+        );
+        assert_eq!(list_settings_files(folder).unwrap().len(), 3);
+    });
+}
+
+#[test]
+fn test_load_settings_or() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_load_or";
+        assert_eq!(
+            load_settings_or(crate_name, TestStruct { a: 7 }),
+            TestStruct { a: 7 }
+        );
+        save_settings(crate_name, &TestStruct { a: 3 }).unwrap();
+        assert_eq!(
+            load_settings_or(crate_name, TestStruct { a: 7 }),
+            TestStruct { a: 3 }
+        );
+
+        // a corrupt file also returns the fallback, and is left in place
+        write_corrupt(crate_name, "corrupt.ser");
+        assert_eq!(
+            load_settings_or_with_filename(crate_name, "corrupt.ser", TestStruct { a: 9 }),
+            TestStruct { a: 9 }
+        );
+        assert!(get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("corrupt.ser")
+            .exists());
+    });
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "logging")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(vec![]);
+
+struct TestLogger;
+
+impl log::Log for TestLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target().starts_with("cr_program_settings") {
+            RECORDS
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_logging() {
+    log::set_logger(&TestLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_logging";
+        save_settings(crate_name, &TestStruct { a: 1 }).unwrap();
+        load_settings::<TestStruct>(crate_name).unwrap();
+        assert!(load_settings_with_filename::<TestStruct>(crate_name, "missing.ser").is_err());
+    });
+
+    let records = RECORDS.lock().unwrap();
+    let has = |level: log::Level, text: &str| {
+        records
+            .iter()
+            .any(|(record_level, message)| *record_level == level && message.contains(text))
+    };
+    assert!(has(log::Level::Debug, "resolved settings directory"));
+    assert!(has(log::Level::Info, "saved settings to"));
+    assert!(has(log::Level::Info, "loaded settings from"));
+    assert!(has(log::Level::Warn, "missing.ser"));
+}
@@ -0,0 +1,69 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_long_file_name() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_long_paths";
+        let t = TestStruct { a: 7 };
+
+        // file names up to 200 bytes are fine on every supported platform
+        let long_file_name = format!("{}.ser", "a".repeat(196));
+        save_settings_with_filename(crate_name, &long_file_name, &t).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, &long_file_name).unwrap(),
+            t
+        );
+
+        // file name components are limited to 255 bytes
+        let too_long_file_name = format!("{}.ser", "a".repeat(300));
+        assert!(matches!(
+            save_settings_with_filename(crate_name, &too_long_file_name, &t),
+            Err(SaveSettingsError::PathTooLong(_))
+        ));
+        assert!(matches!(
+            load_settings_with_filename::<TestStruct>(crate_name, &too_long_file_name),
+            Err(LoadSettingsError::PathTooLong(_))
+        ));
+    });
+}
+
+#[test]
+fn test_long_crate_name() {
+    with_temp_settings_dir(|| {
+        let t = TestStruct { a: 8 };
+        let too_long_crate_name = "c".repeat(300);
+        assert!(matches!(
+            save_settings(&too_long_crate_name, &t),
+            Err(SaveSettingsError::PathTooLong(_))
+        ));
+    });
+}
+
+#[test]
+fn test_deep_path() {
+    with_temp_settings_dir(|| {
+        let t = TestStruct { a: 9 };
+        // 30 nested folders of 200 bytes each, over `MAX_PATH` on Windows and `PATH_MAX` on linux
+        let deep_dir = vec!["d".repeat(200); 30].join("/");
+        let result = save_settings_in_dir(std::path::Path::new(&deep_dir), "deep.ser", &t);
+
+        #[cfg(windows)]
+        {
+            result.unwrap();
+            assert_eq!(
+                load_settings_in_dir::<TestStruct>(std::path::Path::new(&deep_dir), "deep.ser")
+                    .unwrap(),
+                t
+            );
+        }
+        #[cfg(unix)]
+        assert!(matches!(result, Err(SaveSettingsError::PathTooLong(_))));
+    });
+}
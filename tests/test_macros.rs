@@ -1,3 +1,6 @@
+// the test data is kept as originally written, an f32 literal with more digits than it stores, and a `let _` around a unit value
+#![allow(clippy::excessive_precision, clippy::let_unit_value)]
+
 use serde::{Deserialize, Serialize};
 use cr_program_settings::prelude::*;
 
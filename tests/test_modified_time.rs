@@ -0,0 +1,69 @@
+use cr_program_settings::backend::{BackendError, MemoryBackend, SettingsBackend};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_settings_modified_time() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_modified_time";
+
+        let err = settings_modified_time(crate_name, "modified.ser").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("modified.ser"));
+
+        save_settings_with_filename(crate_name, "modified.ser", &TestStruct { a: 1 }).unwrap();
+        let first = settings_modified_time(crate_name, "modified.ser").unwrap();
+        assert!(first <= SystemTime::now());
+
+        // loading does not change the modification time, saving again does
+        load_settings_with_filename::<TestStruct>(crate_name, "modified.ser").unwrap();
+        assert_eq!(
+            settings_modified_time(crate_name, "modified.ser").unwrap(),
+            first
+        );
+        let settings_file = get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("modified.ser");
+        std::fs::File::options()
+            .write(true)
+            .open(&settings_file)
+            .unwrap()
+            .set_modified(first - Duration::from_secs(60))
+            .unwrap();
+        save_settings_with_filename(crate_name, "modified.ser", &TestStruct { a: 2 }).unwrap();
+        assert!(
+            settings_modified_time(crate_name, "modified.ser").unwrap()
+                > first - Duration::from_secs(60)
+        );
+
+        // a directory is not a settings file
+        std::fs::create_dir_all(get_user_home().unwrap().join(crate_name).join("folder.ser"))
+            .unwrap();
+        assert!(settings_modified_time(crate_name, "folder.ser").is_err());
+    });
+}
+
+#[test]
+fn test_backend_without_modified_time() {
+    let backend = MemoryBackend::new();
+    let folder = Path::new("cr_program_settings_modified_time");
+    assert!(matches!(
+        backend.modified(folder, "memory.ser"),
+        Err(BackendError::IOError(err)) if err.kind() == io::ErrorKind::NotFound
+    ));
+    backend.write(folder, "memory.ser", b"a = 1\n").unwrap();
+    assert!(matches!(
+        backend.modified(folder, "memory.ser"),
+        Err(BackendError::IOError(err)) if err.kind() == io::ErrorKind::Unsupported
+    ));
+}
@@ -0,0 +1,53 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::settings_container::SettingsContainer;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct NamedSettings {
+    value: u32,
+}
+
+#[test]
+fn test_name_arguments() {
+    with_temp_settings_dir(|| {
+        let settings = NamedSettings { value: 12 };
+
+        // &str
+        save_settings_with_filename("cr_program_settings_names", "str.ser", &settings).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<NamedSettings>("cr_program_settings_names", "str.ser")
+                .unwrap(),
+            settings
+        );
+        delete_setting_file("cr_program_settings_names", "str.ser").unwrap();
+
+        // String and &String
+        let crate_name = String::from("cr_program_settings_names");
+        let file_name = String::from("string.ser");
+        save_settings_with_filename(&crate_name, &file_name, &settings).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<NamedSettings>(crate_name.clone(), file_name.clone())
+                .unwrap(),
+            settings
+        );
+        delete_setting_file(crate_name.clone(), file_name).unwrap();
+
+        // a crate folder given as a path
+        let crate_dir = PathBuf::from("cr_program_settings_names");
+        save_settings_with_filename(&crate_dir, "path.ser", &settings).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<NamedSettings>(crate_dir.as_path(), "path.ser").unwrap(),
+            settings
+        );
+        delete_setting_file(crate_dir, "path.ser").unwrap();
+
+        let file_name = String::from("container.ser");
+        let container = SettingsContainer::new(settings.clone(), &crate_name, &file_name);
+        container.save().unwrap();
+        let container =
+            SettingsContainer::new(settings.clone(), "cr_program_settings_names", file_name);
+        assert_eq!(container.get_settings(), &Some(settings));
+    });
+}
@@ -0,0 +1,214 @@
+use cr_program_settings::options::{
+    load_settings_with_options, save_settings_with_options, save_settings_with_toml_opts,
+    LineEnding, LoadOptions, SaveOptions, TomlOptions,
+};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{load_settings, save_settings};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Nested {
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct OptionsSettings {
+    name: String,
+    values: Vec<u32>,
+    nested: Nested,
+}
+
+fn settings() -> OptionsSettings {
+    OptionsSettings {
+        name: "options".to_string(),
+        values: vec![1, 2, 3],
+        nested: Nested { enabled: true },
+    }
+}
+
+/// Reads a file of the settings folder `crate_name`
+fn read_file(crate_name: &str, file_name: &str) -> Vec<u8> {
+    std::fs::read(get_user_home().unwrap().join(crate_name).join(file_name)).unwrap()
+}
+
+#[test]
+fn test_call_forms_match_default_options() {
+    with_temp_settings_dir(|| {
+        let crate_name = env!("CARGO_CRATE_NAME");
+        save_settings_with_options(
+            crate_name,
+            "options.ser",
+            &settings(),
+            &SaveOptions::default(),
+        )
+        .unwrap();
+        let expected = read_file(crate_name, "options.ser");
+
+        save_settings(crate_name, &settings()).unwrap();
+        save_settings_with_filename(crate_name, "filename.ser", &settings()).unwrap();
+        save_settings_in_dir(Path::new(crate_name), "in_dir.ser", &settings()).unwrap();
+        save_settings!(settings(), "macro.ser").unwrap();
+        save_settings!(settings(), "macro_folder.ser", crate_name).unwrap();
+        save_settings!(settings(), options = SaveOptions::new()).unwrap();
+        save_settings!(
+            settings(),
+            "macro_options.ser",
+            options = SaveOptions::new()
+        )
+        .unwrap();
+        save_settings!(
+            settings(),
+            "macro_folder_options.ser",
+            crate_name,
+            options = SaveOptions::default()
+        )
+        .unwrap();
+        for file_name in [
+            format!("{}.ser", crate_name).as_str(),
+            "filename.ser",
+            "in_dir.ser",
+            "macro.ser",
+            "macro_folder.ser",
+            "macro_options.ser",
+            "macro_folder_options.ser",
+        ] {
+            assert_eq!(read_file(crate_name, file_name), expected, "{}", file_name);
+        }
+
+        assert_eq!(
+            load_settings::<OptionsSettings>(crate_name).unwrap(),
+            settings()
+        );
+        assert_eq!(load_settings!(OptionsSettings).unwrap(), settings());
+        assert_eq!(
+            load_settings!(OptionsSettings, options = LoadOptions::new()).unwrap(),
+            settings()
+        );
+        assert_eq!(
+            load_settings!(
+                OptionsSettings,
+                "macro.ser",
+                options = LoadOptions::default()
+            )
+            .unwrap(),
+            settings()
+        );
+        assert_eq!(
+            load_settings!(
+                OptionsSettings,
+                "macro.ser",
+                crate_name,
+                options = LoadOptions::new()
+            )
+            .unwrap(),
+            settings()
+        );
+        assert_eq!(
+            load_settings_in_dir::<OptionsSettings>(Path::new(crate_name), "in_dir.ser").unwrap(),
+            load_settings_with_options::<OptionsSettings>(
+                crate_name,
+                "in_dir.ser",
+                &LoadOptions::default()
+            )
+            .unwrap()
+        );
+
+        // errors are the same as well
+        let is_not_found = |err: LoadSettingsError| matches!(err, LoadSettingsError::IOError(err) if err.kind() == std::io::ErrorKind::NotFound);
+        assert!(is_not_found(
+            load_settings!(OptionsSettings, "missing.ser").unwrap_err()
+        ));
+        assert!(is_not_found(
+            load_settings!(OptionsSettings, "missing.ser", options = LoadOptions::new())
+                .unwrap_err()
+        ));
+        std::fs::write(
+            get_user_home().unwrap().join(crate_name).join("broken.ser"),
+            "name = ",
+        )
+        .unwrap();
+        assert!(matches!(
+            load_settings!(OptionsSettings, "broken.ser"),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+        assert!(matches!(
+            load_settings!(OptionsSettings, "broken.ser", options = LoadOptions::new()),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+    });
+}
+
+#[test]
+fn test_macro_options() {
+    with_temp_settings_dir(|| {
+        let crate_name = env!("CARGO_CRATE_NAME");
+        save_settings!(
+            settings(),
+            "crlf.ser",
+            options = SaveOptions::new().line_endings(LineEnding::Crlf)
+        )
+        .unwrap();
+        let file_data = String::from_utf8(read_file(crate_name, "crlf.ser")).unwrap();
+        assert!(file_data.ends_with("\r\n"));
+        assert_eq!(
+            file_data.matches('\n').count(),
+            file_data.matches("\r\n").count()
+        );
+
+        assert!(matches!(
+            load_settings!(
+                OptionsSettings,
+                "crlf.ser",
+                options = LoadOptions::new().max_file_size(8)
+            ),
+            Err(LoadSettingsError::FileTooLarge { limit: 8, .. })
+        ));
+        assert_eq!(
+            load_settings!(OptionsSettings, "crlf.ser").unwrap(),
+            settings()
+        );
+    });
+}
+
+#[test]
+fn test_toml_options() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_toml_options";
+
+        // the default matches the plain save, with every array element on its own line
+        save_settings_with_toml_opts(
+            crate_name,
+            "multiline.ser",
+            &settings(),
+            &TomlOptions::new(),
+        )
+        .unwrap();
+        save_settings_with_filename(crate_name, "plain.ser", &settings()).unwrap();
+        assert_eq!(
+            read_file(crate_name, "multiline.ser"),
+            read_file(crate_name, "plain.ser")
+        );
+        assert!(String::from_utf8(read_file(crate_name, "plain.ser"))
+            .unwrap()
+            .contains("values = [\n"));
+
+        let inline = TomlOptions::new().multiline_arrays(false);
+        save_settings_with_toml_opts(crate_name, "inline.ser", &settings(), &inline).unwrap();
+        let file_data = String::from_utf8(read_file(crate_name, "inline.ser")).unwrap();
+        assert!(file_data.contains("values = [1, 2, 3]\n"));
+        assert_eq!(
+            load_settings_with_filename::<OptionsSettings>(crate_name, "inline.ser").unwrap(),
+            settings()
+        );
+
+        // the toml options combine with the other save options
+        let options = SaveOptions::new()
+            .toml(inline)
+            .line_endings(LineEnding::Crlf);
+        save_settings_with_options(crate_name, "inline_crlf.ser", &settings(), &options).unwrap();
+        let file_data = String::from_utf8(read_file(crate_name, "inline_crlf.ser")).unwrap();
+        assert!(file_data.contains("values = [1, 2, 3]\r\n"));
+    });
+}
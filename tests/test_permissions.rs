@@ -0,0 +1,45 @@
+#![cfg(unix)]
+
+use cr_program_settings::permissions::set_restrictive_permissions;
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    token: String,
+}
+
+fn mode(path: &Path) -> u32 {
+    std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn test_restrictive_permissions() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_permissions";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        let settings_file = settings_dir.join("private.ser");
+        let t = TestStruct {
+            token: "secret".to_string(),
+        };
+
+        save_settings_with_filename(crate_name, "private.ser", &t).unwrap();
+        assert_eq!(mode(&settings_dir), 0o700);
+        assert_eq!(mode(&settings_file), 0o600);
+
+        // files saved before the option existed are tightened on the next save
+        std::fs::set_permissions(&settings_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+        save_settings_with_filename(crate_name, "private.ser", &t).unwrap();
+        assert_eq!(mode(&settings_file), 0o600);
+
+        // disabled, the existing permissions are left alone
+        set_restrictive_permissions(false);
+        std::fs::set_permissions(&settings_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+        save_settings_with_filename(crate_name, "private.ser", &t).unwrap();
+        assert_eq!(mode(&settings_file), 0o644);
+        set_restrictive_permissions(true);
+    });
+}
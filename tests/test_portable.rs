@@ -0,0 +1,67 @@
+use cr_program_settings::portable::{
+    executable_dir, get_storage_mode, has_portable_flag, last_storage_warning, set_storage_mode,
+    StorageMode, PORTABLE_FLAG_FILE_NAME,
+};
+use cr_program_settings::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_portable_mode() {
+    let crate_name = "cr_program_settings_portable";
+    let portable_dir = executable_dir().unwrap().join(crate_name);
+    assert_eq!(get_storage_mode(), StorageMode::Home);
+
+    set_storage_mode(StorageMode::Portable);
+    save_settings(crate_name, &TestStruct { a: 1 }).unwrap();
+    assert!(portable_dir.join(format!("{}.ser", crate_name)).is_file());
+    assert_eq!(
+        load_settings::<TestStruct>(crate_name).unwrap(),
+        TestStruct { a: 1 }
+    );
+    assert!(!get_user_home().unwrap().join(crate_name).exists());
+    delete_settings_folder(crate_name).unwrap();
+    assert!(!portable_dir.exists());
+
+    set_storage_mode(StorageMode::Home);
+    assert!(load_settings::<TestStruct>(crate_name).is_err());
+
+    // without the flag file, auto mode stores settings in the users home
+    let flag_file = executable_dir().unwrap().join(PORTABLE_FLAG_FILE_NAME);
+    set_storage_mode(StorageMode::Auto);
+    save_settings(crate_name, &TestStruct { a: 2 }).unwrap();
+    assert!(!portable_dir.exists());
+    assert!(get_user_home().unwrap().join(crate_name).is_dir());
+    // the executable directory was found, so auto mode did not fall back
+    assert_eq!(last_storage_warning(), None);
+    delete_settings_folder(crate_name).unwrap();
+
+    // with the flag file, auto mode stores settings next to the executable
+    std::fs::write(&flag_file, "").unwrap();
+    save_settings(crate_name, &TestStruct { a: 3 }).unwrap();
+    std::fs::remove_file(&flag_file).unwrap();
+    assert!(portable_dir.join(format!("{}.ser", crate_name)).is_file());
+    assert!(!get_user_home().unwrap().join(crate_name).exists());
+    std::fs::remove_dir_all(&portable_dir).unwrap();
+    set_storage_mode(StorageMode::Home);
+}
+
+#[test]
+fn test_has_portable_flag() {
+    let dir = std::env::temp_dir().join("cr_program_settings_portable_flag");
+    std::fs::create_dir_all(&dir).unwrap();
+    assert!(!has_portable_flag(&dir));
+
+    // a folder with the flag name does not count
+    std::fs::create_dir_all(dir.join(PORTABLE_FLAG_FILE_NAME)).unwrap();
+    assert!(!has_portable_flag(&dir));
+    std::fs::remove_dir(dir.join(PORTABLE_FLAG_FILE_NAME)).unwrap();
+
+    std::fs::write(dir.join(PORTABLE_FLAG_FILE_NAME), "").unwrap();
+    assert!(has_portable_flag(&dir));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
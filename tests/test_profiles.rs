@@ -0,0 +1,93 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::profiles::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ProfileSettings {
+    theme: String,
+}
+
+#[test]
+fn test_profiles() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_profiles";
+        let work = ProfileSettings {
+            theme: "light".to_string(),
+        };
+        let home = ProfileSettings {
+            theme: "dark".to_string(),
+        };
+        save_profile(crate_name, "work", &work).unwrap();
+        save_profile(crate_name, "home", &home).unwrap();
+        assert_eq!(
+            list_profiles(crate_name).unwrap(),
+            vec!["home".to_string(), "work".to_string()]
+        );
+        assert_eq!(
+            load_profile::<ProfileSettings>(crate_name, "home").unwrap(),
+            home
+        );
+
+        // nothing is active until a profile is chosen
+        assert_eq!(get_active_profile(crate_name).unwrap(), None);
+        assert!(matches!(
+            load_active_profile::<ProfileSettings>(crate_name),
+            Err(LoadSettingsError::NoActiveProfile)
+        ));
+
+        set_active_profile(crate_name, "work").unwrap();
+        assert_eq!(
+            get_active_profile(crate_name).unwrap(),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            load_active_profile::<ProfileSettings>(crate_name).unwrap(),
+            work
+        );
+
+        // deleting an inactive profile leaves the pointer alone
+        delete_profile(crate_name, "home").unwrap();
+        assert_eq!(list_profiles(crate_name).unwrap(), vec!["work".to_string()]);
+        assert_eq!(
+            get_active_profile(crate_name).unwrap(),
+            Some("work".to_string())
+        );
+
+        // deleting the active profile clears the pointer
+        delete_profile(crate_name, "work").unwrap();
+        assert!(list_profiles(crate_name).unwrap().is_empty());
+        assert_eq!(get_active_profile(crate_name).unwrap(), None);
+    });
+}
+
+#[test]
+fn test_invalid_profile_names() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_profile_names";
+        let settings = ProfileSettings {
+            theme: "dark".to_string(),
+        };
+        for profile in ["", "../escape", "a/b", "a\\b", "work.toml", "with space"] {
+            assert!(!is_valid_profile_name(profile));
+            assert!(matches!(
+                save_profile(crate_name, profile, &settings),
+                Err(SaveSettingsError::InvalidProfileName(_))
+            ));
+            assert!(matches!(
+                load_profile::<ProfileSettings>(crate_name, profile),
+                Err(LoadSettingsError::InvalidProfileName(_))
+            ));
+            assert!(matches!(
+                set_active_profile(crate_name, profile),
+                Err(SaveSettingsError::InvalidProfileName(_))
+            ));
+            assert_eq!(
+                delete_profile(crate_name, profile).unwrap_err().kind(),
+                std::io::ErrorKind::InvalidInput
+            );
+        }
+        assert!(is_valid_profile_name("work-2_laptop"));
+        assert!(!get_user_home().unwrap().join("escape.toml").exists());
+    });
+}
@@ -0,0 +1,100 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::project::{
+    find_project_settings, find_project_settings_from, load_settings_project_merged,
+    load_settings_project_or_user,
+};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ToolSettings {
+    verbose: bool,
+    jobs: u32,
+}
+
+// the current directory is process wide, so every case lives in a single test
+#[test]
+fn test_project_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_project";
+        let file_name = ".cr_program_settings_tool.toml";
+        let home = get_user_home().unwrap().canonicalize().unwrap();
+        let project_dir = home.join("project");
+        let nested_dir = project_dir.join("src").join("nested");
+        std::fs::create_dir_all(&nested_dir).unwrap();
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&nested_dir).unwrap();
+
+        // reaching the root without a match
+        assert_eq!(find_project_settings(file_name), None);
+        assert!(matches!(
+            load_settings_project_or_user::<ToolSettings>(crate_name, file_name),
+            Err(LoadSettingsError::IOError(_))
+        ));
+        assert!(matches!(
+            load_settings_project_merged::<ToolSettings>(crate_name, file_name),
+            Err(LoadSettingsError::IOError(_))
+        ));
+
+        // without a project file the user settings are used
+        let user = ToolSettings {
+            verbose: false,
+            jobs: 4,
+        };
+        save_settings_with_filename(crate_name, file_name, &user).unwrap();
+        assert_eq!(
+            load_settings_project_or_user::<ToolSettings>(crate_name, file_name).unwrap(),
+            user
+        );
+
+        let project_file = project_dir.join(file_name);
+        std::fs::write(&project_file, "verbose = true\n").unwrap();
+        assert_eq!(find_project_settings(file_name), Some(project_file.clone()));
+
+        // a partial project file can only be merged
+        assert!(matches!(
+            load_settings_project_or_user::<ToolSettings>(crate_name, file_name),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+        assert_eq!(
+            load_settings_project_merged::<ToolSettings>(crate_name, file_name).unwrap(),
+            ToolSettings {
+                verbose: true,
+                jobs: 4
+            }
+        );
+
+        std::fs::write(&project_file, "verbose = true\njobs = 1\n").unwrap();
+        assert_eq!(
+            load_settings_project_or_user::<ToolSettings>(crate_name, file_name).unwrap(),
+            ToolSettings {
+                verbose: true,
+                jobs: 1
+            }
+        );
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&project_file));
+
+        // the project file alone is enough to merge
+        delete_setting_file(crate_name, file_name).unwrap();
+        assert_eq!(
+            load_settings_project_merged::<ToolSettings>(crate_name, file_name).unwrap(),
+            ToolSettings {
+                verbose: true,
+                jobs: 1
+            }
+        );
+
+        // a symlinked directory is walked through its real parents
+        #[cfg(unix)]
+        {
+            let link = home.join("link");
+            std::os::unix::fs::symlink(&nested_dir, &link).unwrap();
+            assert_eq!(
+                find_project_settings_from(&link, file_name),
+                Some(project_file.clone())
+            );
+        }
+
+        std::env::set_current_dir(original_dir).unwrap();
+    });
+}
@@ -0,0 +1,89 @@
+use cr_program_settings::backend::{reset_backend, set_backend, MemoryBackend};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use std::path::PathBuf;
+
+#[test]
+fn test_raw_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_raw";
+        let settings_file = get_user_home().unwrap().join(crate_name).join("raw.ser");
+        std::fs::create_dir_all(settings_file.parent().unwrap()).unwrap();
+        // a file that matches no settings struct can still be inspected
+        std::fs::write(
+            &settings_file,
+            "# comment\nold_key = \"x\"\n[nested]\nn = 1\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_settings_string(crate_name, "raw.ser").unwrap(),
+            "# comment\nold_key = \"x\"\n[nested]\nn = 1\n"
+        );
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+        let value = load_settings_raw(crate_name, "raw.ser").unwrap();
+        assert_eq!(value["old_key"].as_str(), Some("x"));
+        assert_eq!(value["nested"]["n"].as_integer(), Some(1));
+
+        save_settings_raw(crate_name, "copy.ser", &value).unwrap();
+        assert_eq!(load_settings_raw(crate_name, "copy.ser").unwrap(), value);
+
+        assert!(matches!(
+            save_settings_raw(crate_name, "copy.ser", &toml::Value::Integer(1)),
+            Err(SaveSettingsError::RootNotATable(_))
+        ));
+
+        std::fs::write(&settings_file, [0xff, 0xfe, 0x00]).unwrap();
+        match read_settings_string(crate_name, "raw.ser") {
+            Err(LoadSettingsError::InvalidEncoding { path, position }) => {
+                assert_eq!(path, settings_file);
+                assert_eq!(position, 0);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // the same tree under the schemaless editing names
+        let crate_name = "cr_program_settings_value";
+        let settings_file = get_user_home().unwrap().join(crate_name).join("value.toml");
+        std::fs::create_dir_all(settings_file.parent().unwrap()).unwrap();
+        std::fs::write(&settings_file, "name = \"app\"\n[window]\nwidth = 800\n").unwrap();
+
+        let mut value = load_settings_value(crate_name, "value.toml").unwrap();
+        assert_eq!(value["window"]["width"].as_integer(), Some(800));
+        value["window"]
+            .as_table_mut()
+            .unwrap()
+            .insert("width".to_string(), toml::Value::Integer(1024));
+        save_settings_value(crate_name, "value.toml", &value).unwrap();
+        assert_eq!(
+            load_settings_value(crate_name, "value.toml").unwrap(),
+            value
+        );
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+
+        assert!(matches!(
+            save_settings_value(crate_name, "value.toml", &toml::Value::Boolean(true)),
+            Err(SaveSettingsError::RootNotATable(_))
+        ));
+        assert!(matches!(
+            load_settings_value(crate_name, "missing.toml"),
+            Err(LoadSettingsError::IOError(_))
+        ));
+    });
+
+    // the backend is global, so it is only swapped once the other checks are done
+    set_backend(Box::new(MemoryBackend::new()));
+    let crate_name = "cr_program_settings_raw_backend";
+    let mut table = toml::value::Table::new();
+    table.insert("key".to_string(), toml::Value::Boolean(true));
+    save_settings_raw(crate_name, "raw.ser", &toml::Value::Table(table)).unwrap();
+    assert_eq!(
+        read_settings_string(crate_name, "raw.ser").unwrap(),
+        "key = true\n"
+    );
+    assert!(SETTINGS_PATHS
+        .read()
+        .unwrap()
+        .contains(&PathBuf::from(crate_name).join("raw.ser")));
+    reset_backend();
+}
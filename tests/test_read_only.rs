@@ -0,0 +1,117 @@
+use cr_program_settings::batch::{save_settings_batch, BatchItem};
+use cr_program_settings::prelude::*;
+use cr_program_settings::settings_container::SettingsContainer;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{is_read_only, set_read_only};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct TestStruct {
+    a: u32,
+}
+
+// read-only mode is global, so the flag is flipped within a single test
+#[test]
+fn test_read_only_mode() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_read_only";
+        save_settings_with_filename(crate_name, "kept.ser", &TestStruct { a: 1 }).unwrap();
+        let mut container =
+            SettingsContainer::new(TestStruct { a: 1 }, crate_name, "container.ser");
+        container.save().unwrap();
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        let files_before = std::fs::read_dir(&settings_dir).unwrap().count();
+
+        set_read_only(true);
+        assert!(is_read_only());
+
+        // saves are refused
+        assert!(matches!(
+            save_settings_with_filename(crate_name, "kept.ser", &TestStruct { a: 2 }),
+            Err(SaveSettingsError::ReadOnlyMode)
+        ));
+        assert!(matches!(
+            save_settings_with_filename(crate_name, "new.ser", &TestStruct { a: 2 }),
+            Err(SaveSettingsError::ReadOnlyMode)
+        ));
+        assert!(matches!(
+            save_settings_to_path(&settings_dir.join("path.ser"), &TestStruct { a: 2 }),
+            Err(SaveSettingsError::ReadOnlyMode)
+        ));
+        let batch_error = save_settings_batch(vec![BatchItem::new(
+            crate_name,
+            "batch.ser",
+            &TestStruct { a: 2 },
+        )])
+        .unwrap_err();
+        assert!(matches!(batch_error.error, SaveSettingsError::ReadOnlyMode));
+        container.modify(|settings| settings.a = 2);
+        assert!(matches!(
+            container.save(),
+            Err(SaveSettingsError::ReadOnlyMode)
+        ));
+        assert!(container.is_dirty());
+
+        // deletes and renames are refused
+        let is_read_only_error = |result: io::Result<()>| matches!(result, Err(err) if err.kind() == io::ErrorKind::PermissionDenied);
+        assert!(is_read_only_error(delete_setting_file(
+            crate_name, "kept.ser"
+        )));
+        assert!(is_read_only_error(delete_settings_folder(crate_name)));
+        assert!(is_read_only_error(rename_settings_file(
+            crate_name,
+            "kept.ser",
+            "renamed.ser"
+        )));
+        assert!(delete_setting_file_if_exists(crate_name, "kept.ser").is_err());
+        assert!(container.delete().is_err());
+
+        // loads work normally, and nothing changed on disk
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "kept.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        assert_eq!(
+            SettingsContainer::<TestStruct>::load(crate_name, "container.ser")
+                .unwrap()
+                .get_settings(),
+            &Some(TestStruct { a: 1 })
+        );
+        assert_eq!(
+            std::fs::read_dir(&settings_dir).unwrap().count(),
+            files_before
+        );
+
+        // leaving read-only mode saves the pending changes
+        set_read_only(false);
+        container.save().unwrap();
+        assert!(!container.is_dirty());
+        assert_eq!(
+            SettingsContainer::<TestStruct>::load(crate_name, "container.ser")
+                .unwrap()
+                .get_settings(),
+            &Some(TestStruct { a: 2 })
+        );
+        delete_settings_folder(crate_name).unwrap();
+
+        // background saves are refused as well
+        #[cfg(feature = "autosave")]
+        {
+            let container = SettingsContainer::new(TestStruct { a: 1 }, crate_name, "auto.ser");
+            let auto_saver = cr_program_settings::autosave::AutoSaver::new(
+                container,
+                std::time::Duration::from_millis(10),
+            );
+            set_read_only(true);
+            auto_saver.modify(|settings| settings.a = 2);
+            assert!(matches!(
+                auto_saver.flush(),
+                Err(SaveSettingsError::ReadOnlyMode)
+            ));
+            drop(auto_saver);
+            set_read_only(false);
+            assert!(!settings_dir.join("auto.ser").exists());
+        }
+    });
+}
@@ -0,0 +1,78 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::registry::{
+    load_all_registered, register_settings_type, registered_settings, reset_all_registered,
+    save_all_registered, unregister_settings,
+};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct PluginA {
+    count: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct PluginB {
+    name: String,
+}
+
+// the registry is global, so every case lives in a single test
+#[test]
+fn test_settings_registry() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_registry";
+        let a = register_settings_type::<PluginA>(crate_name, "a.ser");
+        let b = register_settings_type::<PluginB>(crate_name, "b.ser");
+        assert_eq!(
+            registered_settings(),
+            vec![
+                "cr_program_settings_registry/a.ser".to_string(),
+                "cr_program_settings_registry/b.ser".to_string()
+            ]
+        );
+
+        // nothing has been saved yet, so every load fails and the defaults are kept
+        let errors = load_all_registered().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, "cr_program_settings_registry/a.ser");
+        assert_eq!(*a.read().unwrap(), PluginA::default());
+
+        a.write().unwrap().count = 5;
+        b.write().unwrap().name = "b".to_string();
+        save_all_registered().unwrap();
+        assert_eq!(
+            load_settings_with_filename::<PluginA>(crate_name, "a.ser").unwrap(),
+            PluginA { count: 5 }
+        );
+
+        reset_all_registered();
+        assert_eq!(*a.read().unwrap(), PluginA::default());
+        assert_eq!(*b.read().unwrap(), PluginB::default());
+        load_all_registered().unwrap();
+        assert_eq!(*a.read().unwrap(), PluginA { count: 5 });
+        assert_eq!(b.read().unwrap().name, "b");
+
+        // registering again replaces the previous handle
+        let a2 = register_settings_type::<PluginA>(crate_name, "a.ser");
+        assert_eq!(registered_settings().len(), 2);
+        a2.write().unwrap().count = 9;
+        save_all_registered().unwrap();
+        assert_eq!(
+            load_settings_with_filename::<PluginA>(crate_name, "a.ser").unwrap(),
+            PluginA { count: 9 }
+        );
+
+        // a failing save does not stop the others
+        std::fs::remove_file(get_user_home().unwrap().join(crate_name).join("b.ser")).unwrap();
+        std::fs::create_dir(get_user_home().unwrap().join(crate_name).join("b.ser")).unwrap();
+        let errors = save_all_registered().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "cr_program_settings_registry/b.ser");
+        assert!(matches!(errors[0].1, SaveSettingsError::PathIsDirectory(_)));
+
+        assert!(unregister_settings("cr_program_settings_registry/a.ser"));
+        assert!(!unregister_settings("cr_program_settings_registry/a.ser"));
+        assert!(unregister_settings("cr_program_settings_registry/b.ser"));
+        assert!(registered_settings().is_empty());
+    });
+}
@@ -0,0 +1,151 @@
+use cr_program_settings::options::{
+    load_settings_with_options, save_settings_with_options, LoadOptions, SaveOptions,
+};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_relative_dir_outside_root() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let outside = home.parent().unwrap().join("cr_program_settings_outside");
+        let escaping = [
+            Path::new("..").join("cr_program_settings_outside"),
+            Path::new("vendor").join("..").join(".."),
+            Path::new(".").join("vendor"),
+            outside.clone(),
+        ];
+        for relative_dir in &escaping {
+            match save_settings_in_dir(relative_dir, "file.ser", &TestStruct { a: 1 }) {
+                Err(SaveSettingsError::IOError(err)) => {
+                    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput)
+                }
+                other => panic!(
+                    "expected {:?} to be rejected, got {:?}",
+                    relative_dir, other
+                ),
+            }
+            match load_settings_in_dir::<TestStruct>(relative_dir, "file.ser") {
+                Err(LoadSettingsError::IOError(err)) => {
+                    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput)
+                }
+                other => panic!(
+                    "expected {:?} to be rejected, got {:?}",
+                    relative_dir, other
+                ),
+            }
+        }
+        assert!(!outside.exists());
+
+        // nested folders are still allowed
+        let vendor_dir = Path::new("cr_program_settings_vendor_dir").join("tool");
+        save_settings_in_dir(&vendor_dir, "file.ser", &TestStruct { a: 2 }).unwrap();
+        assert_eq!(
+            load_settings_in_dir::<TestStruct>(&vendor_dir, "file.ser").unwrap(),
+            TestStruct { a: 2 }
+        );
+    });
+}
+
+/// Asserts that an io error is the `InvalidInput` returned for a path outside the settings root
+fn assert_invalid_input(err: &std::io::Error) {
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{:?}", err);
+}
+
+#[test]
+fn test_file_name_outside_root() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let crate_name = "cr_program_settings_file_name_dir";
+        let escaping = ["../../x.ser", "../x.ser", "sub/x.ser", "", ".", ".."];
+        for file_name in escaping {
+            match save_settings_in_dir(Path::new("vendor"), file_name, &TestStruct { a: 1 }) {
+                Err(SaveSettingsError::IOError(err)) => assert_invalid_input(&err),
+                other => panic!("expected {:?} to be rejected, got {:?}", file_name, other),
+            }
+            match save_settings_with_filename(crate_name, file_name, &TestStruct { a: 1 }) {
+                Err(SaveSettingsError::IOError(err)) => assert_invalid_input(&err),
+                other => panic!("expected {:?} to be rejected, got {:?}", file_name, other),
+            }
+            match load_settings_with_filename::<TestStruct>(crate_name, file_name) {
+                Err(LoadSettingsError::IOError(err)) => assert_invalid_input(&err),
+                other => panic!("expected {:?} to be rejected, got {:?}", file_name, other),
+            }
+        }
+        assert!(!home.parent().unwrap().join("x.ser").exists());
+        assert!(!home.join("x.ser").exists());
+    });
+}
+
+#[test]
+fn test_other_entry_points_outside_root() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let settings = TestStruct { a: 1 };
+        let bad_locations = [
+            ("cr_program_settings_entry_points", "../x.ser"),
+            ("..", "x.ser"),
+            (".", "x.ser"),
+            // an empty folder is the settings root itself
+            ("", "x.ser"),
+        ];
+        for (crate_name, file_name) in bad_locations {
+            let save_results = [
+                save_settings_with_header(crate_name, file_name, &settings, "header"),
+                save_settings_new(crate_name, file_name, &settings),
+                save_settings_with_limit(crate_name, file_name, &settings, 1024),
+                save_settings_with_options(crate_name, file_name, &settings, &SaveOptions::new()),
+            ];
+            for result in save_results {
+                match result {
+                    Err(SaveSettingsError::IOError(err)) => assert_invalid_input(&err),
+                    other => panic!(
+                        "expected {:?}/{:?} to be rejected, got {:?}",
+                        crate_name, file_name, other
+                    ),
+                }
+            }
+            let load_results = [
+                load_settings_with_limit::<TestStruct>(crate_name, file_name, 1024).map(|_| ()),
+                read_settings_string(crate_name, file_name).map(|_| ()),
+                load_settings_with_options::<TestStruct>(
+                    crate_name,
+                    file_name,
+                    &LoadOptions::new(),
+                )
+                .map(|_| ()),
+            ];
+            for result in load_results {
+                match result {
+                    Err(LoadSettingsError::IOError(err)) => assert_invalid_input(&err),
+                    other => panic!(
+                        "expected {:?}/{:?} to be rejected, got {:?}",
+                        crate_name, file_name, other
+                    ),
+                }
+            }
+            assert_invalid_input(&delete_setting_file(crate_name, file_name).unwrap_err());
+        }
+        assert!(!home.join("x.ser").exists());
+        assert!(!home.parent().unwrap().join("x.ser").exists());
+        assert_invalid_input(&delete_settings_folder("").unwrap_err());
+        assert!(home.is_dir());
+
+        // renaming a file out of its folder leaves it in place
+        let crate_name = "cr_program_settings_entry_points";
+        save_settings_with_filename(crate_name, "x.ser", &settings).unwrap();
+        assert_invalid_input(&rename_settings_file(crate_name, "x.ser", "../y.ser").unwrap_err());
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "x.ser").unwrap(),
+            settings
+        );
+        assert!(!home.join("y.ser").exists());
+    });
+}
@@ -0,0 +1,51 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_rename_settings_file() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_rename";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        assert_eq!(
+            rename_settings_file(crate_name, "old.ser", "new.ser")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotFound
+        );
+
+        save_settings_with_filename(crate_name, "old.ser", &TestStruct { a: 1 }).unwrap();
+        rename_settings_file(crate_name, "old.ser", "new.ser").unwrap();
+        assert!(!settings_dir.join("old.ser").exists());
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "new.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        let paths = SETTINGS_PATHS.read().unwrap().clone();
+        assert!(!paths.contains(&settings_dir.join("old.ser")));
+        assert!(paths.contains(&settings_dir.join("new.ser")));
+
+        // an existing destination is never replaced
+        save_settings_with_filename(crate_name, "other.ser", &TestStruct { a: 2 }).unwrap();
+        assert_eq!(
+            rename_settings_file(crate_name, "other.ser", "new.ser")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "new.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "other.ser").unwrap(),
+            TestStruct { a: 2 }
+        );
+    });
+}
@@ -0,0 +1,161 @@
+use cr_program_settings::backend::{set_backend, BackendError, MemoryBackend, SettingsBackend};
+use cr_program_settings::options::{
+    load_settings_with_options, save_settings_with_options, save_settings_with_retry, LoadOptions,
+    SaveOptions,
+};
+use cr_program_settings::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+/// A backend that fails the next `failures` reads and writes with `kind`, like a file briefly locked by an antivirus scanner
+struct FlakyBackend {
+    inner: MemoryBackend,
+    failures: Arc<AtomicU32>,
+    kind: io::ErrorKind,
+}
+
+impl FlakyBackend {
+    fn fail(&self) -> Result<(), BackendError> {
+        let remaining = self.failures.load(Ordering::SeqCst);
+        if remaining == 0 {
+            return Ok(());
+        }
+        self.failures.store(remaining - 1, Ordering::SeqCst);
+        Err(BackendError::IOError(io::Error::new(self.kind, "flaky")))
+    }
+}
+
+impl SettingsBackend for FlakyBackend {
+    fn read(&self, folder: &Path, file: &str) -> Result<Vec<u8>, BackendError> {
+        self.fail()?;
+        self.inner.read(folder, file)
+    }
+
+    fn write(&self, folder: &Path, file: &str, bytes: &[u8]) -> Result<(), BackendError> {
+        self.fail()?;
+        self.inner.write(folder, file, bytes)
+    }
+
+    fn delete(&self, folder: &Path, file: &str) -> Result<(), BackendError> {
+        self.inner.delete(folder, file)
+    }
+
+    fn delete_folder(&self, folder: &Path) -> Result<(), BackendError> {
+        self.inner.delete_folder(folder)
+    }
+
+    fn exists(&self, folder: &Path, file: &str) -> bool {
+        self.inner.exists(folder, file)
+    }
+
+    fn list(&self, folder: &Path) -> Result<Vec<String>, BackendError> {
+        self.inner.list(folder)
+    }
+
+    fn resolve(&self, folder: &Path) -> Option<PathBuf> {
+        self.inner.resolve(folder)
+    }
+}
+
+#[test]
+fn test_retries() {
+    // the backend is global, so every assertion about it lives in this one test
+    let failures = Arc::new(AtomicU32::new(0));
+    set_backend(Box::new(FlakyBackend {
+        inner: MemoryBackend::new(),
+        failures: failures.clone(),
+        kind: io::ErrorKind::PermissionDenied,
+    }));
+    let crate_name = "cr_program_settings_retries";
+    let save_options = SaveOptions::new()
+        .retries(3)
+        .retry_delay(Duration::from_millis(1));
+    let load_options = LoadOptions::new()
+        .retries(3)
+        .retry_delay(Duration::from_millis(1));
+
+    // failing fewer times than there are retries succeeds
+    failures.store(3, Ordering::SeqCst);
+    save_settings_with_options(crate_name, "flaky.ser", &TestStruct { a: 1 }, &save_options)
+        .unwrap();
+    assert_eq!(failures.load(Ordering::SeqCst), 0);
+    failures.store(2, Ordering::SeqCst);
+    assert_eq!(
+        load_settings_with_options::<TestStruct>(crate_name, "flaky.ser", &load_options).unwrap(),
+        TestStruct { a: 1 }
+    );
+
+    // running out of retries reports every attempt
+    failures.store(10, Ordering::SeqCst);
+    match save_settings_with_options(crate_name, "flaky.ser", &TestStruct { a: 2 }, &save_options) {
+        Err(SaveSettingsError::IOErrorAfterRetries { attempts, source }) => {
+            assert_eq!(attempts, 4);
+            assert_eq!(source.kind(), io::ErrorKind::PermissionDenied);
+        }
+        other => panic!("expected IOErrorAfterRetries, got {:?}", other),
+    }
+    assert_eq!(failures.load(Ordering::SeqCst), 6);
+    match load_settings_with_options::<TestStruct>(crate_name, "flaky.ser", &load_options) {
+        Err(LoadSettingsError::IOErrorAfterRetries { attempts, .. }) => assert_eq!(attempts, 4),
+        other => panic!("expected IOErrorAfterRetries, got {:?}", other),
+    }
+    assert_eq!(failures.load(Ordering::SeqCst), 2);
+
+    // the shorthand retries the same way
+    failures.store(2, Ordering::SeqCst);
+    save_settings_with_retry(
+        crate_name,
+        "flaky.ser",
+        &TestStruct { a: 4 },
+        2,
+        Duration::from_millis(1),
+    )
+    .unwrap();
+    failures.store(3, Ordering::SeqCst);
+    assert!(matches!(
+        save_settings_with_retry(
+            crate_name,
+            "flaky.ser",
+            &TestStruct { a: 5 },
+            2,
+            Duration::from_millis(1)
+        ),
+        Err(SaveSettingsError::IOErrorAfterRetries { attempts: 3, .. })
+    ));
+    failures.store(2, Ordering::SeqCst);
+
+    // without retries the error is returned as is
+    assert!(matches!(
+        load_settings_with_options::<TestStruct>(crate_name, "flaky.ser", &LoadOptions::new()),
+        Err(LoadSettingsError::IOError(_))
+    ));
+    assert_eq!(failures.load(Ordering::SeqCst), 1);
+    failures.store(0, Ordering::SeqCst);
+
+    // errors that are not temporary fail immediately
+    let failures = Arc::new(AtomicU32::new(10));
+    set_backend(Box::new(FlakyBackend {
+        inner: MemoryBackend::new(),
+        failures: failures.clone(),
+        kind: io::ErrorKind::NotFound,
+    }));
+    assert!(matches!(
+        load_settings_with_options::<TestStruct>(crate_name, "flaky.ser", &load_options),
+        Err(LoadSettingsError::IOError(err)) if err.kind() == io::ErrorKind::NotFound
+    ));
+    assert_eq!(failures.load(Ordering::SeqCst), 9);
+    assert!(matches!(
+        save_settings_with_options(crate_name, "flaky.ser", &TestStruct { a: 3 }, &save_options),
+        Err(SaveSettingsError::IOError(_))
+    ));
+    assert_eq!(failures.load(Ordering::SeqCst), 8);
+}
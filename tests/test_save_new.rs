@@ -0,0 +1,75 @@
+use cr_program_settings::backend::{MemoryBackend, SettingsBackend};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{reset_home_resolver, set_home_resolver};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Barrier};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct FirstRun {
+    id: u32,
+}
+
+#[test]
+fn test_save_settings_new() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_save_new";
+        save_settings_new(crate_name, "new.ser", &FirstRun { id: 1 }).unwrap();
+        match save_settings_new(crate_name, "new.ser", &FirstRun { id: 2 }) {
+            Err(SaveSettingsError::AlreadyExists(path)) => {
+                assert_eq!(
+                    path,
+                    get_user_home().unwrap().join(crate_name).join("new.ser")
+                )
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(
+            load_settings_with_filename::<FirstRun>(crate_name, "new.ser").unwrap(),
+            FirstRun { id: 1 }
+        );
+        // a normal save still replaces the file
+        save_settings_with_filename(crate_name, "new.ser", &FirstRun { id: 3 }).unwrap();
+        assert_eq!(
+            load_settings_with_filename::<FirstRun>(crate_name, "new.ser").unwrap(),
+            FirstRun { id: 3 }
+        );
+    });
+}
+
+#[test]
+fn test_save_settings_new_race() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_save_new_race";
+        // the temporary home only applies to this thread, so share it with the workers through the resolver
+        let home = get_user_home().unwrap();
+        set_home_resolver(move || Some(home.clone()));
+        let barrier = Arc::new(Barrier::new(8));
+        let workers: Vec<_> = (0..8)
+            .map(|id| {
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    save_settings_new(crate_name, "race.ser", &FirstRun { id }).is_ok()
+                })
+            })
+            .collect();
+        let winners = workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .filter(|won| *won)
+            .count();
+        reset_home_resolver();
+        assert_eq!(winners, 1);
+    });
+}
+
+#[test]
+fn test_memory_backend_write_new() {
+    let backend = MemoryBackend::new();
+    let folder = Path::new("folder");
+    backend.write_new(folder, "file", b"first").unwrap();
+    assert!(backend.write_new(folder, "file", b"second").is_err());
+    assert_eq!(backend.read(folder, "file").unwrap(), b"first");
+}
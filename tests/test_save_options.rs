@@ -0,0 +1,138 @@
+use cr_program_settings::options::{save_settings_with_options, LineEnding, SaveOptions};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct OptionsSettings {
+    name: String,
+    notes: String,
+    table: BTreeMap<String, u32>,
+}
+
+fn settings() -> OptionsSettings {
+    OptionsSettings {
+        name: "options".to_string(),
+        notes: "first line\nsecond line".to_string(),
+        table: [("a".to_string(), 1)].into_iter().collect(),
+    }
+}
+
+/// Saves `settings()` with the line endings and returns the bytes of the file
+fn save_with(line_endings: LineEnding) -> Vec<u8> {
+    let crate_name = "cr_program_settings_save_options";
+    let options = SaveOptions::new().line_endings(line_endings);
+    save_settings_with_options(crate_name, "options.ser", &settings(), &options).unwrap();
+    let file_data = std::fs::read(
+        get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("options.ser"),
+    )
+    .unwrap();
+    // loading accepts either line ending
+    assert_eq!(
+        load_settings_with_filename::<OptionsSettings>(crate_name, "options.ser").unwrap(),
+        settings()
+    );
+    file_data
+}
+
+#[test]
+fn test_line_endings() {
+    with_temp_settings_dir(|| {
+        let lf = save_with(LineEnding::Lf);
+        assert!(lf.ends_with(b"\n") && !lf.ends_with(b"\n\n"));
+        assert!(!lf.contains(&b'\r'));
+
+        // the default options save exactly what the plain save function does
+        save_settings_with_options(
+            "cr_program_settings_save_options",
+            "default.ser",
+            &settings(),
+            &SaveOptions::default(),
+        )
+        .unwrap();
+        save_settings_with_filename("cr_program_settings_save_options", "plain.ser", &settings())
+            .unwrap();
+        let settings_dir = get_user_home()
+            .unwrap()
+            .join("cr_program_settings_save_options");
+        assert_eq!(
+            std::fs::read(settings_dir.join("default.ser")).unwrap(),
+            std::fs::read(settings_dir.join("plain.ser")).unwrap()
+        );
+        assert_eq!(std::fs::read(settings_dir.join("plain.ser")).unwrap(), lf);
+
+        let crlf = save_with(LineEnding::Crlf);
+        let lf_text = String::from_utf8(lf.clone()).unwrap();
+        assert_eq!(
+            String::from_utf8(crlf).unwrap(),
+            lf_text.replace('\n', "\r\n")
+        );
+
+        let native = save_with(LineEnding::Native);
+        if cfg!(windows) {
+            assert_eq!(native, lf_text.replace('\n', "\r\n").into_bytes());
+        } else {
+            assert_eq!(native, lf);
+        }
+    });
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct EmptySettings {}
+
+#[test]
+fn test_single_trailing_newline() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_trailing_newline";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        for (line_endings, expected) in [(LineEnding::Lf, "\n"), (LineEnding::Crlf, "\r\n")] {
+            let options = SaveOptions::new().line_endings(line_endings);
+            save_settings_with_options(crate_name, "empty.ser", &EmptySettings {}, &options)
+                .unwrap();
+            assert_eq!(
+                std::fs::read_to_string(settings_dir.join("empty.ser")).unwrap(),
+                expected
+            );
+            assert_eq!(
+                load_settings_with_filename::<EmptySettings>(crate_name, "empty.ser").unwrap(),
+                EmptySettings {}
+            );
+        }
+    });
+}
+
+#[test]
+fn test_header_create_new_and_limit_options() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_save_options_combined";
+        // the header takes the configured line endings as well
+        let options = SaveOptions::new()
+            .header("first run defaults")
+            .line_endings(LineEnding::Crlf)
+            .create_new(true)
+            .max_file_size(1024);
+        save_settings_with_options(crate_name, "combined.ser", &settings(), &options).unwrap();
+        let text = read_settings_string(crate_name, "combined.ser").unwrap();
+        assert!(text.starts_with("# first run defaults\r\n\r\nname = \"options\"\r\n"));
+
+        assert!(matches!(
+            save_settings_with_options(crate_name, "combined.ser", &settings(), &options),
+            Err(SaveSettingsError::AlreadyExists(_))
+        ));
+
+        let options = SaveOptions::new().max_file_size(8);
+        assert!(matches!(
+            save_settings_with_options(crate_name, "limited.ser", &settings(), &options),
+            Err(SaveSettingsError::FileTooLarge { limit: 8, .. })
+        ));
+        assert!(!get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("limited.ser")
+            .exists());
+    });
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "keyring")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::secrets::{
+    keyring, load_settings_with_secrets, save_settings_with_secrets, set_secret_storage, Secret,
+    SecretStorage,
+};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ApiSettings {
+    endpoint: String,
+    token: Secret,
+    nested: Nested,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Nested {
+    passwords: Vec<Secret>,
+}
+
+#[test]
+fn test_secrets() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_secrets";
+        let settings = ApiSettings {
+            endpoint: "https://example.com".to_string(),
+            token: Secret("token value".to_string()),
+            nested: Nested {
+                passwords: vec![Secret("password value".to_string())],
+            },
+        };
+        assert_eq!(format!("{:?}", settings.token), "Secret(..)");
+        let settings_file = get_user_home().unwrap().join(crate_name).join("api.ser");
+
+        // plaintext mode keeps secrets inline, e.g. for CI without a keyring
+        set_secret_storage(SecretStorage::Plaintext);
+        save_settings_with_secrets(crate_name, "api.ser", &settings).unwrap();
+        let file_data = std::fs::read_to_string(&settings_file).unwrap();
+        assert!(file_data.contains("token = \"token value\""));
+        assert_eq!(
+            load_settings_with_secrets::<ApiSettings>(crate_name, "api.ser").unwrap(),
+            settings
+        );
+        // secrets are plain strings to the rest of the library
+        assert_eq!(
+            load_settings_with_filename::<ApiSettings>(crate_name, "api.ser").unwrap(),
+            settings
+        );
+
+        // keyring mode only writes placeholders, the mock keyring does not keep entries, so loading reports the missing entry
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        set_secret_storage(SecretStorage::Keyring);
+        save_settings_with_secrets(crate_name, "api.ser", &settings).unwrap();
+        let file_data = std::fs::read_to_string(&settings_file).unwrap();
+        assert!(!file_data.contains("token value"));
+        assert!(!file_data.contains("password value"));
+        assert!(file_data.contains("token = \"keyring:cr_program_settings_secrets/api.ser/token\""));
+        assert!(file_data
+            .contains("\"keyring:cr_program_settings_secrets/api.ser/nested.passwords.0\""));
+        assert!(file_data.contains("endpoint = \"https://example.com\""));
+        assert!(matches!(
+            load_settings_with_secrets::<ApiSettings>(crate_name, "api.ser"),
+            Err(LoadSettingsError::KeyringError(keyring::Error::NoEntry))
+        ));
+    });
+}
@@ -0,0 +1,109 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::sections::{delete_section, list_sections, load_section, save_section};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Database {
+    host: String,
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ui {
+    dark_mode: bool,
+    font_size: u32,
+}
+
+#[test]
+fn test_sections() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_sections";
+        let file_name = "combined.toml";
+        let database = Database {
+            host: "localhost".to_string(),
+            port: 5432,
+        };
+        let ui = Ui {
+            dark_mode: true,
+            font_size: 12,
+        };
+
+        save_section(crate_name, file_name, "database", &database).unwrap();
+        save_section(crate_name, file_name, "ui", &ui).unwrap();
+        assert_eq!(
+            list_sections(crate_name, file_name).unwrap(),
+            vec!["database", "ui"]
+        );
+        assert_eq!(
+            load_section::<Database>(crate_name, file_name, "database").unwrap(),
+            database
+        );
+        assert_eq!(load_section::<Ui>(crate_name, file_name, "ui").unwrap(), ui);
+
+        // updating a section leaves the others alone
+        let ui = Ui {
+            dark_mode: false,
+            font_size: 14,
+        };
+        save_section(crate_name, file_name, "ui", &ui).unwrap();
+        assert_eq!(load_section::<Ui>(crate_name, file_name, "ui").unwrap(), ui);
+        assert_eq!(
+            load_section::<Database>(crate_name, file_name, "database").unwrap(),
+            database
+        );
+
+        // keys that are not sections, e.g. added by hand, are kept but not listed
+        let settings_file = get_user_home().unwrap().join(crate_name).join(file_name);
+        let contents = std::fs::read_to_string(&settings_file).unwrap();
+        std::fs::write(&settings_file, format!("version = 2\n{}", contents)).unwrap();
+        save_section(crate_name, file_name, "ui", &ui).unwrap();
+        assert_eq!(
+            list_sections(crate_name, file_name).unwrap(),
+            vec!["database", "ui"]
+        );
+        assert!(read_settings_string(crate_name, file_name)
+            .unwrap()
+            .starts_with("version = 2\n"));
+
+        // a section of the wrong shape fails like any other load
+        assert!(matches!(
+            load_section::<Ui>(crate_name, file_name, "database"),
+            Err(LoadSettingsError::DeserializationError(_))
+        ));
+
+        assert!(delete_section(crate_name, file_name, "ui").unwrap());
+        assert!(!delete_section(crate_name, file_name, "ui").unwrap());
+        assert!(matches!(
+            load_section::<Ui>(crate_name, file_name, "ui"),
+            Err(LoadSettingsError::SectionNotFound(name)) if name == "ui"
+        ));
+        assert_eq!(
+            list_sections(crate_name, file_name).unwrap(),
+            vec!["database"]
+        );
+    });
+}
+
+#[test]
+fn test_section_errors() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_section_errors";
+
+        // a section must be a table
+        assert!(matches!(
+            save_section(crate_name, "errors.toml", "count", &3u32),
+            Err(SettingsError::Save(SaveSettingsError::RootNotATable(_)))
+        ));
+
+        // a missing file is an error, except when saving, which creates it
+        assert!(matches!(
+            load_section::<Ui>(crate_name, "errors.toml", "ui"),
+            Err(LoadSettingsError::IOError(_))
+        ));
+        assert!(matches!(
+            delete_section(crate_name, "errors.toml", "ui"),
+            Err(SettingsError::Load(LoadSettingsError::IOError(_)))
+        ));
+    });
+}
@@ -0,0 +1,233 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::settings_container::SettingsContainer;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_save_checked() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_conflicts";
+        let file_name = "conflict.ser";
+
+        // a new container conflicts with an existing file it has never seen
+        SettingsContainer::new(TestStruct { a: 1 }, crate_name, file_name)
+            .save()
+            .unwrap();
+        let mut ours = SettingsContainer::new(TestStruct { a: 2 }, crate_name, file_name);
+        assert!(matches!(
+            ours.save_checked(),
+            Err(SaveSettingsError::ConflictDetected {
+                on_disk_modified: Some(_)
+            })
+        ));
+        assert_eq!(
+            ours.load_theirs().unwrap().get_settings(),
+            &Some(TestStruct { a: 1 })
+        );
+
+        // saving repeatedly is fine while nobody else touches the file
+        ours.force_save().unwrap();
+        ours.set_settings(TestStruct { a: 3 });
+        ours.save_checked().unwrap();
+        ours.set_settings(TestStruct { a: 4 });
+        ours.save_checked().unwrap();
+
+        // an edit made outside the program is not clobbered
+        let settings_file = get_user_home().unwrap().join(crate_name).join(file_name);
+        let edited = std::fs::read_to_string(&settings_file)
+            .unwrap()
+            .replace("a = 4", "a = 40");
+        std::fs::write(&settings_file, edited).unwrap();
+        assert!(ours.save_checked().is_err());
+        assert_eq!(
+            load_settings_with_filename::<SettingsContainer<TestStruct>>(crate_name, file_name)
+                .unwrap()
+                .get_settings(),
+            &Some(TestStruct { a: 40 })
+        );
+
+        // a deleted file is recreated
+        std::fs::remove_file(&settings_file).unwrap();
+        ours.save_checked().unwrap();
+        let loaded = SettingsContainer::<TestStruct>::load(crate_name, file_name).unwrap();
+        assert_eq!(loaded, ours);
+    });
+}
+
+#[test]
+fn test_container_exists_and_delete() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_container_delete";
+        let container = SettingsContainer::new(TestStruct { a: 1 }, crate_name, "delete.ser");
+        assert!(!container.exists());
+        assert!(container.delete().is_err());
+
+        container.save().unwrap();
+        assert!(container.exists());
+        let settings_file = get_user_home().unwrap().join(crate_name).join("delete.ser");
+        assert!(SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+
+        container.delete().unwrap();
+        assert!(!container.exists());
+        assert!(!settings_file.exists());
+        assert!(!SETTINGS_PATHS.read().unwrap().contains(&settings_file));
+        assert_eq!(container.get_settings(), &Some(TestStruct { a: 1 }));
+    });
+}
+
+#[test]
+fn test_container_dirty_flag() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_container_dirty";
+        let mut container = SettingsContainer::new(TestStruct { a: 1 }, crate_name, "dirty.ser");
+        assert!(container.is_dirty());
+        container.save().unwrap();
+        assert!(!container.is_dirty());
+
+        container.modify(|settings| settings.a = 2);
+        assert!(container.is_dirty());
+        container.save_checked().unwrap();
+        assert!(!container.is_dirty());
+
+        container.set_settings(TestStruct { a: 3 });
+        assert!(container.is_dirty());
+        container.force_save().unwrap();
+
+        container.get_mut_settings().unwrap().a = 4;
+        assert!(container.is_dirty());
+
+        let mut loaded = SettingsContainer::<TestStruct>::load(crate_name, "dirty.ser").unwrap();
+        assert!(!loaded.is_dirty());
+        assert_eq!(loaded.get_settings(), &Some(TestStruct { a: 3 }));
+        assert!(loaded.take_settings().is_some());
+        assert!(loaded.is_dirty());
+
+        // a container without settings has nothing to modify
+        let mut empty = SettingsContainer::<TestStruct>::default(crate_name, "empty.ser");
+        empty.modify(|settings| settings.a = 5);
+        assert!(!empty.is_dirty());
+
+        container.delete().unwrap();
+        assert!(container.is_dirty());
+    });
+}
+
+#[test]
+fn test_container_conversions() {
+    let container = SettingsContainer::new(TestStruct { a: 1 }, "some_crate", "some_file.ser");
+    assert_eq!(container.into_inner(), Some(TestStruct { a: 1 }));
+
+    let container = SettingsContainer::<TestStruct>::default("some_crate", "some_file.ser");
+    let inner: Option<TestStruct> = container.into();
+    assert_eq!(inner, None);
+
+    let container = SettingsContainer::with_default_names(TestStruct { a: 2 }, "some_crate");
+    assert_eq!(container.crate_name(), "some_crate");
+    assert_eq!(container.file_name(), "some_crate.ser");
+    assert!(container.is_dirty());
+    assert_eq!(Option::from(container), Some(TestStruct { a: 2 }));
+}
+
+#[test]
+fn test_try_load_or_else() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_or_else";
+
+        // a missing file falls back without writing anything
+        let fallback =
+            SettingsContainer::try_load_or_else(crate_name, "or_else.ser", || TestStruct { a: 1 });
+        assert_eq!(fallback.get_settings(), &Some(TestStruct { a: 1 }));
+        assert!(fallback.is_dirty());
+        assert!(!fallback.exists());
+
+        // the closure is handed the reason for the fallback
+        let fallback =
+            SettingsContainer::try_load_or_else_with_error(crate_name, "or_else.ser", |err| {
+                assert!(matches!(err, LoadSettingsError::IOError(_)));
+                TestStruct { a: 2 }
+            });
+        assert_eq!(fallback.get_settings(), &Some(TestStruct { a: 2 }));
+
+        // the fallback can be persisted, after which it is loaded instead of calling the closure
+        let saved = SettingsContainer::try_load_or_else_and_save(crate_name, "or_else.ser", || {
+            TestStruct { a: 3 }
+        })
+        .unwrap();
+        assert!(!saved.is_dirty());
+        assert!(saved.exists());
+        let loaded =
+            SettingsContainer::<TestStruct>::try_load_or_else(crate_name, "or_else.ser", || {
+                panic!("the saved fallback should load")
+            });
+        assert_eq!(loaded.get_settings(), &Some(TestStruct { a: 3 }));
+        assert!(!loaded.is_dirty());
+    });
+}
+
+#[test]
+fn test_try_load_or_default_with_reason() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_fallback_reason";
+
+        let (container, reason) = SettingsContainer::<TestStruct>::try_load_or_default_with_reason(
+            crate_name,
+            "reason.ser",
+        );
+        assert_eq!(container.get_settings(), &None);
+        let reason = reason.unwrap();
+        assert!(reason.is_not_found());
+        assert!(!reason.is_corrupt());
+
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(settings_dir.join("reason.ser"), "a = [").unwrap();
+        let (_, reason) = SettingsContainer::<TestStruct>::try_load_or_default_with_reason(
+            crate_name,
+            "reason.ser",
+        );
+        let reason = reason.unwrap();
+        assert!(reason.is_corrupt());
+        assert!(!reason.is_not_found());
+
+        SettingsContainer::new(TestStruct { a: 1 }, crate_name, "reason.ser")
+            .save()
+            .unwrap();
+        let (container, reason) = SettingsContainer::<TestStruct>::try_load_or_default_with_reason(
+            crate_name,
+            "reason.ser",
+        );
+        assert_eq!(container.get_settings(), &Some(TestStruct { a: 1 }));
+        assert!(reason.is_none());
+    });
+}
+
+#[test]
+fn test_try_load_or_default_quarantined() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_container_quarantine";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(settings_dir.join("quarantine.ser"), "a = [").unwrap();
+
+        let (container, quarantined) =
+            SettingsContainer::<TestStruct>::try_load_or_default_quarantined(
+                crate_name,
+                "quarantine.ser",
+            );
+        assert_eq!(container.get_settings(), &None);
+        let quarantined = quarantined.unwrap();
+        assert_eq!(std::fs::read_to_string(&quarantined).unwrap(), "a = [");
+        assert!(!container.exists());
+
+        let (_, quarantined) = SettingsContainer::<TestStruct>::try_load_or_default_quarantined(
+            crate_name,
+            "quarantine.ser",
+        );
+        assert_eq!(quarantined, None);
+    });
+}
@@ -0,0 +1,72 @@
+use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+use cr_program_settings::paths::SettingsPath;
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct PathSettings {
+    downloads: SettingsPath,
+    cache: SettingsPath,
+    absolute: SettingsPath,
+    from_env: SettingsPath,
+}
+
+#[test]
+fn test_settings_path() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let crate_name = "cr_program_settings_settings_path";
+        std::env::set_var("CR_PROGRAM_SETTINGS_PATH_TEST", "from_env_dir");
+        let absolute = home.join("absolute").to_string_lossy().to_string();
+        let settings = PathSettings {
+            downloads: SettingsPath::new("~/Downloads"),
+            cache: SettingsPath::new("cache/thumbnails"),
+            absolute: SettingsPath::new(absolute.clone()),
+            from_env: SettingsPath::new("~/$CR_PROGRAM_SETTINGS_PATH_TEST/file.txt"),
+        };
+        // outside of a load, relative paths stay relative
+        assert_eq!(
+            settings.cache.as_resolved(),
+            PathBuf::from("cache/thumbnails")
+        );
+        assert_eq!(settings.downloads.as_resolved(), home.join("Downloads"));
+        save_settings_with_filename(crate_name, "paths.toml", &settings).unwrap();
+
+        // the original strings are written back
+        let text = read_settings_string(crate_name, "paths.toml").unwrap();
+        assert!(text.contains("\"~/Downloads\""));
+        assert!(text.contains("\"~/$CR_PROGRAM_SETTINGS_PATH_TEST/file.txt\""));
+
+        let loaded = load_settings_with_filename::<PathSettings>(crate_name, "paths.toml").unwrap();
+        assert_eq!(loaded.downloads.original(), "~/Downloads");
+        assert_eq!(loaded.downloads.as_resolved(), home.join("Downloads"));
+        assert_eq!(
+            loaded.cache.as_resolved(),
+            home.join(crate_name).join("cache/thumbnails")
+        );
+        assert_eq!(loaded.absolute.as_resolved(), PathBuf::from(&absolute));
+        assert_eq!(
+            loaded.from_env.as_resolved(),
+            home.join("from_env_dir/file.txt")
+        );
+
+        let loaded = load_settings_with_options::<PathSettings>(
+            crate_name,
+            "paths.toml",
+            &LoadOptions::new().expand_env(true),
+        )
+        .unwrap();
+        assert_eq!(
+            loaded.cache.as_resolved(),
+            home.join(crate_name).join("cache/thumbnails")
+        );
+
+        // a tilde that does not start the path is kept
+        assert_eq!(
+            SettingsPath::new("~user/file").as_resolved(),
+            PathBuf::from("~user/file")
+        );
+    });
+}
@@ -0,0 +1,34 @@
+use cr_program_settings::location::SettingsLocation;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings_derive::settings_path;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_settings_path_macro() {
+    with_temp_settings_dir(|| {
+        let location = settings_path!("cr_program_settings_path_macro", "config.toml");
+        assert_eq!(
+            location,
+            SettingsLocation::new("cr_program_settings_path_macro", "config.toml")
+        );
+        assert!(!location.exists());
+        location.save(&TestStruct { a: 5 }).unwrap();
+        assert!(location.exists());
+        assert!(location
+            .path()
+            .ends_with("cr_program_settings_path_macro/config.toml"));
+        assert_eq!(location.load::<TestStruct>().unwrap(), TestStruct { a: 5 });
+        location.delete().unwrap();
+        assert!(!location.exists());
+
+        // the folder defaults to the crate name, like the declarative macros
+        let location = settings_path!("config.toml");
+        assert_eq!(location.folder(), env!("CARGO_CRATE_NAME"));
+        assert_eq!(location.file_name(), "config.toml");
+    });
+}
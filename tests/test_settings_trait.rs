@@ -0,0 +1,55 @@
+use cr_program_settings::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+struct WindowSettings {
+    width: u32,
+    height: u32,
+}
+
+impl Settings for WindowSettings {
+    const FOLDER: &'static str = "cr_program_settings_trait_test";
+    const FILE_NAME: &'static str = "window.toml";
+}
+
+#[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+struct AudioSettings {
+    volume: f32,
+    muted: bool,
+}
+
+impl Settings for AudioSettings {
+    const FOLDER: &'static str = "cr_program_settings_trait_test";
+    const FILE_NAME: &'static str = "audio.toml";
+}
+
+/// Generic framework code that can persist any settings type
+fn persist<T: Settings>(settings: &T) {
+    settings.save().unwrap();
+    assert!(T::exists());
+}
+
+#[test]
+fn test_settings_types_share_folder() {
+    let window = WindowSettings {
+        width: 1920,
+        height: 1080,
+    };
+    let audio = AudioSettings {
+        volume: 0.5,
+        muted: false,
+    };
+
+    persist(&window);
+    persist(&audio);
+
+    assert_eq!(WindowSettings::load().unwrap(), window);
+    assert_eq!(AudioSettings::load().unwrap(), audio);
+
+    AudioSettings::delete().unwrap();
+    assert!(!AudioSettings::exists());
+    assert_eq!(AudioSettings::load_or_default(), AudioSettings::default());
+    assert_eq!(WindowSettings::load_or_default(), window);
+
+    delete_settings_folder(WindowSettings::FOLDER).unwrap();
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "signing")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::signing::{load_settings_signed, save_settings_signed};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_signed_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_signing";
+        let key = b"signing key";
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+
+        save_settings_signed(crate_name, "signed.ser", &TestStruct { a: 1 }, key).unwrap();
+        assert!(settings_dir.join("signed.ser.sig").is_file());
+        assert_eq!(
+            load_settings_signed::<TestStruct>(crate_name, "signed.ser", key).unwrap(),
+            TestStruct { a: 1 }
+        );
+
+        // an edit made outside the program
+        std::fs::write(settings_dir.join("signed.ser"), "a = 2\n").unwrap();
+        assert!(matches!(
+            load_settings_signed::<TestStruct>(crate_name, "signed.ser", key),
+            Err(LoadSettingsError::SignatureInvalid)
+        ));
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "signed.ser").unwrap(),
+            TestStruct { a: 2 }
+        );
+
+        // a garbled signature is invalid rather than an error
+        std::fs::write(settings_dir.join("signed.ser.sig"), "not hex").unwrap();
+        assert!(matches!(
+            load_settings_signed::<TestStruct>(crate_name, "signed.ser", key),
+            Err(LoadSettingsError::SignatureInvalid)
+        ));
+
+        std::fs::remove_file(settings_dir.join("signed.ser.sig")).unwrap();
+        assert!(matches!(
+            load_settings_signed::<TestStruct>(crate_name, "signed.ser", key),
+            Err(LoadSettingsError::SignatureMissing)
+        ));
+    });
+}
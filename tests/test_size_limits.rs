@@ -0,0 +1,162 @@
+use cr_program_settings::backend::{reset_backend, set_backend, MemoryBackend};
+use cr_program_settings::options::{load_settings_with_options, LoadOptions};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{
+    max_settings_file_size, reset_max_settings_file_size, set_max_settings_file_size,
+    DEFAULT_MAX_SETTINGS_FILE_SIZE,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct LimitedSettings {
+    values: Vec<u32>,
+}
+
+/// Saves and loads settings around the limits, for whichever backend is active
+fn assert_limits(crate_name: &str) {
+    let settings = LimitedSettings {
+        values: (0..100).collect(),
+    };
+    let size = toml::to_string_pretty(&settings).unwrap().len() as u64;
+
+    // the limit is inclusive
+    save_settings_with_limit(crate_name, "limited.ser", &settings, size).unwrap();
+    assert_eq!(
+        load_settings_with_limit::<LimitedSettings>(crate_name, "limited.ser", size).unwrap(),
+        settings
+    );
+
+    let bigger = LimitedSettings {
+        values: (0..1000).collect(),
+    };
+    match save_settings_with_limit(crate_name, "limited.ser", &bigger, size) {
+        Err(SaveSettingsError::FileTooLarge {
+            size: bigger_size,
+            limit,
+            path,
+        }) => {
+            assert!(bigger_size > size);
+            assert_eq!(limit, size);
+            assert!(path.ends_with("limited.ser"));
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    // nothing was written
+    assert_eq!(
+        load_settings_with_filename::<LimitedSettings>(crate_name, "limited.ser").unwrap(),
+        settings
+    );
+
+    match load_settings_with_limit::<LimitedSettings>(crate_name, "limited.ser", size - 1) {
+        Err(LoadSettingsError::FileTooLarge {
+            size: file_size,
+            limit,
+            path,
+        }) => {
+            assert_eq!(file_size, size);
+            assert_eq!(limit, size - 1);
+            assert!(path.ends_with("limited.ser"));
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let err =
+        load_settings_with_limit::<LimitedSettings>(crate_name, "missing.ser", size).unwrap_err();
+    assert!(
+        matches!(err, LoadSettingsError::IOError(err) if err.kind() == std::io::ErrorKind::NotFound)
+    );
+}
+
+// swaps the global backend, so both backends are checked in a single test
+#[test]
+fn test_size_limits() {
+    with_temp_settings_dir(|| {
+        assert_limits("cr_program_settings_limits");
+
+        // a huge sparse file is rejected from its size alone
+        let huge_path = get_user_home()
+            .unwrap()
+            .join("cr_program_settings_limits")
+            .join("huge.ser");
+        let huge_file = std::fs::File::create(&huge_path).unwrap();
+        huge_file.set_len(8 * 1024 * 1024 * 1024).unwrap();
+        drop(huge_file);
+        assert!(matches!(
+            load_settings_with_limit::<LimitedSettings>(
+                "cr_program_settings_limits",
+                "huge.ser",
+                1024 * 1024
+            ),
+            Err(LoadSettingsError::FileTooLarge { size, .. }) if size == 8 * 1024 * 1024 * 1024
+        ));
+
+        // every load has a limit, so the huge file is never allocated
+        assert_eq!(max_settings_file_size(), DEFAULT_MAX_SETTINGS_FILE_SIZE);
+        assert!(matches!(
+            load_settings_with_filename::<LimitedSettings>("cr_program_settings_limits", "huge.ser"),
+            Err(LoadSettingsError::FileTooLarge { limit, .. }) if limit == DEFAULT_MAX_SETTINGS_FILE_SIZE
+        ));
+        assert!(matches!(
+            load_settings_with_options::<LimitedSettings>(
+                "cr_program_settings_limits",
+                "huge.ser",
+                &LoadOptions::new().max_file_size(1024)
+            ),
+            Err(LoadSettingsError::FileTooLarge { limit: 1024, .. })
+        ));
+
+        // the global limit applies to every load unless the options set their own
+        set_max_settings_file_size(8);
+        assert!(matches!(
+            load_settings_with_filename::<LimitedSettings>(
+                "cr_program_settings_limits",
+                "limited.ser"
+            ),
+            Err(LoadSettingsError::FileTooLarge { limit: 8, .. })
+        ));
+        assert!(load_settings_with_options::<LimitedSettings>(
+            "cr_program_settings_limits",
+            "limited.ser",
+            &LoadOptions::new().max_file_size(1024 * 1024)
+        )
+        .is_ok());
+        reset_max_settings_file_size();
+        assert!(load_settings_with_filename::<LimitedSettings>(
+            "cr_program_settings_limits",
+            "limited.ser"
+        )
+        .is_ok());
+
+        // a directory is not a file
+        std::fs::create_dir_all(huge_path.with_file_name("folder.ser")).unwrap();
+        assert!(matches!(
+            load_settings_with_limit::<LimitedSettings>(
+                "cr_program_settings_limits",
+                "folder.ser",
+                1024
+            ),
+            Err(LoadSettingsError::PathIsDirectory(_))
+        ));
+
+        let mut small_file = std::fs::File::create(huge_path.with_file_name("small.ser")).unwrap();
+        small_file.write_all(b"values = [1, 2, 3]\n").unwrap();
+        drop(small_file);
+        assert_eq!(
+            load_settings_with_limit::<LimitedSettings>(
+                "cr_program_settings_limits",
+                "small.ser",
+                1024
+            )
+            .unwrap(),
+            LimitedSettings {
+                values: vec![1, 2, 3]
+            }
+        );
+    });
+
+    set_backend(Box::new(MemoryBackend::new()));
+    assert_limits("cr_program_settings_limits_memory");
+    reset_backend();
+}
@@ -0,0 +1,58 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::profiles::save_profile;
+use cr_program_settings::stats::{settings_stats, settings_stats_recursive, SettingsStats};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Sized {
+    data: String,
+}
+
+#[test]
+fn test_settings_stats() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_stats";
+        assert!(settings_stats(crate_name).is_err());
+
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::create_dir_all(&settings_dir).unwrap();
+        std::fs::write(settings_dir.join("a.ser"), vec![b'a'; 1000]).unwrap();
+        std::fs::write(settings_dir.join("b.ser"), vec![b'b'; 2048]).unwrap();
+        let stats = settings_stats(crate_name).unwrap();
+        assert_eq!(
+            stats,
+            SettingsStats {
+                file_count: 2,
+                total_bytes: 3048
+            }
+        );
+        assert_eq!(stats.to_string(), "3.0 KB across 2 files");
+
+        // profiles live in a subfolder, which only the recursive stats include
+        save_profile(
+            crate_name,
+            "work",
+            &Sized {
+                data: "x".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(settings_stats(crate_name).unwrap(), stats);
+        let recursive = settings_stats_recursive(crate_name).unwrap();
+        assert_eq!(recursive.file_count, 3);
+        assert_eq!(recursive.total_bytes, 3048 + "data = \"x\"\n".len() as u64);
+    });
+}
+
+#[test]
+fn test_settings_stats_display() {
+    let stats = |total_bytes| SettingsStats {
+        file_count: 1,
+        total_bytes,
+    };
+    assert_eq!(stats(0).to_string(), "0 B across 1 file");
+    assert_eq!(stats(1023).to_string(), "1023 B across 1 file");
+    assert_eq!(stats(1536).to_string(), "1.5 KB across 1 file");
+    assert_eq!(stats(5 * 1024 * 1024).to_string(), "5.0 MB across 1 file");
+}
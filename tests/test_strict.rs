@@ -0,0 +1,63 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::strict::load_settings_strict;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Server {
+    host: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct StrictSettings {
+    volume: u32,
+    nickname: Option<String>,
+    servers: Vec<Server>,
+}
+
+#[test]
+fn test_load_settings_strict() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_strict";
+        let settings = StrictSettings {
+            volume: 5,
+            nickname: Some("cory".to_string()),
+            servers: vec![Server {
+                host: "example.com".to_string(),
+            }],
+        };
+
+        // a file written by the library has no unknown keys
+        save_settings_with_filename(crate_name, "strict.ser", &settings).unwrap();
+        let (loaded, unknown_keys) =
+            load_settings_strict::<StrictSettings>(crate_name, "strict.ser").unwrap();
+        assert_eq!(loaded, settings);
+        assert!(unknown_keys.is_empty());
+
+        let path = get_user_home().unwrap().join(crate_name).join("strict.ser");
+        std::fs::write(
+            &path,
+            "volume = 5\nvolme = 7\n[[servers]]\nhost = \"example.com\"\nport = 80\n[extra]\nkey = 1\n",
+        )
+        .unwrap();
+        let (loaded, unknown_keys) =
+            load_settings_strict::<StrictSettings>(crate_name, "strict.ser").unwrap();
+        assert_eq!(loaded.volume, 5);
+        assert_eq!(loaded.nickname, None);
+        assert_eq!(
+            unknown_keys,
+            vec![
+                "extra".to_string(),
+                "servers[0].port".to_string(),
+                "volme".to_string()
+            ]
+        );
+
+        // parse errors are still errors
+        std::fs::write(&path, "volume = [").unwrap();
+        assert!(matches!(
+            load_settings_strict::<StrictSettings>(crate_name, "strict.ser"),
+            Err(LoadSettingsError::ParseError(_))
+        ));
+    });
+}
@@ -0,0 +1,57 @@
+#![cfg(unix)]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::symlink;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_symlinked_settings_folder() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+        let crate_name = "cr_program_settings_symlinked";
+        let link = home.join(crate_name);
+        let target = home.join("symlink_target");
+        std::fs::create_dir_all(&target).unwrap();
+        symlink(&target, &link).unwrap();
+
+        // saving and loading go through the link
+        save_settings_with_filename(crate_name, "file.ser", &TestStruct { a: 1 }).unwrap();
+        assert!(target.join("file.ser").is_file());
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "file.ser").unwrap(),
+            TestStruct { a: 1 }
+        );
+
+        // deleting removes only the link
+        delete_settings_folder(crate_name).unwrap();
+        assert!(std::fs::symlink_metadata(&link).is_err());
+        assert!(target.join("file.ser").is_file());
+        assert!(!SETTINGS_PATHS
+            .read()
+            .unwrap()
+            .contains(&link.join("file.ser")));
+
+        symlink(&target, &link).unwrap();
+        assert!(delete_settings_folder_if_exists(crate_name).unwrap());
+        assert!(std::fs::symlink_metadata(&link).is_err());
+        assert!(target.join("file.ser").is_file());
+
+        // a dangling link is removed as well
+        let dangling = home.join("cr_program_settings_dangling");
+        symlink(home.join("missing_target"), &dangling).unwrap();
+        delete_settings_folder("cr_program_settings_dangling").unwrap();
+        assert!(std::fs::symlink_metadata(&dangling).is_err());
+
+        // following the link deletes the folder it points to
+        symlink(&target, &link).unwrap();
+        delete_settings_folder_with_symlinks(crate_name, true).unwrap();
+        assert!(std::fs::symlink_metadata(&link).is_err());
+        assert!(!target.exists());
+    });
+}
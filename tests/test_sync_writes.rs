@@ -0,0 +1,43 @@
+use cr_program_settings::backend::{set_sync_writes, sync_writes};
+use cr_program_settings::batch::{save_settings_batch, BatchItem};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    a: u32,
+}
+
+#[test]
+fn test_sync_writes() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_sync_writes";
+        for enabled in [true, false] {
+            set_sync_writes(enabled);
+            assert_eq!(sync_writes(), enabled);
+            save_settings_with_filename(crate_name, "synced.ser", &TestStruct { a: 1 }).unwrap();
+            save_settings_new(
+                crate_name,
+                &format!("new_{}.ser", enabled),
+                &TestStruct { a: 2 },
+            )
+            .unwrap();
+            save_settings_batch(vec![BatchItem::new(
+                crate_name,
+                "batch.ser",
+                &TestStruct { a: 3 },
+            )])
+            .unwrap();
+            assert_eq!(
+                load_settings_with_filename::<TestStruct>(crate_name, "synced.ser").unwrap(),
+                TestStruct { a: 1 }
+            );
+            assert_eq!(
+                load_settings_with_filename::<TestStruct>(crate_name, "batch.ser").unwrap(),
+                TestStruct { a: 3 }
+            );
+        }
+        set_sync_writes(true);
+    });
+}
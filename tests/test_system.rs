@@ -0,0 +1,97 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::system::{
+    get_system_settings_dir, load_settings_with_system_defaults, reset_system_settings_root,
+    set_system_settings_root,
+};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Proxy {
+    host: String,
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct SystemSettings {
+    telemetry: bool,
+    proxy: Proxy,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UserProxy {
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UserSettings {
+    proxy: UserProxy,
+}
+
+#[test]
+fn test_load_settings_with_system_defaults() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_system";
+        let file_name = "system.ser";
+        let system_root = get_user_home().unwrap().join("system_root");
+        set_system_settings_root(&system_root);
+        let system_dir = get_system_settings_dir(crate_name);
+        assert_eq!(system_dir, system_root.join(crate_name));
+
+        // missing both files is an error
+        match load_settings_with_system_defaults::<SystemSettings>(crate_name, file_name) {
+            Err(LoadSettingsError::IOError(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // the system file alone is enough
+        std::fs::create_dir_all(&system_dir).unwrap();
+        std::fs::write(
+            system_dir.join(file_name),
+            "telemetry = false\n[proxy]\nhost = \"proxy.corp\"\nport = 8080\n",
+        )
+        .unwrap();
+        let expected = SystemSettings {
+            telemetry: false,
+            proxy: Proxy {
+                host: "proxy.corp".to_string(),
+                port: 8080,
+            },
+        };
+        assert_eq!(
+            load_settings_with_system_defaults::<SystemSettings>(crate_name, file_name).unwrap(),
+            expected
+        );
+
+        // the user file is merged over the system file
+        save_settings_with_filename(
+            crate_name,
+            file_name,
+            &UserSettings {
+                proxy: UserProxy { port: 3128 },
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            load_settings_with_system_defaults::<SystemSettings>(crate_name, file_name).unwrap(),
+            SystemSettings {
+                telemetry: false,
+                proxy: Proxy {
+                    host: "proxy.corp".to_string(),
+                    port: 3128,
+                },
+            }
+        );
+
+        // a malformed system file is reported rather than skipped
+        std::fs::write(system_dir.join(file_name), "telemetry = [").unwrap();
+        assert!(matches!(
+            load_settings_with_system_defaults::<SystemSettings>(crate_name, file_name),
+            Err(LoadSettingsError::DeserializationError(_))
+        ));
+
+        reset_system_settings_root();
+    });
+}
@@ -0,0 +1,119 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::template::{write_template_settings, write_template_settings_with_docs};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Server {
+    host: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    name: String,
+    motd: String,
+    recent: Vec<u32>,
+    servers: Vec<Server>,
+}
+
+impl Default for TestStruct {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            motd: "welcome\nrecent = 1\nenjoy".to_string(),
+            recent: vec![1, 2],
+            servers: vec![
+                Server {
+                    host: "a".to_string(),
+                },
+                Server {
+                    host: "b".to_string(),
+                },
+            ],
+        }
+    }
+}
+
+#[test]
+fn test_write_template_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_template";
+        let path = write_template_settings::<TestStruct>(crate_name, "plain.toml").unwrap();
+        assert_eq!(
+            path,
+            get_user_home().unwrap().join(crate_name).join("plain.toml")
+        );
+        // without docs the template is the same as a plain save of the defaults
+        save_settings_with_filename(crate_name, "saved.toml", &TestStruct::default()).unwrap();
+        assert_eq!(
+            read_settings_string(crate_name, "plain.toml").unwrap(),
+            read_settings_string(crate_name, "saved.toml").unwrap()
+        );
+
+        // an existing file is never replaced
+        save_settings_with_filename(
+            crate_name,
+            "plain.toml",
+            &TestStruct {
+                name: "edited".to_string(),
+                ..TestStruct::default()
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            write_template_settings::<TestStruct>(crate_name, "plain.toml"),
+            Err(SaveSettingsError::AlreadyExists(_))
+        ));
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "plain.toml")
+                .unwrap()
+                .name,
+            "edited"
+        );
+    });
+}
+
+#[test]
+fn test_template_field_docs() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_template_docs";
+        let field_docs = [
+            ("name", "The name shown in the title bar\n\nCan be empty"),
+            ("recent", "Recently opened files"),
+            ("servers", "Servers to connect to"),
+            ("servers.host", "Host name or address"),
+            ("missing", "Not a field"),
+        ];
+        write_template_settings_with_docs::<TestStruct>(crate_name, "docs.toml", &field_docs)
+            .unwrap();
+        let template = read_settings_string(crate_name, "docs.toml").unwrap();
+
+        assert!(template.starts_with(
+            "# The name shown in the title bar\n#\n# Can be empty\nname = \"default\"\n"
+        ));
+        assert!(template.contains("# Recently opened files\nrecent = [\n"));
+        // array elements and the lines of multi-line strings are not keys
+        assert_eq!(template.matches("# Recently opened files").count(), 1);
+        assert!(!template.contains("# Recently opened files\nrecent = 1"));
+        // tables of an array are documented once
+        assert_eq!(
+            template
+                .matches("# Servers to connect to\n[[servers]]")
+                .count(),
+            1
+        );
+        assert_eq!(
+            template
+                .matches("# Host name or address\nhost = \"a\"")
+                .count(),
+            1
+        );
+        assert!(!template.contains("# Host name or address\nhost = \"b\""));
+        assert!(!template.contains("Not a field"));
+
+        assert_eq!(
+            load_settings_with_filename::<TestStruct>(crate_name, "docs.toml").unwrap(),
+            TestStruct::default()
+        );
+    });
+}
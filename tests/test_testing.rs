@@ -0,0 +1,86 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::{with_temp_settings_dir, TempSettingsDir};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestStruct {
+    thread: u32,
+}
+
+#[test]
+fn test_fixtures_in_parallel_threads() {
+    let real_home = get_user_home();
+    let barrier = Arc::new(Barrier::new(2));
+
+    let handles: Vec<_> = (0..2)
+        .map(|thread| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                with_temp_settings_dir(|| {
+                    let crate_name = "cr_program_settings_fixture";
+                    save_settings(crate_name, &TestStruct { thread }).unwrap();
+                    // both threads have saved to the same crate and file name before either loads
+                    barrier.wait();
+                    assert_eq!(
+                        load_settings::<TestStruct>(crate_name).unwrap(),
+                        TestStruct { thread }
+                    );
+                    get_user_home().unwrap()
+                })
+            })
+        })
+        .collect();
+
+    let temp_dirs: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_ne!(temp_dirs[0], temp_dirs[1]);
+    assert!(temp_dirs.iter().all(|dir| !dir.exists()));
+    assert!(!SETTINGS_PATHS
+        .read()
+        .unwrap()
+        .iter()
+        .any(|path| temp_dirs.iter().any(|dir| path.starts_with(dir))));
+    assert_eq!(get_user_home(), real_home);
+}
+
+#[test]
+fn test_fixture_restores_after_panic() {
+    let real_home = get_user_home();
+    let mut temp_dir = None;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        with_temp_settings_dir(|| {
+            temp_dir = get_user_home();
+            save_settings("cr_program_settings_fixture", &TestStruct { thread: 0 }).unwrap();
+            panic!("test panic inside fixture");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(get_user_home(), real_home);
+    assert!(!temp_dir.unwrap().exists());
+}
+
+#[test]
+fn test_temp_settings_dir_guard() {
+    let real_home = get_user_home();
+    let outer = TempSettingsDir::new();
+    assert_eq!(get_user_home().unwrap(), outer.path());
+    save_settings("cr_program_settings_fixture", &TestStruct { thread: 1 }).unwrap();
+
+    // guards nest, restoring the outer directory when the inner one is dropped
+    let inner = TempSettingsDir::new();
+    let inner_path = inner.path().to_path_buf();
+    assert_ne!(inner_path, outer.path());
+    assert!(load_settings::<TestStruct>("cr_program_settings_fixture").is_err());
+    drop(inner);
+    assert!(!inner_path.exists());
+    assert_eq!(
+        load_settings::<TestStruct>("cr_program_settings_fixture").unwrap(),
+        TestStruct { thread: 1 }
+    );
+
+    let outer_path = outer.path().to_path_buf();
+    drop(outer);
+    assert!(!outer_path.exists());
+    assert_eq!(get_user_home(), real_home);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "tracing")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+use tracing_test::traced_test;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TracedSettings {
+    value: u32,
+}
+
+#[traced_test]
+#[test]
+fn test_tracing_events() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_tracing";
+        save_settings_with_filename(crate_name, "traced.ser", &TracedSettings { value: 1 })
+            .unwrap();
+        load_settings_with_filename::<TracedSettings>(crate_name, "traced.ser").unwrap();
+        delete_setting_file(crate_name, "traced.ser").unwrap();
+        assert!(load_settings_with_filename::<TracedSettings>(crate_name, "traced.ser").is_err());
+
+        let path = get_user_home().unwrap().join(crate_name).join("traced.ser");
+        let path_field = format!("path={}", path.display());
+        logs_assert(|lines: &[&str]| {
+            let has = |level: &str, operation: &str| {
+                lines.iter().any(|line| {
+                    line.contains(level)
+                        && line.contains(&format!("operation=\"{}\"", operation))
+                        && line.contains(&path_field)
+                })
+            };
+            for (level, operation) in [
+                ("DEBUG", "save"),
+                ("DEBUG", "load"),
+                ("DEBUG", "delete"),
+                ("WARN", "load"),
+            ] {
+                if !has(level, operation) {
+                    return Err(format!("no {} event for {}", level, operation));
+                }
+            }
+            Ok(())
+        });
+        // saves and loads record the size of the file
+        assert!(logs_contain("size=10"));
+        assert!(logs_contain("elapsed_us="));
+        assert!(logs_contain("error="));
+    });
+}
+
+#[traced_test]
+#[test]
+fn test_tracing_if_exists_deletes() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_tracing_if_exists";
+        save_settings_with_filename(crate_name, "traced.ser", &TracedSettings { value: 1 })
+            .unwrap();
+        assert!(delete_setting_file_if_exists(crate_name, "traced.ser").unwrap());
+        assert!(delete_settings_folder_if_exists(crate_name).unwrap());
+        // a missing file is still traced
+        assert!(!delete_setting_file_if_exists(crate_name, "missing.ser").unwrap());
+
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        logs_assert(|lines: &[&str]| {
+            for path in [
+                settings_dir.join("traced.ser"),
+                settings_dir.clone(),
+                settings_dir.join("missing.ser"),
+            ] {
+                // the path is followed by the next field, so a folder does not match the files inside it
+                let path_field = format!("path={} ", path.display());
+                if !lines.iter().any(|line| {
+                    line.contains("DEBUG")
+                        && line.contains("operation=\"delete\"")
+                        && line.contains(&path_field)
+                }) {
+                    return Err(format!("no delete event for {}", path.display()));
+                }
+            }
+            Ok(())
+        });
+    });
+}
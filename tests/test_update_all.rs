@@ -0,0 +1,94 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::profiles::{
+    list_profiles, load_profile, save_profile, update_all_profiles,
+};
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Account {
+    name: String,
+    token: Option<String>,
+}
+
+#[test]
+fn test_update_all_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_update_all";
+        for name in ["a", "b", "c"] {
+            let account = Account {
+                name: name.to_string(),
+                token: Some(format!("{}-token", name)),
+            };
+            save_settings_with_filename(crate_name, format!("{}.ser", name), &account).unwrap();
+        }
+        let settings_dir = get_user_home().unwrap().join(crate_name);
+        std::fs::write(settings_dir.join("broken.ser"), "name = [not toml").unwrap();
+        std::fs::write(settings_dir.join("other.ser"), "unrelated = true\n").unwrap();
+
+        let mut updated = vec![];
+        let results = update_all_settings::<Account>(crate_name, |account| {
+            updated.push(account.name.clone());
+            account.token = None;
+        });
+        assert_eq!(updated, vec!["a", "b", "c"]);
+
+        // files that fail to load are reported and left untouched
+        assert_eq!(results.len(), 5);
+        for (path, result) in &results {
+            let file_name = path.file_name().unwrap().to_str().unwrap();
+            match file_name {
+                "broken.ser" | "other.ser" => assert!(
+                    matches!(
+                        result,
+                        Err(SettingsError::Load(LoadSettingsError::ParseError(_)))
+                    ),
+                    "{:?}",
+                    result
+                ),
+                _ => assert!(result.is_ok(), "{:?}", result),
+            }
+        }
+        assert_eq!(
+            std::fs::read_to_string(settings_dir.join("broken.ser")).unwrap(),
+            "name = [not toml"
+        );
+        for name in ["a", "b", "c"] {
+            assert_eq!(
+                load_settings_with_filename::<Account>(crate_name, format!("{}.ser", name))
+                    .unwrap(),
+                Account {
+                    name: name.to_string(),
+                    token: None
+                }
+            );
+        }
+
+        assert!(
+            update_all_settings::<Account>("cr_program_settings_never_saved", |_| {}).is_empty()
+        );
+    });
+}
+
+#[test]
+fn test_update_all_profiles() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_update_profiles";
+        for name in ["work", "home"] {
+            let account = Account {
+                name: name.to_string(),
+                token: Some("leaked".to_string()),
+            };
+            save_profile(crate_name, name, &account).unwrap();
+        }
+
+        let results = update_all_profiles::<Account>(crate_name, |account| account.token = None);
+        assert_eq!(results.len(), 2);
+        for name in list_profiles(crate_name).unwrap() {
+            assert_eq!(
+                load_profile::<Account>(crate_name, &name).unwrap().token,
+                None
+            );
+        }
+    });
+}
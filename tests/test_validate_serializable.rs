@@ -0,0 +1,69 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::assert_settings_roundtrip;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct TestStruct {
+    a: u32,
+    name: Option<String>,
+    values: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Skipped {
+    a: u32,
+    #[serde(skip)]
+    cache: u32,
+}
+
+#[test]
+fn test_validate_serializable() {
+    validate_serializable(&TestStruct::default()).unwrap();
+    validate_serializable(&HashMap::from([("a".to_string(), 1)])).unwrap();
+
+    // values above i64::MAX can not be written as TOML integers
+    let overflow = TestStruct {
+        values: vec![u64::MAX],
+        ..TestStruct::default()
+    };
+    assert!(matches!(
+        validate_serializable(&overflow),
+        Err(SaveSettingsError::SerializationError(_))
+    ));
+
+    assert!(matches!(
+        validate_serializable(&HashMap::from([(1, 1)])),
+        Err(SaveSettingsError::SerializationError(_))
+    ));
+    assert!(matches!(
+        validate_serializable(&None::<TestStruct>),
+        Err(SaveSettingsError::SerializationError(_))
+    ));
+    assert!(matches!(
+        validate_serializable(&7u32),
+        Err(SaveSettingsError::RootNotATable(_))
+    ));
+}
+
+#[test]
+fn test_assert_settings_roundtrip() {
+    assert_settings_roundtrip(&TestStruct {
+        a: 1,
+        name: Some("name".to_string()),
+        values: vec![1, 2],
+    });
+    assert_settings_roundtrip(&Skipped { a: 1, cache: 0 });
+}
+
+#[test]
+#[should_panic(expected = "settings changed after saving and loading them again")]
+fn test_assert_settings_roundtrip_changed() {
+    assert_settings_roundtrip(&Skipped { a: 1, cache: 2 });
+}
+
+#[test]
+#[should_panic(expected = "settings can not be serialized")]
+fn test_assert_settings_roundtrip_unserializable() {
+    assert_settings_roundtrip(&HashMap::from([(1, 1)]));
+}
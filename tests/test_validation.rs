@@ -0,0 +1,93 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct PortSettings {
+    port: u16,
+    name: String,
+}
+
+impl Validate for PortSettings {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut messages = vec![];
+        if self.port < 1024 {
+            messages.push(format!("port {} is reserved", self.port));
+        }
+        if self.name.is_empty() {
+            messages.push("name must not be empty".to_string());
+        }
+        if messages.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::from_messages(messages))
+        }
+    }
+}
+
+#[test]
+fn test_load_invalid_file_untouched() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_validation";
+        let file_name = "invalid.ser";
+        let invalid = PortSettings {
+            port: 80,
+            name: "".to_string(),
+        };
+        // write the invalid settings without validation, as if a user edited the file by hand
+        save_settings_with_filename(crate_name, file_name, &invalid).unwrap();
+        let path = get_user_home().unwrap().join(crate_name).join(file_name);
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        match load_settings_validated::<PortSettings>(crate_name, file_name) {
+            Err(LoadSettingsError::ValidationFailed(err)) => {
+                assert_eq!(err.messages().len(), 2);
+                assert!(err.to_string().contains("port 80 is reserved"));
+            }
+            other => panic!("expected a validation failure, got {:?}", other),
+        }
+
+        assert_eq!(before, std::fs::read_to_string(&path).unwrap());
+
+        delete_settings_folder(crate_name).unwrap();
+    });
+}
+
+#[test]
+fn test_load_with_validator() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_validation";
+        let file_name = "validator.ser";
+        let settings = PortSettings {
+            port: 8080,
+            name: "".to_string(),
+        };
+        save_settings_with_filename(crate_name, file_name, &settings).unwrap();
+
+        // the closure is used instead of the `Validate` impl
+        let loaded = load_settings_with_validator::<PortSettings, _>(crate_name, file_name, |s| {
+            if s.port == 8080 {
+                Ok(())
+            } else {
+                Err("port must be 8080".to_string())
+            }
+        })
+        .unwrap();
+        assert_eq!(loaded, settings);
+
+        match load_settings_with_validator::<PortSettings, _>(crate_name, file_name, |s| {
+            if s.name.is_empty() {
+                Err("name must not be empty".to_string())
+            } else {
+                Ok(())
+            }
+        }) {
+            Err(LoadSettingsError::ValidationFailed(err)) => {
+                assert_eq!(err.messages(), ["name must not be empty"])
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        delete_setting_file(crate_name, file_name).unwrap();
+    });
+}
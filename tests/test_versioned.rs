@@ -0,0 +1,80 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::versioned::{
+    list_settings_versions, load_settings_current_version, load_settings_versioned,
+    reset_settings_version, save_settings_current_version, save_settings_versioned,
+    set_settings_version, settings_version,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct VersionedSettings {
+    value: u32,
+}
+
+// the configured version is global, so everything is checked in a single test
+#[test]
+fn test_versioned_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_versioned";
+        let file_name = "settings.ser";
+        assert_eq!(
+            list_settings_versions(crate_name).unwrap(),
+            Vec::<u32>::new()
+        );
+
+        save_settings_versioned(crate_name, 10, file_name, &VersionedSettings { value: 10 })
+            .unwrap();
+        save_settings_versioned(crate_name, 2, file_name, &VersionedSettings { value: 2 }).unwrap();
+        let version_dir = get_user_home().unwrap().join(crate_name).join("v2");
+        assert!(version_dir.join(file_name).is_file());
+
+        // unrelated files and folders are not versions
+        save_settings_with_filename(crate_name, "v3", &VersionedSettings { value: 3 }).unwrap();
+        for folder in ["vnext", "v", "v+4", "backup"] {
+            std::fs::create_dir_all(get_user_home().unwrap().join(crate_name).join(folder))
+                .unwrap();
+        }
+        assert_eq!(list_settings_versions(crate_name).unwrap(), vec![2, 10]);
+
+        assert_eq!(
+            load_settings_versioned::<VersionedSettings>(crate_name, 2, file_name).unwrap(),
+            VersionedSettings { value: 2 }
+        );
+        let err =
+            load_settings_versioned::<VersionedSettings>(crate_name, 5, file_name).unwrap_err();
+        assert!(
+            matches!(err, LoadSettingsError::IOError(err) if err.kind() == std::io::ErrorKind::NotFound)
+        );
+
+        // without a configured version the crate folder is used directly
+        assert_eq!(settings_version(), None);
+        save_settings_current_version(crate_name, file_name, &VersionedSettings { value: 0 })
+            .unwrap();
+        assert_eq!(
+            load_settings_with_filename::<VersionedSettings>(crate_name, file_name).unwrap(),
+            VersionedSettings { value: 0 }
+        );
+
+        set_settings_version(10);
+        assert_eq!(settings_version(), Some(10));
+        assert_eq!(
+            load_settings_current_version::<VersionedSettings>(crate_name, file_name).unwrap(),
+            VersionedSettings { value: 10 }
+        );
+        set_settings_version(11);
+        save_settings_current_version(crate_name, file_name, &VersionedSettings { value: 11 })
+            .unwrap();
+        assert_eq!(list_settings_versions(crate_name).unwrap(), vec![2, 10, 11]);
+        assert_eq!(
+            load_settings_versioned::<VersionedSettings>(crate_name, 10, file_name).unwrap(),
+            VersionedSettings { value: 10 }
+        );
+
+        reset_settings_version();
+        assert_eq!(
+            load_settings_current_version::<VersionedSettings>(crate_name, file_name).unwrap(),
+            VersionedSettings { value: 0 }
+        );
+    });
+}
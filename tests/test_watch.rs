@@ -0,0 +1,91 @@
+#![cfg(feature = "watch")]
+
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::watch::{subscribe_settings_changes, watch_settings, SettingsEvent};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct WatchedSettings {
+    value: u32,
+}
+
+#[test]
+fn test_watch_settings() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_watch";
+        let file_name = "watched.ser";
+        let (sender, receiver) = channel();
+        let handle = watch_settings::<WatchedSettings>(crate_name, file_name, move |result| {
+            let _ = sender.send(result.ok());
+        })
+        .unwrap();
+        let settings_file = get_user_home().unwrap().join(crate_name).join(file_name);
+
+        // a burst of writes is reported once with the final value
+        for value in 0..10 {
+            save_settings_with_filename(crate_name, file_name, &WatchedSettings { value }).unwrap();
+        }
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+            Some(WatchedSettings { value: 9 })
+        );
+        assert!(receiver.recv_timeout(Duration::from_millis(500)).is_err());
+
+        // other files in the folder are ignored
+        save_settings_with_filename(crate_name, "other.ser", &WatchedSettings { value: 1 })
+            .unwrap();
+        assert!(receiver.recv_timeout(Duration::from_millis(500)).is_err());
+
+        // malformed content is reported as an error instead of panicking
+        std::fs::write(&settings_file, "value = [this is not toml").unwrap();
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), None);
+
+        handle.stop();
+        save_settings_with_filename(crate_name, file_name, &WatchedSettings { value: 2 }).unwrap();
+        assert!(receiver.recv_timeout(Duration::from_millis(500)).is_err());
+    });
+}
+
+#[test]
+fn test_subscribe_settings_changes() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_subscribe";
+        let file_name = "subscribed.ser";
+        let (handle1, receiver1) =
+            subscribe_settings_changes::<WatchedSettings>(crate_name, file_name).unwrap();
+        let (handle2, receiver2) =
+            subscribe_settings_changes::<WatchedSettings>(crate_name, file_name).unwrap();
+        let settings_file = get_user_home().unwrap().join(crate_name).join(file_name);
+
+        // every subscriber receives each change
+        save_settings_with_filename(crate_name, file_name, &WatchedSettings { value: 5 }).unwrap();
+        for receiver in [&receiver1, &receiver2] {
+            assert!(matches!(
+                receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+                SettingsEvent::Reloaded(WatchedSettings { value: 5 })
+            ));
+        }
+
+        std::fs::write(&settings_file, "value = [this is not toml").unwrap();
+        for receiver in [&receiver1, &receiver2] {
+            assert!(matches!(
+                receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+                SettingsEvent::ParseError(LoadSettingsError::ParseError(_))
+            ));
+        }
+
+        // a stopped subscriber does not affect the others
+        handle2.stop();
+        std::fs::remove_file(&settings_file).unwrap();
+        assert!(matches!(
+            receiver1.recv_timeout(Duration::from_secs(5)).unwrap(),
+            SettingsEvent::Deleted
+        ));
+        assert!(receiver2.recv_timeout(Duration::from_millis(500)).is_err());
+
+        handle1.stop();
+    });
+}
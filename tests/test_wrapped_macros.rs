@@ -0,0 +1,39 @@
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::{reset_default_folder, set_default_folder};
+use serde::{Deserialize, Serialize};
+use settings_wrapper::{delete_wrapped, load_wrapped, save_wrapped};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct AppSettings {
+    a: u32,
+}
+
+// the default folder is global, so both cases live in a single test
+#[test]
+fn test_wrapped_macros_use_application_folder() {
+    with_temp_settings_dir(|| {
+        let home = get_user_home().unwrap();
+
+        // without a default folder, the macros inside the wrapper use the wrappers crate name
+        save_wrapped(&AppSettings { a: 1 }).unwrap();
+        assert!(home.join("settings_wrapper").join("wrapped.ser").is_file());
+        assert!(!home.join(env!("CARGO_CRATE_NAME")).exists());
+        delete_wrapped().unwrap();
+        assert!(!home.join("settings_wrapper").join("wrapped.ser").exists());
+
+        // the application sets its own folder once, and the wrapper follows it
+        set_default_folder("my_app");
+        save_wrapped(&AppSettings { a: 2 }).unwrap();
+        assert!(home.join("my_app").join("wrapped.ser").is_file());
+        assert!(!home.join("settings_wrapper").join("wrapped.ser").exists());
+        assert_eq!(load_wrapped::<AppSettings>().unwrap(), AppSettings { a: 2 });
+        assert_eq!(
+            load_settings_with_filename::<AppSettings>("my_app", "wrapped.ser").unwrap(),
+            AppSettings { a: 2 }
+        );
+        delete_wrapped().unwrap();
+        assert!(!home.join("my_app").join("wrapped.ser").exists());
+        reset_default_folder();
+    });
+}
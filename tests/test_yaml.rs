@@ -0,0 +1,161 @@
+#![cfg(feature = "yaml")]
+
+use cr_program_settings::format::{convert_settings, load_settings_auto, Format};
+use cr_program_settings::prelude::*;
+use cr_program_settings::testing::with_temp_settings_dir;
+use cr_program_settings::yaml::{load_default_settings_yaml, save_default_settings_yaml};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Service {
+    image: String,
+    replicas: u32,
+    env: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Deployment {
+    name: String,
+    services: BTreeMap<String, Service>,
+}
+
+fn deployment() -> Deployment {
+    let service = Service {
+        image: "registry/app:1.0".to_string(),
+        replicas: 2,
+        env: BTreeMap::from([("LOG_LEVEL".to_string(), "info".to_string())]),
+    };
+    Deployment {
+        name: "prod".to_string(),
+        services: BTreeMap::from([
+            ("api".to_string(), service.clone()),
+            ("worker".to_string(), service),
+        ]),
+    }
+}
+
+#[test]
+fn test_yaml_round_trip() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_yaml";
+        save_settings_yaml(crate_name, "deploy.yaml", &deployment()).unwrap();
+        let path = get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("deploy.yaml");
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("name: prod\n"));
+        assert_eq!(
+            load_settings_yaml::<Deployment>(crate_name, "deploy.yaml").unwrap(),
+            deployment()
+        );
+        assert_eq!(
+            load_settings_auto::<Deployment>(crate_name, "deploy.yaml").unwrap(),
+            deployment()
+        );
+
+        let toml_name = convert_settings::<Deployment>(
+            crate_name,
+            "deploy.yaml",
+            Format::Yaml,
+            Format::Toml,
+            true,
+        )
+        .unwrap();
+        assert_eq!(toml_name, "deploy.toml");
+        assert!(!path.exists());
+    });
+}
+
+#[test]
+fn test_yaml_anchors_and_merge_keys() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_yaml_anchors";
+        let file_contents = "\
+name: prod
+services:
+  api: &service
+    image: registry/app:1.0
+    replicas: 2
+    env: &env
+      LOG_LEVEL: info
+  worker:
+    <<: *service
+    replicas: 4
+    env: *env
+";
+        let path = get_user_home().unwrap().join(crate_name).join("deploy.yml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, file_contents).unwrap();
+
+        let loaded = load_settings_auto::<Deployment>(crate_name, "deploy.yml").unwrap();
+        let mut expected = deployment();
+        expected.services.get_mut("worker").unwrap().replicas = 4;
+        assert_eq!(loaded, expected);
+    });
+}
+
+#[test]
+fn test_default_yaml_file() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_yaml_default";
+        assert!(load_default_settings_yaml::<Deployment>(crate_name).is_err());
+
+        // a hand named .yml file is found
+        save_settings_yaml(
+            crate_name,
+            "cr_program_settings_yaml_default.yml",
+            &deployment(),
+        )
+        .unwrap();
+        assert_eq!(
+            load_default_settings_yaml::<Deployment>(crate_name).unwrap(),
+            deployment()
+        );
+
+        // the .yaml file wins when both exist
+        let mut renamed = deployment();
+        renamed.name = "staging".to_string();
+        save_default_settings_yaml(crate_name, &renamed).unwrap();
+        assert!(get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("cr_program_settings_yaml_default.yaml")
+            .exists());
+        assert_eq!(
+            load_default_settings_yaml::<Deployment>(crate_name).unwrap(),
+            renamed
+        );
+    });
+}
+
+#[test]
+fn test_yaml_errors() {
+    with_temp_settings_dir(|| {
+        let crate_name = "cr_program_settings_yaml_errors";
+        let path = get_user_home()
+            .unwrap()
+            .join(crate_name)
+            .join("broken.yaml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let file_contents = "name: prod\nservices: [unclosed\n";
+        std::fs::write(&path, file_contents).unwrap();
+        let err = load_settings_yaml::<Deployment>(crate_name, "broken.yaml").unwrap_err();
+        assert!(matches!(
+            err,
+            LoadSettingsError::YamlDeserializationError(_)
+        ));
+        let location = err.location(file_contents).unwrap();
+        assert_eq!(location.line, 3);
+        assert!(!location.message.contains("at line"));
+
+        std::fs::write(&path, "name: prod\nservices: 5\n").unwrap();
+        assert!(matches!(
+            load_settings_yaml::<Deployment>(crate_name, "broken.yaml"),
+            Err(LoadSettingsError::YamlDeserializationError(_))
+        ));
+    });
+}